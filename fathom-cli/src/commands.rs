@@ -2,3 +2,4 @@ pub mod check;
 pub mod compile;
 pub mod data;
 pub mod doc;
+pub mod explain;
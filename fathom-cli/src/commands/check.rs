@@ -10,6 +10,9 @@ pub struct Options {
     /// Print the elaborated core module.
     #[structopt(long = "emit-core")]
     emit_core: bool,
+    /// Print the item dependency graph of the module as Graphviz DOT.
+    #[structopt(long = "emit-item-graph")]
+    emit_item_graph: bool,
     /// Checks that the core module is well-formed after elaboration.
     #[structopt(long = "validate-core")]
     validate_core: bool,
@@ -22,9 +25,13 @@ pub fn run(options: &crate::Options, command_options: &Options) -> anyhow::Resul
     driver.set_emit_writer(BufferedStandardStream::stdout(options.color));
     driver.set_diagnostic_writer(BufferedStandardStream::stderr(options.color));
 
-    driver.check(&command_options.format_file)?;
+    if command_options.emit_item_graph {
+        driver.emit_item_graph(&command_options.format_file)?;
+    } else {
+        driver.check(&command_options.format_file)?;
+    }
 
-    if !driver.check_diagnostics()? {
+    if !driver.finish()? {
         std::process::exit(exitcode::DATAERR);
     } else {
         std::process::exit(exitcode::OK);
@@ -1,3 +1,4 @@
+use codespan_reporting::term::termcolor::BufferedStandardStream;
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -9,12 +10,16 @@ pub struct Options {
     /// Checks that the core module is well-formed after elaboration
     #[structopt(long = "validate-core")]
     validate_core: bool,
+    /// Inlines trivial aliases, like `const Length : Format = U16Be`, into
+    /// the items that refer to them.
+    #[structopt(long = "inline-aliases")]
+    inline_aliases: bool,
     /// Target to generate
     #[structopt(
         long = "target",
         name = "TARGET",
         case_insensitive = true,
-        possible_values = &["rust"],
+        possible_values = &["rust", "typescript"],
         parse(try_from_str = parse_target),
     )]
     target: Target,
@@ -23,17 +28,32 @@ pub struct Options {
 #[derive(StructOpt, Debug)]
 enum Target {
     Rust,
+    TypeScript,
 }
 
 fn parse_target(src: &str) -> Result<Target, &'static str> {
     match () {
         () if src.eq_ignore_ascii_case("rust") => Ok(Target::Rust),
-        () => Err("valid values: rust"),
+        () if src.eq_ignore_ascii_case("typescript") => Ok(Target::TypeScript),
+        () => Err("valid values: rust, typescript"),
     }
 }
 
-pub fn run(_options: &crate::Options, command_options: &Options) -> anyhow::Result<()> {
+pub fn run(options: &crate::Options, command_options: &Options) -> anyhow::Result<()> {
+    let mut driver = fathom::driver::Driver::new();
+    driver.set_validate_core(command_options.validate_core);
+    driver.set_inline_aliases(command_options.inline_aliases);
+    driver.set_emit_writer(BufferedStandardStream::stdout(options.color));
+    driver.set_diagnostic_writer(BufferedStandardStream::stderr(options.color));
+
     match command_options.target {
-        Target::Rust => Err(anyhow::anyhow!("error: not yet implemented")),
+        Target::Rust => return Err(anyhow::anyhow!("error: not yet implemented")),
+        Target::TypeScript => driver.emit_typescript(&command_options.format_file)?,
+    }
+
+    if !driver.finish()? {
+        std::process::exit(exitcode::DATAERR);
+    } else {
+        std::process::exit(exitcode::OK);
     }
 }
@@ -1,4 +1,5 @@
 use codespan_reporting::term::termcolor::BufferedStandardStream;
+use fathom::lang::core::binary::Endianness;
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -21,6 +22,31 @@ pub struct Options {
     /// The item name to begin reading from
     #[structopt(long = "item-name", default_value = "Main")]
     item_name: String,
+    /// Only read the field at the given dot-separated path, eg. `header.version`
+    #[structopt(long = "field-path")]
+    field_path: Option<String>,
+    /// Write the data back after reading it, checking that the original bytes are reproduced
+    #[structopt(long = "round-trip", conflicts_with = "field-path")]
+    round_trip: bool,
+    /// Read the binary file as a sequence of records of the item, emitting each one as it is read
+    #[structopt(long = "records", conflicts_with_all = &["field-path", "round-trip"])]
+    records: bool,
+    /// The byte order to use for integer formats that do not specify one, eg. `U16`
+    #[structopt(
+        long = "default-endianness",
+        name = "ENDIANNESS",
+        default_value = "big",
+        possible_values = &["big", "little"],
+        parse(try_from_str = parse_endianness),
+    )]
+    default_endianness: Endianness,
+    /// The maximum number of formats to read for the item, to guard against pathological inputs
+    #[structopt(long = "step-limit", name = "STEPS")]
+    step_limit: Option<usize>,
+    /// Report how much work the evaluator did while reading, for diagnosing performance problems
+    #[cfg(feature = "eval-stats")]
+    #[structopt(long = "eval-stats")]
+    eval_stats: bool,
     /// The binary file to read
     #[structopt(name = "BINARY-PATH", parse(from_os_str))]
     binary_file: PathBuf, // TODO: parse multiple binary files
@@ -29,18 +55,48 @@ pub struct Options {
 pub fn run(options: &crate::Options, command_options: &Options) -> anyhow::Result<()> {
     let mut driver = fathom::driver::Driver::new();
     driver.set_validate_core(command_options.validate_core);
+    driver.set_default_endianness(command_options.default_endianness);
+    driver.set_read_step_limit(command_options.step_limit);
+    #[cfg(feature = "eval-stats")]
+    driver.set_count_eval_stats(command_options.eval_stats);
     driver.set_emit_writer(BufferedStandardStream::stdout(options.color));
     driver.set_diagnostic_writer(BufferedStandardStream::stderr(options.color));
 
-    driver.read_data(
-        &command_options.format_file,
-        &command_options.item_name,
-        &command_options.binary_file,
-    )?;
+    match &command_options.field_path {
+        None if command_options.round_trip => driver.write_format(
+            &command_options.format_file,
+            &command_options.item_name,
+            &command_options.binary_file,
+        )?,
+        None if command_options.records => driver.read_records(
+            &command_options.format_file,
+            &command_options.item_name,
+            &command_options.binary_file,
+        )?,
+        None => driver.read_data(
+            &command_options.format_file,
+            &command_options.item_name,
+            &command_options.binary_file,
+        )?,
+        Some(field_path) => driver.read_field(
+            &command_options.format_file,
+            &command_options.item_name,
+            field_path,
+            &command_options.binary_file,
+        )?,
+    }
 
-    if !driver.check_diagnostics()? {
+    if !driver.finish()? {
         std::process::exit(exitcode::DATAERR);
     } else {
         std::process::exit(exitcode::OK);
     }
 }
+
+fn parse_endianness(src: &str) -> Result<Endianness, &'static str> {
+    match () {
+        () if src.eq_ignore_ascii_case("big") => Ok(Endianness::Big),
+        () if src.eq_ignore_ascii_case("little") => Ok(Endianness::Little),
+        () => Err("valid values: big, little"),
+    }
+}
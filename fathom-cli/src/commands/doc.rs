@@ -18,7 +18,7 @@ pub fn run(options: &crate::Options, command_options: &Options) -> anyhow::Resul
     // TODO: Write to file
     driver.write_doc(&command_options.format_file)?;
 
-    if !driver.check_diagnostics()? {
+    if !driver.finish()? {
         std::process::exit(exitcode::DATAERR);
     } else {
         std::process::exit(exitcode::OK);
@@ -0,0 +1,24 @@
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+pub struct Options {
+    /// The diagnostic code to explain, eg. `E0505`.
+    #[structopt(name = "CODE")]
+    code: String,
+}
+
+pub fn run(_options: &crate::Options, command_options: &Options) -> anyhow::Result<()> {
+    match fathom::driver::Driver::explain(&command_options.code) {
+        Some(explanation) => {
+            print!("{}", explanation);
+            std::process::exit(exitcode::OK);
+        }
+        None => {
+            eprintln!(
+                "error: no extended explanation for `{}`",
+                command_options.code,
+            );
+            std::process::exit(exitcode::USAGE);
+        }
+    }
+}
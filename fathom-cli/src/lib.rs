@@ -47,6 +47,9 @@ enum Command {
     /// Generate documentation for binary formats
     #[structopt(name = "doc")]
     Doc(commands::doc::Options),
+    /// Explain a diagnostic code in more detail
+    #[structopt(name = "explain")]
+    Explain(commands::explain::Options),
 }
 
 fn parse_color_choice(src: &str) -> Result<ColorChoice, &'static str> {
@@ -76,5 +79,6 @@ pub fn run(options: Options) -> anyhow::Result<()> {
         Command::Compile(command_options) => commands::compile::run(&options, command_options),
         Command::Check(command_options) => commands::check::run(&options, command_options),
         Command::Doc(command_options) => commands::doc::run(&options, command_options),
+        Command::Explain(command_options) => commands::explain::run(&options, command_options),
     }
 }
@@ -73,3 +73,85 @@ fn stl_emit_core() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn stl_emit_item_graph() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "check",
+        "--emit-item-graph",
+        "--format-file=../examples/stl.fathom",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("digraph {\n"))
+        .stdout(predicate::str::contains("\"Vec3d\" [label=\"Vec3d\""))
+        .stdout(predicate::str::contains("\"Triangle\" [label=\"Triangle\""))
+        .stdout(predicate::str::contains("\"Main\" [label=\"Main\""))
+        .stdout(predicate::str::contains("\"Triangle\" -> \"Vec3d\";"))
+        .stdout(predicate::str::contains("\"Main\" -> \"Triangle\";"))
+        .stdout(predicate::str::contains("\"Main\" -> \"Vec3d\";").not())
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn field_declaration_equals_hint() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "check",
+        "--format-file=../tests/struct/fail_field_declaration_equals.fathom",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "help: field declarations use `:` to specify the type of a field",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn field_definition_colon_hint() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "check",
+        "--format-file=../tests/struct/fail_field_definition_colon.fathom",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "help: field definitions use `=` to specify the value of a field",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn summary() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "--color=never",
+        "check",
+        "--format-file=tests/cli/snapshots/summary.fathom",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "error: aborting due to 2 previous errors; 1 warning emitted",
+        ));
+
+    Ok(())
+}
@@ -19,3 +19,47 @@ fn target_rust_is_not_implemented() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn target_typescript() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "compile",
+        "--target=typescript",
+        "--format-file=tests/cli/snapshots/typescript.fathom",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::similar(include_str!(
+            "snapshots/typescript.ts"
+        )))
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn target_typescript_inline_aliases() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "compile",
+        "--target=typescript",
+        "--inline-aliases",
+        "--format-file=tests/cli/snapshots/inline_aliases.fathom",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("export type Coord").not())
+        .stdout(predicate::str::contains("export type Length").not())
+        .stdout(predicate::str::contains("export type Vertex ").not())
+        .stdout(predicate::str::contains("export type Vertices = Point[];"))
+        .stdout(predicate::str::contains("    len: number;\n"))
+        .stdout(predicate::str::contains("    origin: Point;\n"))
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
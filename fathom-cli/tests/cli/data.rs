@@ -65,3 +65,207 @@ fn stl_cube_validate_core() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn stl_cube_field_path() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "data",
+        "--format-file=../examples/stl.fathom",
+        "--field-path=triangle_count",
+        "../examples/data/stl/cube.stl",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::similar("Main.triangle_count = 12\n"))
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn stl_cube_unknown_field_path() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "data",
+        "--format-file=../examples/stl.fathom",
+        "--field-path=triangle_count.x",
+        "../examples/data/stl/cube.stl",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("no field named `x` in the format"));
+
+    Ok(())
+}
+
+// The triangles are read by applying `FormatArray` to the triangle count,
+// which is looked up from the locals.
+#[cfg(feature = "eval-stats")]
+#[test]
+fn stl_cube_eval_stats() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "--color=never",
+        "data",
+        "--format-file=../examples/stl.fathom",
+        "--eval-stats",
+        "../examples/data/stl/cube.stl",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("Main = struct {"))
+        .stderr(predicate::str::is_match(concat!(
+            r"^note: counted the work done by the evaluator while reading\n",
+            r" = terms evaluated: [1-9][0-9]*\n",
+            r" = function eliminations: [1-9][0-9]*\n",
+            r" = values allocated: [1-9][0-9]*\n",
+            r" = Arc clones: [1-9][0-9]*\n",
+        ))?);
+
+    Ok(())
+}
+
+#[test]
+fn stl_cube_round_trip() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "data",
+        "--round-trip",
+        "--format-file=../examples/stl.fathom",
+        "../examples/data/stl/cube.stl",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn stl_cube_records() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "data",
+        "--format-file=../examples/stl.fathom",
+        "--records",
+        "../examples/data/stl/cube.stl",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "Main[0] = struct {\n    header = [",
+        ))
+        .stdout(predicate::str::contains("Main[1]").not())
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn round_trip_mismatch() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "data",
+        "--round-trip",
+        "--format-file=tests/cli/snapshots/round_trip_mismatch.fathom",
+        "tests/cli/snapshots/round_trip_mismatch.bin",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "did not reproduce the original bytes",
+        ))
+        .stderr(predicate::str::contains(
+            "the bytes first differ at offset 0x3",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn floats() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "data",
+        "--format-file=tests/cli/snapshots/floats.fathom",
+        "tests/cli/snapshots/floats.bin",
+    ]);
+
+    // The subnormal is rendered so that it parses back to the same value.
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("inf = F64Infinity,"))
+        .stdout(predicate::str::contains("nan = F64Nan,"))
+        .stdout(predicate::str::contains(format!(
+            "subnormal = {},",
+            f64::from_bits(1),
+        )))
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn records_before_truncated_record() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&[
+        "data",
+        "--format-file=tests/cli/snapshots/records.fathom",
+        "--item-name=Record",
+        "--records",
+        "tests/cli/snapshots/records.bin",
+    ]);
+
+    // The records before the truncated record have already been emitted.
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Record[2] = struct {\n    id = 2,\n    value = 4,\n}",
+        ))
+        .stdout(predicate::str::contains("Record[3]").not())
+        .stderr(predicate::str::contains(
+            "attempted to read beyond the end of the buffer",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn default_endianness() -> anyhow::Result<()> {
+    for (endianness, value) in &[("big", 258), ("little", 513)] {
+        let mut cmd = Command::cargo_bin("fathom")?;
+
+        cmd.args(&[
+            "data",
+            "--format-file=tests/cli/snapshots/endianness.fathom",
+            &format!("--default-endianness={}", endianness),
+            "tests/cli/snapshots/endianness.bin",
+        ]);
+
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(format!("value = {},", value)))
+            .stdout(predicate::str::contains("fixed = 258,"))
+            .stderr(predicate::str::is_empty());
+    }
+
+    Ok(())
+}
@@ -37,3 +37,19 @@ fn stl() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn stl_repr_types() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&["doc", "--format-file=../examples/stl.fathom"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r##"<var><a href="#">F32Le</a></var> <span class="repr">→ <var><a href="#">F32</a></var></span>"##,
+        ))
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn known_code() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&["explain", "E0505"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "A term did not have the type it was expected to have.",
+        ))
+        .stderr(predicate::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn unknown_code() -> anyhow::Result<()> {
+    let mut cmd = Command::cargo_bin("fathom")?;
+
+    cmd.args(&["explain", "E9999"]);
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "no extended explanation for `E9999`",
+        ));
+
+    Ok(())
+}
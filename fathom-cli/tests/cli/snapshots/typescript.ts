@@ -0,0 +1,48 @@
+// A small module for testing the TypeScript target.
+
+/**
+ * A point in two dimensions.
+ */
+export interface Point {
+    x: bigint;
+    y: bigint;
+}
+
+export interface Tagged<A> {
+    tag: boolean;
+    value: A;
+}
+
+export type Length = number;
+
+/**
+ * A list of labelled samples.
+ */
+export interface Samples {
+    /**
+     * The number of samples.
+     */
+    len: number;
+    timestamp: bigint;
+    label: string;
+    values: number[];
+    pairs: { fst: number; snd: number }[];
+}
+
+export interface Wrapper<F> {
+    inner: F;
+    samples: Samples;
+}
+
+export interface Header {
+    width: number;
+    offset: bigint;
+}
+
+export interface Combinators {
+    magic: number;
+    reversed: number[];
+    chunked: number[][];
+    wide: bigint[];
+    present: number | null;
+}
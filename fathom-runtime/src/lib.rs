@@ -6,7 +6,7 @@ mod read;
 mod write;
 
 pub use read::{FormatReader, ReadEofError, ReadError, ReadFormat, ReadFormatUnchecked, ReadScope};
-pub use write::{FormatWriter, WriteFormat};
+pub use write::{FormatWriter, WriteError, WriteFormat};
 
 /// Binary formats with a corresponding host representation.
 pub trait Format {
@@ -94,6 +94,60 @@ impl WriteFormat for U16Be {
     }
 }
 
+/// Marker type for unsigned 24-bit integers (little endian).
+///
+/// These are represented on the host as `u32`s. Only the low 24 bits of the
+/// value are written.
+#[derive(Copy, Clone)]
+pub enum U24Le {}
+
+impl<'data> ReadFormatUnchecked<'data> for U24Le {
+    const SIZE: usize = 3;
+
+    #[inline]
+    unsafe fn read_unchecked(reader: &mut FormatReader<'data>) -> u32 {
+        let b0 = u32::from(reader.read_unchecked::<U8>());
+        let b1 = u32::from(reader.read_unchecked::<U8>());
+        let b2 = u32::from(reader.read_unchecked::<U8>());
+        b0 | (b1 << 8) | (b2 << 16)
+    }
+}
+
+impl WriteFormat for U24Le {
+    fn write(writer: &mut FormatWriter, value: u32) {
+        writer.write_u8(value as u8);
+        writer.write_u8((value >> 8) as u8);
+        writer.write_u8((value >> 16) as u8);
+    }
+}
+
+/// Marker type for unsigned 24-bit integers (big endian).
+///
+/// These are represented on the host as `u32`s. Only the low 24 bits of the
+/// value are written.
+#[derive(Copy, Clone)]
+pub enum U24Be {}
+
+impl<'data> ReadFormatUnchecked<'data> for U24Be {
+    const SIZE: usize = 3;
+
+    #[inline]
+    unsafe fn read_unchecked(reader: &mut FormatReader<'data>) -> u32 {
+        let b0 = u32::from(reader.read_unchecked::<U8>());
+        let b1 = u32::from(reader.read_unchecked::<U8>());
+        let b2 = u32::from(reader.read_unchecked::<U8>());
+        (b0 << 16) | (b1 << 8) | b2
+    }
+}
+
+impl WriteFormat for U24Be {
+    fn write(writer: &mut FormatWriter, value: u32) {
+        writer.write_u8((value >> 16) as u8);
+        writer.write_u8((value >> 8) as u8);
+        writer.write_u8(value as u8);
+    }
+}
+
 /// Marker type for unsigned 32-bit integers (little endian).
 #[derive(Copy, Clone)]
 pub enum U32Le {}
@@ -235,6 +289,8 @@ macro_rules! impl_uint_marker {
 impl_uint_marker!(U8, u8);
 impl_uint_marker!(U16Le, u16);
 impl_uint_marker!(U16Be, u16);
+impl_uint_marker!(U24Le, u32);
+impl_uint_marker!(U24Be, u32);
 impl_uint_marker!(U32Le, u32);
 impl_uint_marker!(U32Be, u32);
 impl_uint_marker!(U64Le, u64);
@@ -252,6 +308,20 @@ pub enum I16Le {}
 #[derive(Copy, Clone)]
 pub enum I16Be {}
 
+/// Marker type for signed, two's complement 24-bit integers (little endian).
+///
+/// These are represented on the host as `i32`s, sign extended from the 24th
+/// bit. Only the low 24 bits of the value are written.
+#[derive(Copy, Clone)]
+pub enum I24Le {}
+
+/// Marker type for signed, two's complement 24-bit integers (big endian).
+///
+/// These are represented on the host as `i32`s, sign extended from the 24th
+/// bit. Only the low 24 bits of the value are written.
+#[derive(Copy, Clone)]
+pub enum I24Be {}
+
 /// Marker type for signed, two's complement 32-bit integers (little endian).
 #[derive(Copy, Clone)]
 pub enum I32Le {}
@@ -299,10 +369,44 @@ macro_rules! impl_int_marker {
     };
 }
 
+macro_rules! impl_int24_marker {
+    ($Int:ident, $UInt:ident) => {
+        impl Format for $Int {
+            type Host = i32;
+        }
+
+        impl<'data> ReadFormatUnchecked<'data> for $Int {
+            const SIZE: usize = $UInt::SIZE;
+
+            #[inline]
+            unsafe fn read_unchecked(reader: &mut FormatReader<'data>) -> i32 {
+                ((reader.read_unchecked::<$UInt>() << 8) as i32) >> 8
+            }
+        }
+
+        impl<'data> ReadFormat<'data> for $Int {
+            #[inline]
+            fn read(reader: &mut FormatReader<'data>) -> Result<i32, ReadError> {
+                reader.check_available($Int::SIZE)?;
+                Ok(unsafe { reader.read_unchecked::<$Int>() })
+            }
+        }
+
+        impl WriteFormat for $Int {
+            #[inline]
+            fn write(writer: &mut FormatWriter, value: i32) {
+                writer.write::<$UInt>(value as u32);
+            }
+        }
+    };
+}
+
 impl_int_marker!(I8, U8, i8);
 impl_int_marker!(I16Le, U16Le, i16);
 impl_int_marker!(I16Be, U16Be, i16);
 impl_int_marker!(I32Le, U32Le, i32);
+impl_int24_marker!(I24Le, U24Le);
+impl_int24_marker!(I24Be, U24Be);
 impl_int_marker!(I32Be, U32Be, i32);
 impl_int_marker!(I64Le, U64Le, i64);
 impl_int_marker!(I64Be, U64Be, i64);
@@ -374,6 +478,58 @@ mod tests {
         reader.read::<T>().unwrap()
     }
 
+    #[test]
+    fn reader_at_offset() {
+        let scope = ReadScope::new(&[0, 1, 2, 3, 4]);
+        let mut reader = scope.reader_at(2);
+
+        assert_eq!(reader.current_pos(), Some(2));
+        assert_eq!(reader.read::<U8>().unwrap(), 2);
+        assert_eq!(reader.remaining(), 2);
+        assert_eq!(reader.scope_at_pos(1).read::<U8>().unwrap(), 1);
+        assert!(reader.scope_at_pos(5).data().is_empty());
+    }
+
+    #[test]
+    fn reader_seek_relative() {
+        let scope = ReadScope::new(&[0, 1, 2, 3, 4]);
+        let mut reader = scope.reader_at(1);
+
+        reader.seek_relative(3).unwrap();
+        assert_eq!(reader.position(), 4);
+        assert_eq!(reader.remaining(), 1);
+        reader.seek_relative(-2).unwrap();
+        assert_eq!(reader.read::<U8>().unwrap(), 2);
+        reader.seek_relative(2).unwrap();
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn reader_seek_relative_out_of_bounds() {
+        let scope = ReadScope::new(&[0, 1, 2, 3, 4]);
+        let mut reader = scope.reader_at(2);
+
+        match reader.seek_relative(-3) {
+            Err(ReadError::SeekBeforeStart { pos: 2, offset: -3 }) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+        match reader.seek_relative(i64::MIN) {
+            Err(ReadError::SeekBeforeStart { .. }) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+        match reader.seek_relative(4) {
+            Err(ReadError::SeekAfterEnd { pos: 2, offset: 4 }) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+        assert_eq!(reader.position(), 2);
+
+        let mut reader = scope.offset(3).reader();
+        match reader.seek_relative(-1) {
+            Err(ReadError::SeekBeforeStart { pos: 3, offset: -1 }) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
     proptest! {
         #[test]
         fn u8_round_trip(value: u8) {
@@ -393,6 +549,18 @@ mod tests {
             prop_assert_eq!(round_trip::<U16Be>(&mut writer, value), value);
         }
 
+        #[test]
+        fn u24le_round_trip(value in 0..(1u32 << 24)) {
+            let mut writer = FormatWriter::new(vec![]);
+            prop_assert_eq!(round_trip::<U24Le>(&mut writer, value), value);
+        }
+
+        #[test]
+        fn u24be_round_trip(value in 0..(1u32 << 24)) {
+            let mut writer = FormatWriter::new(vec![]);
+            prop_assert_eq!(round_trip::<U24Be>(&mut writer, value), value);
+        }
+
         #[test]
         fn u32le_round_trip(value: u32) {
             let mut writer = FormatWriter::new(vec![]);
@@ -434,6 +602,18 @@ mod tests {
             prop_assert_eq!(round_trip::<I16Be>(&mut writer, value), value);
         }
 
+        #[test]
+        fn i24le_round_trip(value in -(1i32 << 23)..(1i32 << 23)) {
+            let mut writer = FormatWriter::new(vec![]);
+            prop_assert_eq!(round_trip::<I24Le>(&mut writer, value), value);
+        }
+
+        #[test]
+        fn i24be_round_trip(value in -(1i32 << 23)..(1i32 << 23)) {
+            let mut writer = FormatWriter::new(vec![]);
+            prop_assert_eq!(round_trip::<I24Be>(&mut writer, value), value);
+        }
+
         #[test]
         fn i32le_round_trip(value: i32) {
             let mut writer = FormatWriter::new(vec![]);
@@ -1,5 +1,6 @@
 //! Read binary data.
 
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 
@@ -14,6 +15,56 @@ pub enum ReadError {
     DuplicatePosition { offset: usize },
     /// Position overflowed maximum allowed size.
     OverflowingPosition,
+    /// A value did not match the constant it was required to equal.
+    UnexpectedConst { expected: String, found: String },
+    /// Bytes were left over after reading a nested format.
+    TrailingBytes { count: usize },
+    /// A version number was lower than the minimum supported version.
+    UnsupportedVersion { found: String, required: String },
+    /// An element of an array was out of order.
+    UnsortedElement { index: usize },
+    /// An element of an array was equal to an earlier element.
+    DuplicateElement { index: usize },
+    /// A row of an array of arrays had a different length to the first row.
+    MismatchedRowLength { index: usize },
+    /// Two arrays that were zipped together had different lengths.
+    MismatchedArrayLengths { first: usize, second: usize },
+    /// An array was split, or had elements taken or dropped, at an index past
+    /// its end.
+    ArrayIndexOutOfBounds { index: usize, len: usize },
+    /// A format did not consume the number of bytes that it was asserted to.
+    SizeMismatch { expected: usize, found: usize },
+    /// A padding byte was not equal to the expected fill byte.
+    InvalidPadding {
+        index: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// A string contained invalid UTF-8, starting at the given byte offset.
+    InvalidUtf8 { offset: usize },
+    /// A variable-length integer did not fit in its representation after
+    /// reading the given number of bytes.
+    OverflowingInteger { count: usize },
+    /// Padding to align the given position to a multiple of the given
+    /// alignment extended past the end of the buffer.
+    AlignmentOverflow { offset: usize, align: usize },
+    /// Reading took more steps than the limit that was set for it.
+    ReadTimeout { limit: usize },
+    /// A position computed from the binary data did not fit in the range of
+    /// positions, along with the label of the field that was being read, if
+    /// known.
+    ArithmeticOverflow { field: Option<String> },
+    /// A relative seek from the given position moved before the start of the
+    /// scope.
+    SeekBeforeStart { pos: usize, offset: i64 },
+    /// A relative seek from the given position moved past the end of the
+    /// scope.
+    SeekAfterEnd { pos: usize, offset: i64 },
+    /// A format that was asserted to be unreachable was read. This indicates
+    /// a bug in the data description, rather than invalid binary data.
+    ReachedNever,
+    /// A field path referred to a field that the format does not have.
+    UnknownField { label: String },
     /// An end of file error.
     Eof(ReadEofError),
 }
@@ -32,6 +83,99 @@ impl fmt::Display for ReadError {
             ReadError::OverflowingPosition => {
                 write!(f, "position overflowed maximum allowed size")
             }
+            ReadError::UnexpectedConst { expected, found } => {
+                write!(f, "expected constant `{}`, found `{}`", expected, found)
+            }
+            ReadError::TrailingBytes { count } => {
+                write!(
+                    f,
+                    "{} bytes were left over after reading nested data",
+                    count
+                )
+            }
+            ReadError::UnsupportedVersion { found, required } => write!(
+                f,
+                "unsupported version `{}`, expected at least `{}`",
+                found, required,
+            ),
+            ReadError::UnsortedElement { index } => {
+                write!(f, "array element {} is out of order", index)
+            }
+            ReadError::DuplicateElement { index } => write!(
+                f,
+                "array element {} is a duplicate of an earlier element",
+                index,
+            ),
+            ReadError::MismatchedRowLength { index } => write!(
+                f,
+                "array row {} has a different length to the first row",
+                index,
+            ),
+            ReadError::MismatchedArrayLengths { first, second } => write!(
+                f,
+                "zipped arrays have different lengths ({} and {})",
+                first, second,
+            ),
+            ReadError::ArrayIndexOutOfBounds { index, len } => write!(
+                f,
+                "index {} is out of bounds for an array of length {}",
+                index, len,
+            ),
+            ReadError::SizeMismatch { expected, found } => write!(
+                f,
+                "expected format to consume {} bytes, but it consumed {}",
+                expected, found,
+            ),
+            ReadError::InvalidPadding {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "padding byte {} was {:#04x}, expected {:#04x}",
+                index, found, expected,
+            ),
+            ReadError::AlignmentOverflow { offset, align } => write!(
+                f,
+                "aligning position {:x} to a multiple of {} overflowed the end of the buffer",
+                offset, align,
+            ),
+            ReadError::ReadTimeout { limit } => {
+                write!(f, "reading exceeded the limit of {} steps", limit)
+            }
+            ReadError::ArithmeticOverflow { field: None } => {
+                write!(f, "arithmetic overflowed while computing a position")
+            }
+            ReadError::ArithmeticOverflow { field: Some(field) } => write!(
+                f,
+                "arithmetic overflowed while computing a position in field `{}`",
+                field,
+            ),
+            ReadError::SeekBeforeStart { pos, offset } => write!(
+                f,
+                "seeking by {} bytes from position {:x} moved before the start of the buffer",
+                offset, pos,
+            ),
+            ReadError::SeekAfterEnd { pos, offset } => write!(
+                f,
+                "seeking by {} bytes from position {:x} moved past the end of the buffer",
+                offset, pos,
+            ),
+            ReadError::ReachedNever => write!(
+                f,
+                "bug: reached a format that was asserted to be unreachable",
+            ),
+            ReadError::UnknownField { label } => {
+                write!(f, "no field named `{}` in the format", label)
+            }
+            ReadError::InvalidUtf8 { offset } => {
+                write!(f, "invalid UTF-8 at byte {} of string", offset)
+            }
+            ReadError::OverflowingInteger { count } => write!(
+                f,
+                "variable-length integer overflowed after {} bytes",
+                count,
+            ),
             ReadError::Eof(error) => error.fmt(f),
         }
     }
@@ -42,7 +186,26 @@ impl Error for ReadError {
         match self {
             ReadError::InvalidDataDescription
             | ReadError::DuplicatePosition { .. }
-            | ReadError::OverflowingPosition => None,
+            | ReadError::OverflowingPosition
+            | ReadError::UnexpectedConst { .. }
+            | ReadError::TrailingBytes { .. }
+            | ReadError::UnsupportedVersion { .. }
+            | ReadError::UnsortedElement { .. }
+            | ReadError::DuplicateElement { .. }
+            | ReadError::MismatchedRowLength { .. }
+            | ReadError::MismatchedArrayLengths { .. }
+            | ReadError::ArrayIndexOutOfBounds { .. }
+            | ReadError::SizeMismatch { .. }
+            | ReadError::InvalidPadding { .. }
+            | ReadError::InvalidUtf8 { .. }
+            | ReadError::OverflowingInteger { .. }
+            | ReadError::AlignmentOverflow { .. }
+            | ReadError::ReadTimeout { .. }
+            | ReadError::ArithmeticOverflow { .. }
+            | ReadError::SeekBeforeStart { .. }
+            | ReadError::SeekAfterEnd { .. }
+            | ReadError::ReachedNever
+            | ReadError::UnknownField { .. } => None,
             ReadError::Eof(error) => Some(error),
         }
     }
@@ -95,6 +258,20 @@ impl<'data> ReadScope<'data> {
         }
     }
 
+    /// Construct a new read context in this scope, beginning at an offset
+    /// from the start of the scope.
+    ///
+    /// Positions are still measured from the start of the scope, so links
+    /// that are read at the offset refer to the same data as they would if
+    /// the scope had been read from the beginning.
+    #[inline]
+    pub fn reader_at(&self, offset: usize) -> FormatReader<'data> {
+        FormatReader {
+            scope: *self,
+            offset,
+        }
+    }
+
     /// Return a new scope, offset to the desired position in the buffer.
     ///
     /// If we exceed the length of the buffer, and scope pointing to an empty
@@ -142,12 +319,76 @@ impl<'data> FormatReader<'data> {
         usize::checked_add(self.scope.base, self.offset)
     }
 
+    /// Create a new scope at a position, measured from the same base as
+    /// [`FormatReader::current_pos`].
+    ///
+    /// If the position is outside of the scope of the reader, a scope pointing
+    /// to an empty buffer will be returned.
+    #[inline]
+    pub fn scope_at_pos(&self, pos: usize) -> ReadScope<'data> {
+        let data = (pos.checked_sub(self.scope.base))
+            .and_then(|offset| self.scope.data.get(offset..))
+            .unwrap_or(&[]);
+        ReadScope { base: pos, data }
+    }
+
+    /// The number of bytes remaining in the scope of the reader.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.scope.data.len().saturating_sub(self.offset)
+    }
+
+    /// The number of bytes that the reader has moved past, measured from the
+    /// start of its scope.
+    ///
+    /// Unlike [`FormatReader::current_pos`], this does not include the base
+    /// position of the scope.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.offset
+    }
+
+    /// Move the reader forwards or backwards by a number of bytes.
+    ///
+    /// Seeking to the end of the scope is allowed, but seeking past either
+    /// end of the scope results in an error, leaving the reader unchanged.
+    pub fn seek_relative(&mut self, offset: i64) -> Result<(), ReadError> {
+        let pos = self.current_pos().ok_or(ReadError::OverflowingPosition)?;
+        let new_offset = match offset {
+            0.. => usize::try_from(offset)
+                .ok()
+                .and_then(|offset| self.offset.checked_add(offset))
+                .filter(|new_offset| *new_offset <= self.scope.data.len())
+                .ok_or(ReadError::SeekAfterEnd { pos, offset })?,
+            _ => usize::try_from(offset.unsigned_abs())
+                .ok()
+                .and_then(|offset| self.offset.checked_sub(offset))
+                .ok_or(ReadError::SeekBeforeStart { pos, offset })?,
+        };
+        self.offset = new_offset;
+        Ok(())
+    }
+
     /// Read some binary data in the context.
     #[inline]
     pub fn read<T: ReadFormat<'data>>(&mut self) -> Result<T::Host, ReadError> {
         T::read(self)
     }
 
+    /// Create a new scope over the next `len` bytes, advancing the reader past
+    /// them. Positions in the new scope are relative to the same base as the
+    /// positions in this reader.
+    #[inline]
+    pub fn read_scope(&mut self, len: usize) -> Result<ReadScope<'data>, ReadEofError> {
+        self.check_available(len)?;
+        let scope = ReadScope {
+            base: self.scope.base + self.offset,
+            data: &self.scope.data[self.offset..][..len],
+        };
+        self.offset += len;
+        Ok(scope)
+    }
+
     /// Read some binary data in the context without bounds checking.
     ///
     /// # Safety
@@ -1,3 +1,6 @@
+use std::error::Error;
+use std::fmt;
+
 use crate::Format;
 
 /// An in-memory buffer that can be written into.
@@ -36,3 +39,36 @@ pub trait WriteFormat: Format {
     /// Write the binary representation of `Self::Host` to `writer`.
     fn write(writer: &mut FormatWriter, value: Self::Host);
 }
+
+/// An error produced while writing binary data.
+#[derive(Debug)]
+pub enum WriteError {
+    /// Tried to write a broken data description.
+    InvalidDataDescription,
+    /// A value could not be represented by the format it was written with.
+    UnrepresentableValue { value: String },
+    /// Tried to write a format that discards information when it is read.
+    NonInvertibleFormat { format: String },
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteError::InvalidDataDescription => {
+                write!(f, "attempted to write improperly specified data")
+            }
+            WriteError::UnrepresentableValue { value } => {
+                write!(
+                    f,
+                    "the value `{}` could not be represented by its format",
+                    value
+                )
+            }
+            WriteError::NonInvertibleFormat { format } => {
+                write!(f, "the format `{}` can not be written", format)
+            }
+        }
+    }
+}
+
+impl Error for WriteError {}
@@ -29,6 +29,9 @@ pub struct Directives {
     /// //~ note: regex
     /// //~ help: regex
     /// ```
+    ///
+    /// `note` and `help` directives also match the notes of a diagnostic that
+    /// start with `note:` or `help:`.
     pub expected_diagnostics: Vec<ExpectedDiagnostic>,
 }
 
@@ -5,6 +5,7 @@ use codespan_reporting::term::termcolor::{BufferWriter, ColorChoice};
 use fathom::lang::FileId;
 use fathom::pass::{core_to_pretty, core_to_surface, surface_to_core, surface_to_doc};
 use libtest_mimic::{Outcome, Test};
+use std::collections::HashMap;
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -20,6 +21,10 @@ lazy_static::lazy_static! {
     static ref GLOBALS: fathom::lang::core::Globals = fathom::lang::core::Globals::default();
 }
 
+/// The representation types of the fields of struct formats, keyed by item
+/// name and field label.
+type FieldReprTypes = HashMap<String, HashMap<String, fathom::lang::surface::Term>>;
+
 /// Recursively walk over test files under a file path.
 pub fn find_fathom_files(root: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
     WalkDir::new(root)
@@ -157,8 +162,8 @@ fn run_full_test(_fathom_exe: &str, format_file: &Path) -> Outcome {
     };
 
     let surface_module = full_test.parse_surface();
-    full_test.compile_doc(&surface_module);
-    let core_module = full_test.surface_to_core(&surface_module);
+    let (core_module, field_repr_types) = full_test.surface_to_core(&surface_module);
+    full_test.compile_doc(&surface_module, &field_repr_types);
     full_test.roundtrip_surface_to_core(&core_module);
     full_test.roundtrip_core_to_pretty(&core_module);
     full_test.binary_parse_tests();
@@ -190,9 +195,10 @@ impl<'a> FullTest<'a> {
     fn surface_to_core(
         &mut self,
         surface_module: &fathom::lang::surface::Module,
-    ) -> fathom::lang::core::Module {
+    ) -> (fathom::lang::core::Module, FieldReprTypes) {
         let mut context = surface_to_core::Context::new(&GLOBALS);
         let core_module = context.from_module(&surface_module);
+        let field_repr_types = context.field_repr_types().clone();
         self.found_messages.extend(context.drain_messages());
 
         // The core syntax from the elaborator should always be well-formed!
@@ -218,7 +224,7 @@ impl<'a> FullTest<'a> {
             });
         }
 
-        core_module
+        (core_module, field_repr_types)
     }
 
     fn roundtrip_surface_to_core(&mut self, core_module: &fathom::lang::core::Module) {
@@ -227,13 +233,20 @@ impl<'a> FullTest<'a> {
         let surface_module = context.from_module(&core_to_surface_context.from_module(core_module));
         let elaboration_messages = context.drain_messages().collect::<Vec<_>>();
 
-        if !elaboration_messages.is_empty() {
-            let pretty_arena = pretty::Arena::new();
+        let pretty_arena = pretty::Arena::new();
+        // Warnings are expected to be reported again when re-elaborating the
+        // distilled module, so only errors are treated as failures.
+        let elaboration_diagnostics = elaboration_messages
+            .iter()
+            .map(|message| message.to_diagnostic(&pretty_arena))
+            .filter(|diagnostic| diagnostic.severity >= Severity::Error)
+            .collect::<Vec<_>>();
+
+        if !elaboration_diagnostics.is_empty() {
             let mut buffer = BufferWriter::stderr(ColorChoice::Auto).buffer();
 
-            for message in &elaboration_messages {
-                let diagnostic = message.to_diagnostic(&pretty_arena);
-                term::emit(&mut buffer, &self.term_config, &self.files, &diagnostic).unwrap();
+            for diagnostic in &elaboration_diagnostics {
+                term::emit(&mut buffer, &self.term_config, &self.files, diagnostic).unwrap();
             }
 
             self.failures.push(Failure {
@@ -405,10 +418,14 @@ impl<'a> FullTest<'a> {
         }
     }
 
-    fn compile_doc(&mut self, surface_module: &fathom::lang::surface::Module) {
+    fn compile_doc(
+        &mut self,
+        surface_module: &fathom::lang::surface::Module,
+        field_repr_types: &FieldReprTypes,
+    ) {
         let mut output = Vec::new();
         surface_to_doc::Context::new()
-            .from_module(&mut output, surface_module)
+            .from_module(&mut output, surface_module, field_repr_types)
             .unwrap();
 
         if let Err(error) = snapshot::compare(&self.snapshot_file.with_extension("html"), &output) {
@@ -532,12 +549,33 @@ fn is_expected(
             let found_message = &found_diagnostic.message;
 
             found_line_index == expected_diagnostic.line_index
-                && found_diagnostic.severity == expected_diagnostic.severity
-                && expected_diagnostic.pattern.is_match(found_message)
+                && (found_diagnostic.severity == expected_diagnostic.severity
+                    && expected_diagnostic.pattern.is_match(found_message)
+                    || is_expected_note(found_diagnostic, expected_diagnostic))
         }
     })
 }
 
+/// Check if a `note` or `help` directive matches one of the notes attached to
+/// a diagnostic, like `help: did you mean `height`?`.
+fn is_expected_note(
+    found_diagnostic: &Diagnostic<FileId>,
+    expected_diagnostic: &ExpectedDiagnostic,
+) -> bool {
+    let prefix = match expected_diagnostic.severity {
+        Severity::Note => "note: ",
+        Severity::Help => "help: ",
+        _ => return false,
+    };
+    found_diagnostic
+        .notes
+        .iter()
+        .any(|note| match note.strip_prefix(prefix) {
+            Some(note) => expected_diagnostic.pattern.is_match(note),
+            None => false,
+        })
+}
+
 fn process_output_details(name: &str, output: &std::process::Output) -> Vec<(String, String)> {
     let mut details = Vec::new();
 
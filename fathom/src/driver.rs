@@ -1,15 +1,23 @@
-use codespan_reporting::diagnostic::Severity;
+use codespan_reporting::diagnostic::{Diagnostic, Severity};
 use codespan_reporting::files::SimpleFiles;
 use codespan_reporting::term;
 use codespan_reporting::term::termcolor::{BufferedStandardStream, ColorChoice, WriteColor};
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt;
 use std::io;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
+use crate::lang::core::binary::Endianness;
+use crate::lang::core::semantics::{self, EvalStats, Value};
 use crate::lang::{core, surface, FileId};
-use crate::pass::{core_to_pretty, surface_to_core, surface_to_doc, surface_to_pretty};
-use crate::reporting::Message;
+use crate::pass::{
+    core_to_dot, core_to_pretty, core_to_typescript, surface_to_core, surface_to_doc,
+    surface_to_pretty,
+};
+use crate::reporting::{self, Message};
 
 lazy_static::lazy_static! {
     static ref GLOBALS: core::Globals = core::Globals::default();
@@ -40,16 +48,28 @@ impl TermWidth {
 pub struct Driver {
     validate_core: bool,
     emit_core: bool,
+    inline_aliases: bool,
     emit_width: TermWidth,
+    default_endianness: Endianness,
+    read_step_limit: Option<usize>,
+    count_eval_stats: bool,
     emit_writer: Box<dyn WriteColor>,
     codespan_config: codespan_reporting::term::Config,
     diagnostic_writer: Box<dyn WriteColor>,
 
     files: SimpleFiles<String, String>,
+    /// The paths of the source files that have been read, in the order that
+    /// they were read.
+    source_paths: Vec<PathBuf>,
     surface_to_core: surface_to_core::Context<'static>,
     surface_to_doc: surface_to_doc::Context,
     core_typing: core::typing::Context<'static>,
     messages: Vec<Message>,
+    error_count: usize,
+    warning_count: usize,
+    /// The work done by the evaluator while reading binary data, if it is
+    /// being counted.
+    eval_stats: Option<EvalStats>,
 }
 
 impl Driver {
@@ -58,16 +78,24 @@ impl Driver {
         Driver {
             validate_core: false,
             emit_core: false,
+            inline_aliases: false,
             emit_width: TermWidth::Auto,
+            default_endianness: Endianness::default(),
+            read_step_limit: None,
+            count_eval_stats: false,
             emit_writer: Box::new(BufferedStandardStream::stdout(ColorChoice::Auto)),
             codespan_config: codespan_reporting::term::Config::default(),
             diagnostic_writer: Box::new(BufferedStandardStream::stderr(ColorChoice::Auto)),
 
             files: SimpleFiles::new(),
+            source_paths: Vec::new(),
             surface_to_core: surface_to_core::Context::new(&GLOBALS),
             surface_to_doc: surface_to_doc::Context::new(),
             core_typing: core::typing::Context::new(&GLOBALS),
             messages: Vec::new(),
+            error_count: 0,
+            warning_count: 0,
+            eval_stats: None,
         }
     }
 
@@ -81,11 +109,41 @@ impl Driver {
         self.validate_core = validate_core;
     }
 
+    /// Set to `true` to inline trivial aliases before generating code.
+    pub fn set_inline_aliases(&mut self, inline_aliases: bool) {
+        self.inline_aliases = inline_aliases;
+    }
+
     /// Set the width to use for printing diagnostics.
     pub fn set_emit_width(&mut self, emit_width: TermWidth) {
         self.emit_width = emit_width;
     }
 
+    /// Set the byte order used when reading and writing integer formats that
+    /// do not specify one, like `U16`.
+    pub fn set_default_endianness(&mut self, default_endianness: Endianness) {
+        self.default_endianness = default_endianness;
+    }
+
+    /// Set the maximum number of formats that can be read for each item when
+    /// reading binary data, or `None` for no limit.
+    pub fn set_read_step_limit(&mut self, read_step_limit: Option<usize>) {
+        self.read_step_limit = read_step_limit;
+    }
+
+    /// Set to `true` to count the work done by the evaluator while reading
+    /// binary data, reporting it when the driver is finished. Nothing is
+    /// counted unless the `eval-stats` feature is enabled.
+    pub fn set_count_eval_stats(&mut self, count_eval_stats: bool) {
+        self.count_eval_stats = count_eval_stats;
+    }
+
+    /// The work done by the evaluator while reading binary data, if it was
+    /// counted.
+    pub fn eval_stats(&self) -> Option<EvalStats> {
+        self.eval_stats
+    }
+
     /// Set the writer to use when emitting data and intermediate languages
     pub fn set_emit_writer(&mut self, stream: impl 'static + WriteColor) {
         self.emit_writer = Box::new(stream) as Box<dyn WriteColor>;
@@ -110,54 +168,209 @@ impl Driver {
 
         let core_module = self.surface_to_core_module(&surface_module);
         let mut core_binary_read = core::binary::read::Context::new(&GLOBALS, &core_module);
+        core_binary_read.set_default_endianness(self.default_endianness);
+        core_binary_read.set_step_limit(self.read_step_limit);
 
         // TODO: Avoid needing to read the buffer all at once
-        let buffer = match std::fs::read(binary_path) {
-            Ok(buffer) => buffer,
-            Err(error) => {
-                self.messages.push(Message::ReadFile {
-                    path: binary_path.to_owned(),
-                    error: error.to_string(),
-                });
-                return Ok(());
-            }
+        let buffer = match self.read_binary_file(binary_path) {
+            Some(buffer) => buffer,
+            None => return Ok(()),
         };
 
         // TODO: Force diagnostics to be rendered here?
 
         let read_scope = fathom_runtime::ReadScope::new(&buffer);
         // TODO: Make the reading of binary data more lazy
-        let (main_value, links) =
-            core_binary_read.read_item(&mut read_scope.reader(), item_name)?;
+        self.start_eval_stats();
+        let result = core_binary_read.read_item(&mut read_scope.reader(), item_name);
+        self.finish_eval_stats();
+        let (main_value, links) = result?;
 
-        let pretty_arena = pretty::Arena::new(); // TODO: reuse arenas
-        let main_term = self.surface_to_core.read_back_to_surface(&main_value);
-        let pretty::DocBuilder(_, doc) = surface_to_pretty::from_term(&pretty_arena, &main_term);
+        self.emit_value(item_name, &main_value)?;
+        for (link_pos, link_value) in links {
+            self.emit_value(format_args!("{:#x}", link_pos), &link_value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a binary data file as a sequence of records, each described by
+    /// the same item, until the end of the file is reached.
+    ///
+    /// Each record is emitted as soon as it has been read and is then
+    /// dropped, so only one record is held in memory at a time.
+    pub fn read_records(
+        &mut self,
+        format_path: &Path,
+        item_name: &str,
+        binary_path: &Path,
+    ) -> Result<(), ReadDataError> {
+        let surface_module = match self.add_source_file(format_path) {
+            Some(file_id) => self.parse_surface_module(file_id),
+            None => return Ok(()),
+        };
+
+        let core_module = self.surface_to_core_module(&surface_module);
+        let mut core_binary_read = core::binary::read::Context::new(&GLOBALS, &core_module);
+        core_binary_read.set_default_endianness(self.default_endianness);
+        core_binary_read.set_step_limit(self.read_step_limit);
+
+        // TODO: Avoid needing to read the buffer all at once
+        let buffer = match self.read_binary_file(binary_path) {
+            Some(buffer) => buffer,
+            None => return Ok(()),
+        };
+
+        let read_scope = fathom_runtime::ReadScope::new(&buffer);
+        let mut reader = read_scope.reader();
+        let mut index = 0;
+        while reader.remaining() > 0 {
+            let remaining = reader.remaining();
+            self.start_eval_stats();
+            let result = core_binary_read.read_item(&mut reader, item_name);
+            self.finish_eval_stats();
+            let (record_value, links) = result?;
+            // Avoid looping forever on records that consume no data.
+            if reader.remaining() == remaining {
+                return Err(fathom_runtime::ReadError::InvalidDataDescription.into());
+            }
+
+            self.emit_value(format_args!("{}[{}]", item_name, index), &record_value)?;
+            for (link_pos, link_value) in links {
+                self.emit_value(format_args!("{:#x}", link_pos), &link_value)?;
+            }
+            index += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Read a binary data file using a format module, returning the emitted
+    /// data as a string rather than writing it to the emit writer.
+    pub fn read_data_to_string(
+        &mut self,
+        format_path: &Path,
+        item_name: &str,
+        binary_path: &Path,
+    ) -> Result<String, ReadDataError> {
+        self.emit_to_string(|driver| driver.read_data(format_path, item_name, binary_path))
+    }
+
+    /// Read a binary data file using a format module, and then write the data
+    /// back using the same format, checking that the original bytes were
+    /// reproduced. An error is reported if the format is not invertible.
+    pub fn write_format(
+        &mut self,
+        format_path: &Path,
+        item_name: &str,
+        binary_path: &Path,
+    ) -> Result<(), ReadDataError> {
+        let surface_module = match self.add_source_file(format_path) {
+            Some(file_id) => self.parse_surface_module(file_id),
+            None => return Ok(()),
+        };
+
+        let core_module = self.surface_to_core_module(&surface_module);
+        let mut core_binary_read = core::binary::read::Context::new(&GLOBALS, &core_module);
+        core_binary_read.set_default_endianness(self.default_endianness);
+        core_binary_read.set_step_limit(self.read_step_limit);
+        let mut core_binary_write = core::binary::write::Context::new(&GLOBALS, &core_module);
+        core_binary_write.set_default_endianness(self.default_endianness);
+
+        let buffer = match self.read_binary_file(binary_path) {
+            Some(buffer) => buffer,
+            None => return Ok(()),
+        };
+
+        let read_scope = fathom_runtime::ReadScope::new(&buffer);
+        self.start_eval_stats();
+        let result = core_binary_read.read_item(&mut read_scope.reader(), item_name);
+        self.finish_eval_stats();
+        let (main_value, _) = result?;
+
+        let mut writer = fathom_runtime::FormatWriter::new(Vec::with_capacity(buffer.len()));
+        core_binary_write.write_item(&mut writer, item_name, &main_value)?;
+        let written_buffer = writer.into_buffer();
+
+        if written_buffer != buffer {
+            let offset = Iterator::zip(buffer.iter(), written_buffer.iter())
+                .position(|(byte0, byte1)| byte0 != byte1)
+                .unwrap_or_else(|| usize::min(buffer.len(), written_buffer.len()));
+
+            self.messages.push(Message::RoundTripMismatch {
+                path: binary_path.to_owned(),
+                offset,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Read a single field from a binary data file using a format module
+    ///
+    /// The field is located by a dot-separated path of field labels, for
+    /// example `header.version`. Parsing stops once the field has been read,
+    /// and preceding fields of a statically known size are skipped over.
+    pub fn read_field(
+        &mut self,
+        format_path: &Path,
+        item_name: &str,
+        field_path: &str,
+        binary_path: &Path,
+    ) -> Result<(), ReadDataError> {
+        let surface_module = match self.add_source_file(format_path) {
+            Some(file_id) => self.parse_surface_module(file_id),
+            None => return Ok(()),
+        };
+
+        let core_module = self.surface_to_core_module(&surface_module);
+        let mut core_binary_read = core::binary::read::Context::new(&GLOBALS, &core_module);
+        core_binary_read.set_default_endianness(self.default_endianness);
+        core_binary_read.set_step_limit(self.read_step_limit);
+
+        let buffer = match self.read_binary_file(binary_path) {
+            Some(buffer) => buffer,
+            None => return Ok(()),
+        };
+
+        let read_scope = fathom_runtime::ReadScope::new(&buffer);
+        let labels = field_path.split('.').collect::<Vec<_>>();
+        self.start_eval_stats();
+        let result = core_binary_read.read_item_field(&mut read_scope.reader(), item_name, &labels);
+        self.finish_eval_stats();
+        let field_value = result?;
+
+        let pretty_arena = pretty::Arena::new();
+        let field_term = self.surface_to_core.read_back_to_surface(&field_value);
+        let pretty::DocBuilder(_, doc) = surface_to_pretty::from_term(&pretty_arena, &field_term);
 
         writeln!(
             &mut self.emit_writer,
-            "{name} = {term}",
+            "{name}.{path} = {term}",
             name = item_name,
+            path = field_path,
             term = doc.pretty(self.emit_width.compute())
         )?;
         self.emit_writer.flush()?;
 
-        for (link_pos, link_value) in links {
-            let pretty_arena = pretty::Arena::new(); // TODO: reuse arenas
-            let link_term = self.surface_to_core.read_back_to_surface(&link_value);
-            let pretty::DocBuilder(_, doc) =
-                surface_to_pretty::from_term(&pretty_arena, &link_term);
+        Ok(())
+    }
 
-            writeln!(
-                &mut self.emit_writer,
-                "{pos:#x} = {term}",
-                pos = link_pos,
-                term = doc.pretty(self.emit_width.compute())
-            )?;
-            self.emit_writer.flush()?;
+    /// List the names of the global definitions, along with their types.
+    ///
+    /// The globals are listed in alphabetical order, and the types are
+    /// pretty printed on a single line.
+    pub fn global_types(&mut self) -> Vec<(&'static str, String)> {
+        let mut global_types = Vec::new();
+
+        for (name, (r#type, _)) in GLOBALS.entries() {
+            let pretty_arena = pretty::Arena::new();
+            let r#type = self.surface_to_core.core_to_surface(r#type);
+            let pretty::DocBuilder(_, doc) = surface_to_pretty::from_term(&pretty_arena, &r#type);
+            global_types.push((name.as_str(), doc.pretty(usize::MAX).to_string()));
         }
 
-        Ok(())
+        global_types
     }
 
     /// Elaborate the surface language into the core language
@@ -181,6 +394,62 @@ impl Driver {
         Ok(())
     }
 
+    /// Elaborate the surface language into the core language, returning the
+    /// emitted output as a string rather than writing it to the emit writer.
+    pub fn check_to_string(&mut self, format_path: &Path) -> Result<String, io::Error> {
+        self.emit_to_string(|driver| driver.check(format_path))
+    }
+
+    /// Emit the dependency graph of the items in a format module as Graphviz DOT
+    pub fn emit_item_graph(&mut self, format_path: &Path) -> Result<(), io::Error> {
+        let surface_module = match self.add_source_file(format_path) {
+            Some(file_id) => self.parse_surface_module(file_id),
+            None => return Ok(()),
+        };
+
+        let core_module = self.surface_to_core_module(&surface_module);
+
+        core_to_dot::from_module(&mut self.emit_writer, &core_module)?;
+        self.emit_writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Emit TypeScript type definitions for the host representations of the
+    /// items in a format module
+    pub fn emit_typescript(&mut self, format_path: &Path) -> Result<(), io::Error> {
+        let surface_module = match self.add_source_file(format_path) {
+            Some(file_id) => self.parse_surface_module(file_id),
+            None => return Ok(()),
+        };
+
+        let mut core_module = self.surface_to_core_module(&surface_module);
+        if self.inline_aliases {
+            core_module = core::inline::inline_aliases(&core_module);
+        }
+
+        core_to_typescript::from_module(&mut self.emit_writer, &GLOBALS, &core_module)?;
+        self.emit_writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Compute a structural hash of an item in a format module, for detecting
+    /// when its definition has changed.
+    ///
+    /// Returns `None` if the format module could not be read, or if it does
+    /// not define an item with the given name.
+    pub fn item_hash(&mut self, format_path: &Path, item_name: &str) -> Option<u64> {
+        let surface_module = match self.add_source_file(format_path) {
+            Some(file_id) => self.parse_surface_module(file_id),
+            None => return None,
+        };
+
+        let core_module = self.surface_to_core_module(&surface_module);
+
+        core::hash::item_hashes(&core_module).remove(item_name)
+    }
+
     /// Compile documentation for a format module
     pub fn write_doc(&mut self, format_path: &Path) -> Result<(), io::Error> {
         let surface_module = match self.add_source_file(format_path) {
@@ -188,12 +457,46 @@ impl Driver {
             None => return Ok(()),
         };
 
-        self.surface_to_doc
-            .from_module(&mut io::stdout().lock(), &surface_module)?; // TODO: allow for writer to be customised?
+        // Elaborate the module to find the representation types of the fields
+        self.surface_to_core_module(&surface_module);
+        let field_repr_types = self.surface_to_core.field_repr_types();
+
+        self.surface_to_doc.from_module(
+            &mut io::stdout().lock(), // TODO: allow for writer to be customised?
+            &surface_module,
+            field_repr_types,
+        )?;
 
         Ok(())
     }
 
+    /// Write a Makefile-style depfile to `path`, listing the source files that
+    /// have been read by the driver as the dependencies of `output_target`.
+    /// Files that were read more than once are only listed once.
+    pub fn write_depfile(&self, path: &Path, output_target: &Path) -> Result<(), io::Error> {
+        std::fs::write(path, self.depfile(output_target))
+    }
+
+    fn depfile(&self, output_target: &Path) -> String {
+        let mut depfile = escape_depfile_path(output_target);
+        depfile.push(':');
+        let mut seen_paths = HashSet::new();
+        for source_path in &self.source_paths {
+            if !seen_paths.insert(source_path) {
+                continue;
+            }
+            depfile.push(' ');
+            depfile.push_str(&escape_depfile_path(source_path));
+        }
+        depfile.push('\n');
+        depfile
+    }
+
+    /// Look up the extended explanation for a diagnostic code, eg. `E0505`.
+    pub fn explain(code: &str) -> Option<&'static str> {
+        reporting::explain(code)
+    }
+
     /// Write diagnostics to the diagnostics writer
     // TODO: stream diagnostics rather than having to wait util compilation completes
     pub fn check_diagnostics(&mut self) -> Result<bool, codespan_reporting::files::Error> {
@@ -203,6 +506,11 @@ impl Driver {
         for message in &self.messages {
             let diagnostic = message.to_diagnostic(&pretty_arena);
             is_ok &= diagnostic.severity < Severity::Error;
+            match diagnostic.severity {
+                Severity::Bug | Severity::Error => self.error_count += 1,
+                Severity::Warning => self.warning_count += 1,
+                Severity::Note | Severity::Help => {}
+            }
             term::emit(
                 &mut self.diagnostic_writer,
                 &self.codespan_config,
@@ -216,11 +524,138 @@ impl Driver {
         Ok(is_ok)
     }
 
+    /// Write any remaining diagnostics to the diagnostics writer, followed by
+    /// a summary of the number of errors and warnings that were emitted
+    /// during this run, for example:
+    ///
+    /// ```text
+    /// error: aborting due to 3 previous errors; 1 warning emitted
+    /// ```
+    ///
+    /// Returns `false` if any errors were emitted during this run.
+    pub fn finish(&mut self) -> Result<bool, codespan_reporting::files::Error> {
+        self.check_diagnostics()?;
+
+        if let Some(eval_stats) = self.eval_stats {
+            let note = Diagnostic::note()
+                .with_message("counted the work done by the evaluator while reading")
+                .with_notes(vec![
+                    format!("terms evaluated: {}", eval_stats.evals),
+                    format!("function eliminations: {}", eval_stats.function_elims),
+                    format!("values allocated: {}", eval_stats.value_allocs),
+                    format!("Arc clones: {}", eval_stats.arc_clones),
+                ]);
+            term::emit(
+                &mut self.diagnostic_writer,
+                &self.codespan_config,
+                &self.files,
+                &note,
+            )?;
+            self.diagnostic_writer.flush()?;
+        }
+
+        let pluralize = |count: usize, noun: &str| match count {
+            1 => format!("{} {}", count, noun),
+            _ => format!("{} {}s", count, noun),
+        };
+        let summary = match (self.error_count, self.warning_count) {
+            (0, 0) => None,
+            (0, warning_count) => Some(
+                Diagnostic::warning()
+                    .with_message(format!("{} emitted", pluralize(warning_count, "warning"),)),
+            ),
+            (error_count, 0) => Some(Diagnostic::error().with_message(format!(
+                "aborting due to {}",
+                pluralize(error_count, "previous error"),
+            ))),
+            (error_count, warning_count) => Some(Diagnostic::error().with_message(format!(
+                "aborting due to {}; {} emitted",
+                pluralize(error_count, "previous error"),
+                pluralize(warning_count, "warning"),
+            ))),
+        };
+
+        if let Some(summary) = summary {
+            term::emit(
+                &mut self.diagnostic_writer,
+                &self.codespan_config,
+                &self.files,
+                &summary,
+            )?;
+            self.diagnostic_writer.flush()?;
+        }
+
+        Ok(self.error_count == 0)
+    }
+
     // Internals
 
+    /// Run `emit` with a temporary emit writer, returning everything that was
+    /// written to it. The previous emit writer is restored afterwards.
+    fn emit_to_string<E>(
+        &mut self,
+        emit: impl FnOnce(&mut Driver) -> Result<(), E>,
+    ) -> Result<String, E> {
+        let buffer = SharedBuffer::default();
+        let emit_writer = std::mem::replace(&mut self.emit_writer, Box::new(buffer.clone()));
+        let result = emit(self);
+        self.emit_writer = emit_writer;
+        result?;
+
+        let bytes = buffer.0.borrow();
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Start counting the work done by the evaluator, if it is enabled.
+    fn start_eval_stats(&mut self) {
+        if self.count_eval_stats {
+            semantics::start_eval_stats();
+        }
+    }
+
+    /// Add the work done by the evaluator since it was last started to the
+    /// counts for this run.
+    fn finish_eval_stats(&mut self) {
+        if let Some(eval_stats) = semantics::finish_eval_stats() {
+            *self.eval_stats.get_or_insert_with(EvalStats::default) += eval_stats;
+        }
+    }
+
+    /// Emit a value that was read from a binary file, as a binding of the
+    /// given name, flushing the emit writer afterwards.
+    fn emit_value(&mut self, name: impl fmt::Display, value: &Value) -> io::Result<()> {
+        let pretty_arena = pretty::Arena::new(); // TODO: reuse arenas
+        let term = self.surface_to_core.read_back_to_surface(value);
+        let pretty::DocBuilder(_, doc) = surface_to_pretty::from_term(&pretty_arena, &term);
+
+        writeln!(
+            &mut self.emit_writer,
+            "{name} = {term}",
+            name = name,
+            term = doc.pretty(self.emit_width.compute())
+        )?;
+        self.emit_writer.flush()
+    }
+
     fn add_source_file(&mut self, path: &Path) -> Option<usize> {
         match std::fs::read_to_string(path) {
-            Ok(source) => Some(self.files.add(path.display().to_string(), source)),
+            Ok(source) => {
+                self.source_paths.push(path.to_owned());
+                Some(self.files.add(path.display().to_string(), source))
+            }
+            Err(error) => {
+                self.messages.push(Message::ReadFile {
+                    path: path.to_owned(),
+                    error: error.to_string(),
+                });
+                None
+            }
+        }
+    }
+
+    fn read_binary_file(&mut self, path: &Path) -> Option<Vec<u8>> {
+        match std::fs::read(path) {
+            Ok(buffer) => Some(buffer),
             Err(error) => {
                 self.messages.push(Message::ReadFile {
                     path: path.to_owned(),
@@ -249,11 +684,55 @@ impl Driver {
     }
 }
 
-/// An error produced while reading binary data.
+/// Escape the characters in a path that have a special meaning in Makefile
+/// rules.
+fn escape_depfile_path(path: &Path) -> String {
+    let mut escaped = String::new();
+    for ch in path.display().to_string().chars() {
+        match ch {
+            ' ' | '#' => escaped.push('\\'),
+            '$' => escaped.push('$'),
+            _ => {}
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// An uncoloured buffer that can be shared with the driver's emit writer.
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl WriteColor for SharedBuffer {
+    fn supports_color(&self) -> bool {
+        false
+    }
+
+    fn set_color(&mut self, _: &term::termcolor::ColorSpec) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// An error produced while reading (or writing back) binary data.
 #[derive(Debug)]
 pub enum ReadDataError {
     Io(io::Error),
     Read(fathom_runtime::ReadError),
+    Write(fathom_runtime::WriteError),
 }
 
 impl fmt::Display for ReadDataError {
@@ -261,6 +740,7 @@ impl fmt::Display for ReadDataError {
         match self {
             ReadDataError::Io(error) => error.fmt(f),
             ReadDataError::Read(error) => error.fmt(f),
+            ReadDataError::Write(error) => error.fmt(f),
         }
     }
 }
@@ -270,6 +750,7 @@ impl std::error::Error for ReadDataError {
         match self {
             ReadDataError::Io(error) => Some(error),
             ReadDataError::Read(error) => Some(error),
+            ReadDataError::Write(error) => Some(error),
         }
     }
 }
@@ -285,3 +766,127 @@ impl From<fathom_runtime::ReadError> for ReadDataError {
         ReadDataError::Read(error)
     }
 }
+
+impl From<fathom_runtime::WriteError> for ReadDataError {
+    fn from(error: fathom_runtime::WriteError) -> ReadDataError {
+        ReadDataError::Write(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_to_string_emits_core() {
+        let mut driver = Driver::new();
+        driver.set_emit_core(true);
+        driver.set_emit_width(TermWidth::Explicit(80));
+
+        let output = driver
+            .check_to_string(Path::new("../examples/stl.fathom"))
+            .unwrap();
+
+        assert!(driver.check_diagnostics().unwrap());
+        assert!(output.contains("struct Main : Format {"));
+    }
+
+    #[test]
+    fn depfile_lists_source_files() {
+        let header_path = Path::new("../fathom-cli/tests/cli/snapshots/depfile_header.fathom");
+        let main_path = Path::new("../fathom-cli/tests/cli/snapshots/depfile main.fathom");
+
+        let mut driver = Driver::new();
+        driver.check(header_path).unwrap();
+        driver.check(main_path).unwrap();
+        driver.check(header_path).unwrap();
+        assert!(driver.check_diagnostics().unwrap());
+
+        assert_eq!(
+            driver.depfile(Path::new("main.ts")),
+            "main.ts: ../fathom-cli/tests/cli/snapshots/depfile_header.fathom \
+             ../fathom-cli/tests/cli/snapshots/depfile\\ main.fathom\n",
+        );
+    }
+
+    #[test]
+    fn item_hash_ignores_names() {
+        let format_path = Path::new("../fathom-cli/tests/cli/snapshots/item_hash.fathom");
+
+        let mut driver = Driver::new();
+        let hash = |driver: &mut Driver, name| driver.item_hash(format_path, name).unwrap();
+        assert_eq!(hash(&mut driver, "Header"), hash(&mut driver, "Header2"));
+        assert_ne!(hash(&mut driver, "Header"), hash(&mut driver, "Header3"));
+        assert_eq!(hash(&mut driver, "Main"), hash(&mut driver, "Main2"));
+        assert_ne!(hash(&mut driver, "Main"), hash(&mut driver, "Main3"));
+        assert_eq!(driver.item_hash(format_path, "Missing"), None);
+        assert!(driver.check_diagnostics().unwrap());
+    }
+
+    #[test]
+    fn read_data_to_string_emits_surface_terms() {
+        let mut driver = Driver::new();
+        driver.set_emit_width(TermWidth::Explicit(80));
+
+        let output = driver
+            .read_data_to_string(
+                Path::new("../examples/stl.fathom"),
+                "Main",
+                Path::new("../examples/data/stl/cube.stl"),
+            )
+            .unwrap();
+
+        assert!(driver.check_diagnostics().unwrap());
+        assert!(output.starts_with("Main = struct {\n    header = ["));
+    }
+
+    #[test]
+    fn write_format_round_trips() {
+        let mut driver = Driver::new();
+
+        driver
+            .write_format(
+                Path::new("../examples/stl.fathom"),
+                "Main",
+                Path::new("../examples/data/stl/cube.stl"),
+            )
+            .unwrap();
+
+        assert!(driver.check_diagnostics().unwrap());
+    }
+
+    #[test]
+    fn global_types_are_pretty_printed() {
+        let mut driver = Driver::new();
+        let global_types = driver.global_types();
+
+        let type_of = |name| {
+            global_types
+                .iter()
+                .find(|(global_name, _)| *global_name == name)
+                .map(|(_, r#type)| r#type.as_str())
+        };
+
+        assert_eq!(type_of("U16Be"), Some("Format"));
+        assert_eq!(type_of("Int"), Some("Type"));
+        assert_eq!(type_of("FormatArray"), Some("Int -> Format -> Format"));
+        assert_eq!(type_of("Link"), Some("Pos -> Int -> Format -> Format"));
+        assert_eq!(type_of("NotAGlobal"), None);
+    }
+
+    #[test]
+    fn missing_file_has_explained_code() {
+        let mut driver = Driver::new();
+        driver.set_diagnostic_writer(SharedBuffer::default());
+        driver
+            .check(Path::new("../examples/missing.fathom"))
+            .unwrap();
+
+        let pretty_arena = pretty::Arena::new();
+        let diagnostic = driver.messages[0].to_diagnostic(&pretty_arena);
+
+        assert_eq!(diagnostic.code.as_deref(), Some("E0002"));
+        assert!(!Driver::explain("E0002").unwrap().is_empty());
+        assert_eq!(Driver::explain("E9999"), None);
+    }
+}
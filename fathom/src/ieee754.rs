@@ -31,6 +31,57 @@ impl Ieee754 for f64 {
     }
 }
 
+/// Convert the bits of an IEEE-754 half-precision float to a single-precision
+/// float. Every half-precision float is exactly representable as a
+/// single-precision float, so this conversion is lossless.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits & 0x8000) << 16;
+    let exponent = u32::from((bits >> 10) & 0x1f);
+    let mantissa = u32::from(bits & 0x03ff);
+    let magnitude = match exponent {
+        0 => f32::from(bits & 0x03ff) * f32::powi(2.0, -24),
+        0x1f => f32::from_bits(0x7f80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    };
+    f32::from_bits(sign | magnitude.to_bits())
+}
+
+/// Convert a single-precision float to the bits of the nearest IEEE-754
+/// half-precision float, rounding ties to even. Values that are too large to
+/// be represented become infinities, and NaNs remain NaNs.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent == 0xff {
+        let quiet_bit = if mantissa == 0 { 0 } else { 0x0200 };
+        return sign | 0x7c00 | quiet_bit | (mantissa >> 13) as u16;
+    }
+
+    let exponent = exponent - 127 + 15;
+    let (half, shift) = match exponent {
+        _ if exponent >= 0x1f => return sign | 0x7c00,
+        _ if exponent < -10 => return sign,
+        // Subnormal half-precision floats, with the implicit leading bit made
+        // explicit in the mantissa
+        _ if exponent <= 0 => {
+            let shift = (14 - exponent) as u32;
+            ((mantissa | 0x0080_0000) >> shift, shift)
+        }
+        _ => (((exponent as u32) << 10) | (mantissa >> 13), 13),
+    };
+    let remainder = (mantissa | 0x0080_0000) & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+    // A carry out of the mantissa correctly rounds up to the next exponent
+    let half = match remainder {
+        _ if remainder > halfway || (remainder == halfway && half & 1 == 1) => half + 1,
+        _ => half,
+    };
+    sign | half as u16
+}
+
 /// Use bitwise equality, combined with a NaN check to provide a
 /// logically consistent equality comparison of floating point
 /// numbers. This means that the following weirdness (from an
@@ -52,8 +103,8 @@ pub fn logical_eq<T: Ieee754>(value0: T, value1: T) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use proptest::num::{f32, f64};
-    use proptest::{prop_assert, proptest};
+    use proptest::num::{f32, f64, u16};
+    use proptest::{prop_assert, prop_assert_eq, proptest};
 
     use super::*;
 
@@ -77,6 +128,23 @@ mod tests {
             prop_assert!(logical_eq(value0, value1));
         }
 
+        #[test]
+        fn f16_round_trip(bits in u16::ANY) {
+            let value = f16_to_f32(bits);
+            match value.is_nan() {
+                true => prop_assert!(f32_to_f16(value) & 0x7c00 == 0x7c00),
+                false => prop_assert_eq!(f32_to_f16(value), bits),
+            }
+        }
+
+        #[test]
+        fn f16_rounding(bits in 0..0x7bffu16) {
+            // Halfway between two half-precision floats rounds to the even one
+            let (value0, value1) = (f16_to_f32(bits), f16_to_f32(bits + 1));
+            let even_bits = if bits & 1 == 0 { bits } else { bits + 1 };
+            prop_assert_eq!(f32_to_f16(value0 + (value1 - value0) / 2.0), even_bits);
+        }
+
         #[test]
         fn f64_reflexive(value in f64::ANY) {
             prop_assert!(logical_eq(value, value));
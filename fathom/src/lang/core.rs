@@ -17,6 +17,8 @@ mod grammar {
 }
 
 pub mod binary;
+pub mod hash;
+pub mod inline;
 pub mod semantics;
 pub mod typing;
 
@@ -113,23 +115,51 @@ pub enum Sort {
 pub enum Primitive {
     /// Integer constants.
     Int(BigInt),
+    /// IEEE-754 half-precision floating point constants, stored as their bits.
+    F16(u16),
     /// IEEE-754 single-precision floating point constants.
     F32(f32),
     /// IEEE-754 double-precision floating point constants.
     F64(f64),
+    /// Unicode strings.
+    String(String),
     /// Positions in the byte stream.
     ///
     /// These should only appear when using the binary interpreter.
     Pos(usize),
 }
 
+impl Primitive {
+    /// The name of the global that refers to this primitive, if it is a
+    /// non-finite floating point value that can not be written as a numeric
+    /// literal.
+    pub fn float_global_name(&self) -> Option<&'static str> {
+        match self {
+            Primitive::F16(bits) if ieee754::f16_to_f32(*bits).is_nan() => Some("F16Nan"),
+            Primitive::F16(0x7c00) => Some("F16Infinity"),
+            Primitive::F16(0xfc00) => Some("F16NegInfinity"),
+            Primitive::F32(value) if value.is_nan() => Some("F32Nan"),
+            Primitive::F32(value) if *value == f32::INFINITY => Some("F32Infinity"),
+            Primitive::F32(value) if *value == f32::NEG_INFINITY => Some("F32NegInfinity"),
+            Primitive::F64(value) if value.is_nan() => Some("F64Nan"),
+            Primitive::F64(value) if *value == f64::INFINITY => Some("F64Infinity"),
+            Primitive::F64(value) if *value == f64::NEG_INFINITY => Some("F64NegInfinity"),
+            _ => None,
+        }
+    }
+}
+
 impl PartialEq for Primitive {
     fn eq(&self, other: &Primitive) -> bool {
         match (self, other) {
             (Primitive::Int(val0), Primitive::Int(val1)) => val0 == val1,
+            (Primitive::F16(val0), Primitive::F16(val1)) => {
+                ieee754::logical_eq(ieee754::f16_to_f32(*val0), ieee754::f16_to_f32(*val1))
+            }
             (Primitive::F32(val0), Primitive::F32(val1)) => ieee754::logical_eq(*val0, *val1),
             (Primitive::F64(val0), Primitive::F64(val1)) => ieee754::logical_eq(*val0, *val1),
             (Primitive::Pos(val0), Primitive::Pos(val1)) => val0 == val1,
+            (Primitive::String(val0), Primitive::String(val1)) => val0 == val1,
             (_, _) => false,
         }
     }
@@ -202,17 +232,27 @@ pub struct FieldDefinition {
 /// An environment of global definitions.
 pub struct Globals {
     entries: BTreeMap<String, (Arc<Term>, Option<Arc<Term>>)>,
+    /// The fields of the global struct types, along with the number of
+    /// parameters that the struct types take.
+    struct_types: BTreeMap<String, (usize, Arc<[FieldDeclaration]>)>,
 }
 
 impl Globals {
     pub fn new(entries: BTreeMap<String, (Arc<Term>, Option<Arc<Term>>)>) -> Globals {
-        Globals { entries }
+        Globals {
+            entries,
+            struct_types: BTreeMap::new(),
+        }
     }
 
     pub fn get(&self, name: &str) -> Option<&(Arc<Term>, Option<Arc<Term>>)> {
         self.entries.get(name)
     }
 
+    pub fn get_struct_type(&self, name: &str) -> Option<&(usize, Arc<[FieldDeclaration]>)> {
+        self.struct_types.get(name)
+    }
+
     pub fn entries(&self) -> impl Iterator<Item = (&String, &(Arc<Term>, Option<Arc<Term>>))> {
         self.entries.iter()
     }
@@ -228,8 +268,50 @@ impl Default for Globals {
         let mut entries = BTreeMap::new();
 
         entries.insert("Int".to_owned(), (Arc::new(term(Sort(Type))), None));
+        entries.insert("F16".to_owned(), (Arc::new(term(Sort(Type))), None));
         entries.insert("F32".to_owned(), (Arc::new(term(Sort(Type))), None));
         entries.insert("F64".to_owned(), (Arc::new(term(Sort(Type))), None));
+        entries.insert("String".to_owned(), (Arc::new(term(Sort(Type))), None));
+        entries.insert("Void".to_owned(), (Arc::new(term(Sort(Type))), None));
+        for (name, bits) in &[
+            ("F16Infinity", 0x7c00),
+            ("F16NegInfinity", 0xfc00),
+            ("F16Nan", 0x7e00),
+        ] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(Global("F16".to_owned()))),
+                    Some(Arc::new(term(Primitive(self::Primitive::F16(*bits))))),
+                ),
+            );
+        }
+        for (name, value) in &[
+            ("F32Infinity", f32::INFINITY),
+            ("F32NegInfinity", f32::NEG_INFINITY),
+            ("F32Nan", f32::NAN),
+        ] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(Global("F32".to_owned()))),
+                    Some(Arc::new(term(Primitive(self::Primitive::F32(*value))))),
+                ),
+            );
+        }
+        for (name, value) in &[
+            ("F64Infinity", f64::INFINITY),
+            ("F64NegInfinity", f64::NEG_INFINITY),
+            ("F64Nan", f64::NAN),
+        ] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(Global("F64".to_owned()))),
+                    Some(Arc::new(term(Primitive(self::Primitive::F64(*value))))),
+                ),
+            );
+        }
         entries.insert("Bool".to_owned(), (Arc::new(term(Sort(Type))), None));
         entries.insert(
             "true".to_owned(),
@@ -239,6 +321,71 @@ impl Default for Globals {
             "false".to_owned(),
             (Arc::new(term(Global("Bool".to_owned()))), None),
         );
+        entries.insert("Unit".to_owned(), (Arc::new(term(Sort(Type))), None));
+        entries.insert(
+            "unit".to_owned(),
+            (Arc::new(term(Global("Unit".to_owned()))), None),
+        );
+        entries.insert(
+            "BoolSelect".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Bool".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Int".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(Global("Int".to_owned()))),
+                        ))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        for name in &["IntTestBits", "IntEq"] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Int".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(Global("Bool".to_owned()))),
+                        ))),
+                    ))),
+                    None,
+                ),
+            );
+        }
+        for (name, param_type, body_type) in
+            &[("F16ToF32", "F16", "F32"), ("F32ToF16", "F32", "F16")]
+        {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global(param_type.to_string()))),
+                        Arc::new(term(Global(body_type.to_string()))),
+                    ))),
+                    None,
+                ),
+            );
+        }
+        for name in &["IntAdd", "IntMax", "IntDiv"] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Int".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(Global("Int".to_owned()))),
+                        ))),
+                    ))),
+                    None,
+                ),
+            );
+        }
         entries.insert(
             "Array".to_owned(),
             (
@@ -252,11 +399,33 @@ impl Default for Globals {
                 None,
             ),
         );
+        entries.insert(
+            "List".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(Sort(Type))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "Option".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(Sort(Type))),
+                ))),
+                None,
+            ),
+        );
         entries.insert("Pos".to_owned(), (Arc::new(term(Sort(Type))), None));
 
         entries.insert("U8".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("U16Le".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("U16Be".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert("U24Le".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert("U24Be".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("U32Le".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("U32Be".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("U64Le".to_owned(), (Arc::new(term(FormatType)), None));
@@ -264,14 +433,62 @@ impl Default for Globals {
         entries.insert("S8".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("S16Le".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("S16Be".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert("S24Le".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert("S24Be".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("S32Le".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("S32Be".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("S64Le".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("S64Be".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert("Endianness".to_owned(), (Arc::new(term(Sort(Type))), None));
+        for name in &["big", "little"] {
+            entries.insert(
+                name.to_string(),
+                (Arc::new(term(Global("Endianness".to_owned()))), None),
+            );
+        }
+        for name in &["FormatUInt", "FormatSInt"] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Endianness".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                    None,
+                ),
+            );
+        }
+        entries.insert(
+            "FormatULeb128".to_owned(),
+            (Arc::new(term(FormatType)), None),
+        );
+        entries.insert(
+            "FormatSLeb128".to_owned(),
+            (Arc::new(term(FormatType)), None),
+        );
+        entries.insert("FormatVlq".to_owned(), (Arc::new(term(FormatType)), None));
+        for name in &["U16", "U32", "U64", "S16", "S32", "S64"] {
+            entries.insert(name.to_string(), (Arc::new(term(FormatType)), None));
+        }
+        entries.insert("F16Le".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert("F16Be".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("F32Le".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("F32Be".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("F64Le".to_owned(), (Arc::new(term(FormatType)), None));
         entries.insert("F64Be".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert(
+            "FormatSize".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(Global("Int".to_owned()))),
+                ))),
+                None,
+            ),
+        );
         entries.insert(
             "FormatArray".to_owned(),
             (
@@ -285,7 +502,423 @@ impl Default for Globals {
                 None,
             ),
         );
+        entries.insert(
+            "FormatArrayMapIndex".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(FormatType)),
+                        ))),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayChunked".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Int".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(FormatType)),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayReverse".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayTranspose".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayWindows".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayIndexed".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArraySplit".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayTake".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayDrop".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayFold".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(FunctionType(
+                                Arc::new(term(Global("Int".to_owned()))),
+                                Arc::new(term(Global("Int".to_owned()))),
+                            ))),
+                        ))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(FormatType)),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayZip".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayConcat".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArraySorted".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayUnique".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        for name in &[
+            "FormatArraySortedBy",
+            "FormatArrayUniqueBy",
+            "FormatArraySortBy",
+            "FormatArrayDedupBy",
+        ] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("String".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(FormatType)),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                    None,
+                ),
+            );
+        }
+        entries.insert(
+            "FormatArraySort".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayDedup".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatArrayRemaining".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        for name in &["FormatRepeatUntil", "FormatRepeatUntilInclusive"] {
+            entries.insert(
+                name.to_string(),
+                (
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(Global("Bool".to_owned()))),
+                        ))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(FormatType)),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                    None,
+                ),
+            );
+        }
+        entries.insert(
+            "FormatLengthPrefixed".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
         entries.insert("CurrentPos".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert(
+            "FormatAlign".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatFixed".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatConst".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatAssertSize".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatNested".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Bool".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(FormatType)),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatPadded".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Int".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(FormatType)),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatPresentIf".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Bool".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatMinVersion".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Int".to_owned()))),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(Global("Int".to_owned()))),
+                            Arc::new(term(FunctionType(
+                                Arc::new(term(FormatType)),
+                                Arc::new(term(FormatType)),
+                            ))),
+                        ))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        entries.insert("FormatBom".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert("FormatNever".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert(
+            "FormatUtf8".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatReprCoerce".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(FormatType)),
+                    Arc::new(term(FormatType)),
+                ))),
+                None,
+            ),
+        );
+        entries.insert(
+            "FormatAsciiLine".to_owned(),
+            (Arc::new(term(FormatType)), None),
+        );
+        entries.insert("FormatCStr".to_owned(), (Arc::new(term(FormatType)), None));
+        entries.insert(
+            "FormatStrFixed".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Int".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Global("Int".to_owned()))),
+                        Arc::new(term(FormatType)),
+                    ))),
+                ))),
+                None,
+            ),
+        );
         entries.insert(
             "Link".to_owned(),
             (
@@ -302,8 +935,183 @@ impl Default for Globals {
                 None,
             ),
         );
+        entries.insert(
+            "FormatAtOffsets".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Global("Pos".to_owned()))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(FormatType)),
+                        Arc::new(term(FunctionType(
+                            Arc::new(term(FormatType)),
+                            Arc::new(term(FormatType)),
+                        ))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+
+        let field = |label: &str, r#type: Term| FieldDeclaration {
+            doc: Arc::new([]),
+            label: Located::generated(label.to_owned()),
+            type_: Arc::new(r#type),
+        };
+
+        let mut struct_types = BTreeMap::new();
 
-        Globals::new(entries)
+        entries.insert(
+            "Pair".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(FunctionType(
+                        Arc::new(term(Sort(Type))),
+                        Arc::new(term(Sort(Type))),
+                    ))),
+                ))),
+                None,
+            ),
+        );
+        struct_types.insert(
+            "Pair".to_owned(),
+            (
+                2,
+                Arc::from(vec![
+                    field("fst", term(Local(LocalIndex(1)))),
+                    field("snd", term(Local(LocalIndex(1)))),
+                ]),
+            ),
+        );
+
+        entries.insert(
+            "Indexed".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(Sort(Type))),
+                ))),
+                None,
+            ),
+        );
+        struct_types.insert(
+            "Indexed".to_owned(),
+            (
+                1,
+                Arc::from(vec![
+                    field("index", term(Global("Int".to_owned()))),
+                    field("value", term(Local(LocalIndex(1)))),
+                ]),
+            ),
+        );
+
+        entries.insert(
+            "Folded".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(Sort(Type))),
+                ))),
+                None,
+            ),
+        );
+        struct_types.insert(
+            "Folded".to_owned(),
+            (
+                1,
+                Arc::from(vec![
+                    field("elems", term(Local(LocalIndex(0)))),
+                    field("result", term(Global("Int".to_owned()))),
+                ]),
+            ),
+        );
+
+        entries.insert(
+            "Version".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(Sort(Type))),
+                ))),
+                None,
+            ),
+        );
+        struct_types.insert(
+            "Version".to_owned(),
+            (
+                1,
+                Arc::from(vec![
+                    field("major", term(Local(LocalIndex(0)))),
+                    field("minor", term(Local(LocalIndex(1)))),
+                    field("patch", term(Local(LocalIndex(2)))),
+                ]),
+            ),
+        );
+
+        entries.insert(
+            "Split".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(Sort(Type))),
+                ))),
+                None,
+            ),
+        );
+        struct_types.insert(
+            "Split".to_owned(),
+            (
+                1,
+                Arc::from(vec![
+                    field(
+                        "head",
+                        term(FunctionElim(
+                            Arc::new(term(Global("List".to_owned()))),
+                            Arc::new(term(Local(LocalIndex(0)))),
+                        )),
+                    ),
+                    field(
+                        "tail",
+                        term(FunctionElim(
+                            Arc::new(term(Global("List".to_owned()))),
+                            Arc::new(term(Local(LocalIndex(1)))),
+                        )),
+                    ),
+                ]),
+            ),
+        );
+
+        entries.insert(
+            "Captured".to_owned(),
+            (
+                Arc::new(term(FunctionType(
+                    Arc::new(term(Sort(Type))),
+                    Arc::new(term(Sort(Type))),
+                ))),
+                None,
+            ),
+        );
+        struct_types.insert(
+            "Captured".to_owned(),
+            (
+                1,
+                Arc::from(vec![
+                    field(
+                        "raw",
+                        term(FunctionElim(
+                            Arc::new(term(Global("List".to_owned()))),
+                            Arc::new(term(Global("Int".to_owned()))),
+                        )),
+                    ),
+                    field("value", term(Local(LocalIndex(1)))),
+                ]),
+            ),
+        );
+
+        Globals {
+            entries,
+            struct_types,
+        }
     }
 }
 
@@ -3,4 +3,137 @@
 //! This is only a naive implementation, and intended for getting a better idea
 //! of whether our compiled back-ends actually meet the specification.
 
+use num_traits::ToPrimitive;
+use std::sync::Arc;
+
+use crate::lang::core::semantics::Value;
+use crate::lang::core::Primitive;
+
 pub mod read;
+pub mod write;
+
+/// The byte order to use when reading or writing the integer formats that do
+/// not specify one, for example `U16` rather than `U16Be` or `U16Le`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Endianness {
+    #[default]
+    Big,
+    Little,
+}
+
+impl Endianness {
+    /// The suffix of the names of the integer formats that use this byte order.
+    fn format_suffix(self) -> &'static str {
+        match self {
+            Endianness::Big => "Be",
+            Endianness::Little => "Le",
+        }
+    }
+
+    /// Resolve the name of an integer format that does not specify a byte
+    /// order, like `U16`, to the name of the format that uses this byte order.
+    pub fn resolve_format_name(self, name: &str) -> Option<String> {
+        match name {
+            "U16" | "U32" | "U64" | "S16" | "S32" | "S64" => {
+                Some(format!("{}{}", name, self.format_suffix()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Byte order marks, along with the encodings they identify.
+const BYTE_ORDER_MARKS: [(&[u8], u8); 3] = [
+    (&[0xEF, 0xBB, 0xBF], 1), // UTF-8
+    (&[0xFE, 0xFF], 2),       // UTF-16 (big endian)
+    (&[0xFF, 0xFE], 3),       // UTF-16 (little endian)
+];
+
+/// An error produced while transposing an array of arrays.
+enum TransposeError {
+    /// The value was not an array of arrays.
+    NotAnArray,
+    /// The row at the given index had a different length to the first row.
+    MismatchedRowLength(usize),
+}
+
+/// Swap the rows and columns of an array of arrays.
+///
+/// Every row must have the same length. An empty array transposes to an empty
+/// array.
+fn transpose(value: &Value) -> Result<Value, TransposeError> {
+    let row_values = match value {
+        Value::ArrayTerm(row_values) => row_values,
+        _ => return Err(TransposeError::NotAnArray),
+    };
+
+    let mut rows = Vec::<&[Arc<Value>]>::with_capacity(row_values.len());
+    for (index, row_value) in row_values.iter().enumerate() {
+        match row_value.as_ref() {
+            Value::ArrayTerm(elem_values) if index == 0 || elem_values.len() == rows[0].len() => {
+                rows.push(elem_values.as_slice());
+            }
+            Value::ArrayTerm(_) => return Err(TransposeError::MismatchedRowLength(index)),
+            _ => return Err(TransposeError::NotAnArray),
+        }
+    }
+
+    let cols = rows.first().map_or(0, |row| row.len());
+    let col_values = (0..cols)
+        .map(|col| {
+            let elem_values = rows.iter().map(|row| row[col].clone()).collect();
+            Arc::new(Value::ArrayTerm(elem_values))
+        })
+        .collect();
+
+    Ok(Value::ArrayTerm(col_values))
+}
+
+/// Collect the overlapping windows of `size` consecutive elements of an array.
+///
+/// The size must be at least one, and no greater than the length of the array.
+fn windows(size: usize, elem_values: &[Arc<Value>]) -> Option<Value> {
+    if size == 0 || size > elem_values.len() {
+        return None;
+    }
+
+    let window_values = (elem_values.windows(size))
+        .map(|window| Arc::new(Value::ArrayTerm(window.to_vec())))
+        .collect();
+
+    Some(Value::ArrayTerm(window_values))
+}
+
+/// Recover the elements of an array from its overlapping windows. This is the
+/// inverse of [`windows`].
+fn unwindows(size: usize, window_values: &[Arc<Value>]) -> Option<Value> {
+    let mut elem_values = Vec::new();
+    for (index, window_value) in window_values.iter().enumerate() {
+        match window_value.as_ref() {
+            Value::ArrayTerm(window) if window.len() == size && index == 0 => {
+                elem_values.extend(window.iter().cloned());
+            }
+            Value::ArrayTerm(window) if window.len() == size => {
+                elem_values.extend(window.last().cloned());
+            }
+            _ => return None,
+        }
+    }
+
+    match elem_values.is_empty() {
+        true => None,
+        false => Some(Value::ArrayTerm(elem_values)),
+    }
+}
+
+/// The number that the backing integer of a fixed-point number is divided by,
+/// given the number of fractional bits.
+fn fixed_scale(frac_bits: &Value) -> Option<f64> {
+    match frac_bits {
+        Value::Primitive(Primitive::Int(frac_bits)) => match frac_bits.to_i32() {
+            Some(frac_bits @ 0..=64) => Some(2f64.powi(frac_bits)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -1,10 +1,15 @@
 use contracts::debug_ensures;
 use fathom_runtime::{FormatReader, ReadError};
+use itertools::Itertools;
 use num_traits::ToPrimitive;
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::convert::TryFrom;
 use std::sync::Arc;
 
+use crate::ieee754::f16_to_f32;
 use crate::lang::core;
+use crate::lang::core::binary::{self, Endianness, TransposeError, BYTE_ORDER_MARKS};
 use crate::lang::core::semantics::{self, Elim, Head, Value};
 use crate::lang::core::{FieldDeclaration, Globals, ItemData, Module, Primitive};
 
@@ -14,6 +19,10 @@ pub struct Context<'globals> {
     items: HashMap<String, semantics::Item>,
     locals: core::Locals<Arc<Value>>,
     pending_links: VecDeque<(usize, Arc<Value>)>,
+    default_endianness: Endianness,
+    step_limit: Option<usize>,
+    /// The number of formats read since the start of the current item.
+    steps: usize,
 }
 
 impl<'globals> Context<'globals> {
@@ -24,6 +33,9 @@ impl<'globals> Context<'globals> {
             items: HashMap::new(),
             locals: core::Locals::new(),
             pending_links: VecDeque::new(),
+            default_endianness: Endianness::default(),
+            step_limit: None,
+            steps: 0,
         };
 
         for item in &module.items {
@@ -55,6 +67,40 @@ impl<'globals> Context<'globals> {
         context
     }
 
+    /// Set the byte order to use for the integer formats that do not specify
+    /// one, for example `U16` rather than `U16Be` or `U16Le`. This defaults to
+    /// [`Endianness::Big`].
+    pub fn set_default_endianness(&mut self, default_endianness: Endianness) {
+        self.default_endianness = default_endianness;
+    }
+
+    /// Classify a module item by the head of the format that it defines,
+    /// returning `None` if the item is missing or does not define a format.
+    ///
+    /// Constants are classified by their head alone, so constants that define
+    /// host types are not distinguished from formats.
+    pub fn classify_item(&self, name: &str) -> Option<semantics::FormatClass> {
+        match self.items.get(name).map(|item| &item.data) {
+            Some(semantics::ItemData::StructFormat(_, _)) => {
+                Some(semantics::FormatClass::Struct(name.to_owned()))
+            }
+            Some(semantics::ItemData::Constant(value)) => match value.as_ref() {
+                Value::Stuck(_, _) => Some(semantics::classify_format(value)),
+                _ => None,
+            },
+            Some(semantics::ItemData::StructType(_, _)) | None => None,
+        }
+    }
+
+    /// Limit the number of formats that can be read when reading an item,
+    /// including the formats nested inside it. Reading fails with
+    /// [`ReadError::ReadTimeout`] once the limit is exceeded. This guards
+    /// against pathological data descriptions when reading untrusted data. By
+    /// default there is no limit.
+    pub fn set_step_limit(&mut self, step_limit: Option<usize>) {
+        self.step_limit = step_limit;
+    }
+
     /// Evaluate a term in the parser context.
     fn eval(&mut self, term: &core::Term) -> Arc<Value> {
         semantics::eval(self.globals, &self.items, &mut self.locals, term)
@@ -70,6 +116,12 @@ impl<'globals> Context<'globals> {
     }
 
     /// Read a module item in the context.
+    ///
+    /// Positions, including the targets of links, are measured from the start
+    /// of the reader's scope, not from the point where the item begins. Use
+    /// [`ReadScope::reader_at`] to read an item embedded in a larger buffer.
+    ///
+    /// [`ReadScope::reader_at`]: fathom_runtime::ReadScope::reader_at
     #[debug_ensures(self.locals.is_empty())]
     #[debug_ensures(self.pending_links.is_empty())]
     pub fn read_item(
@@ -77,7 +129,8 @@ impl<'globals> Context<'globals> {
         reader: &mut FormatReader<'_>,
         name: &str,
     ) -> Result<(Value, HashMap<usize, Arc<Value>>), ReadError> {
-        let root_scope = reader.scope();
+        self.steps = 0;
+        let root_reader = reader.clone();
         let parsed_value = match self.items.get(name).cloned().map(|item| item.data) {
             Some(semantics::ItemData::Constant(value)) => self.read_format(reader, &value),
             Some(semantics::ItemData::StructFormat(0, field_declarations)) => {
@@ -100,7 +153,7 @@ impl<'globals> Context<'globals> {
                     match parsed_links.entry(offset) {
                         // The offset has not yet been parsed...
                         Entry::Vacant(parsed_entry) => {
-                            let mut inner_reader = root_scope.offset(offset).reader();
+                            let mut inner_reader = root_reader.scope_at_pos(offset).reader();
                             let value = match self.read_format(&mut inner_reader, &format) {
                                 Ok(value) => value,
                                 Err(error) => {
@@ -127,6 +180,38 @@ impl<'globals> Context<'globals> {
         result
     }
 
+    /// Read a single field of a module item, following the labels in
+    /// `field_path` through nested struct formats. Reading stops as soon as
+    /// the field has been read, so the data that follows it is never consumed.
+    /// Preceding fields are skipped over when their size is statically known
+    /// and the field does not depend on their values.
+    #[debug_ensures(self.locals.is_empty())]
+    #[debug_ensures(self.pending_links.is_empty())]
+    pub fn read_item_field(
+        &mut self,
+        reader: &mut FormatReader<'_>,
+        name: &str,
+        field_path: &[&str],
+    ) -> Result<Value, ReadError> {
+        self.steps = 0;
+        let parsed_value = match self.items.get(name).cloned().map(|item| item.data) {
+            Some(semantics::ItemData::Constant(value)) => {
+                self.read_format_field(reader, &value, field_path)
+            }
+            Some(semantics::ItemData::StructFormat(0, field_declarations)) => {
+                self.read_struct_format_field(reader, &field_declarations, &[], field_path)
+            }
+            Some(semantics::ItemData::StructFormat(_, _))
+            | Some(semantics::ItemData::StructType(_, _))
+            | None => Err(ReadError::InvalidDataDescription), // TODO: Improve error!
+        };
+
+        // Links are only followed when reading whole items.
+        self.pending_links.clear();
+
+        parsed_value
+    }
+
     #[debug_ensures(self.items.len() == old(self.items.len()))]
     #[debug_ensures(self.locals.size() == old(self.locals.size()))]
     fn read_struct_format(
@@ -150,7 +235,13 @@ impl<'globals> Context<'globals> {
         for field_declaration in field_declarations.iter() {
             let label = field_declaration.label.data.clone();
             let format = self.eval_with_locals(&mut format_locals, &field_declaration.type_);
-            let value = Arc::new(self.read_format(reader, &format)?);
+            let value = match self.read_format(reader, &format) {
+                // Record the innermost field that the overflow occurred in.
+                Err(ReadError::ArithmeticOverflow { field: None }) => {
+                    return Err(ReadError::ArithmeticOverflow { field: Some(label) });
+                }
+                result => Arc::new(result?),
+            };
 
             format_locals.push(value.clone());
             fields.insert(label, value);
@@ -159,6 +250,90 @@ impl<'globals> Context<'globals> {
         Ok(Value::StructTerm(fields))
     }
 
+    #[debug_ensures(self.items.len() == old(self.items.len()))]
+    #[debug_ensures(self.locals.size() == old(self.locals.size()))]
+    fn read_struct_format_field(
+        &mut self,
+        reader: &mut FormatReader<'_>,
+        field_declarations: &[FieldDeclaration],
+        elims: &[Elim],
+        field_path: &[&str],
+    ) -> Result<Value, ReadError> {
+        let (label, field_path) = match field_path.split_first() {
+            Some((label, field_path)) => (label, field_path),
+            None => return self.read_struct_format(reader, field_declarations, elims),
+        };
+        let target = (field_declarations.iter())
+            .position(|field_declaration| field_declaration.label.data == *label)
+            .ok_or_else(|| ReadError::UnknownField {
+                label: label.to_string(),
+            })?;
+
+        let mut format_locals = core::Locals::new();
+
+        for elim in elims {
+            match elim {
+                Elim::Function(value) => format_locals.push(value.clone()),
+                _ => panic!("invalid elimination"),
+            }
+        }
+
+        for (index, field_declaration) in field_declarations[..target].iter().enumerate() {
+            let format = self.eval_with_locals(&mut format_locals, &field_declaration.type_);
+            // Fields with a statically known size can be skipped over, so long
+            // as none of the fields up to the target field depend on them.
+            let is_used = (field_declarations[(index + 1)..=target].iter().enumerate()).any(
+                |(offset, field_declaration)| mentions_local(&field_declaration.type_, offset),
+            );
+            let size = match is_used {
+                true => None,
+                false => semantics::format_size(self.globals, &self.items, &format),
+            };
+            let value = match size.and_then(|size| size.to_usize()) {
+                Some(size) => {
+                    reader.read_scope(size)?;
+                    Value::Error
+                }
+                None => self.read_format(reader, &format)?,
+            };
+
+            format_locals.push(Arc::new(value));
+        }
+
+        let format = self.eval_with_locals(&mut format_locals, &field_declarations[target].type_);
+        self.read_format_field(reader, &format, field_path)
+    }
+
+    #[debug_ensures(self.items.len() == old(self.items.len()))]
+    #[debug_ensures(self.locals.size() == old(self.locals.size()))]
+    fn read_format_field(
+        &mut self,
+        reader: &mut FormatReader<'_>,
+        format: &Value,
+        field_path: &[&str],
+    ) -> Result<Value, ReadError> {
+        match (format, field_path) {
+            (format, []) => self.read_format(reader, format),
+            (Value::Stuck(Head::Item(item_name), elims), field_path) => {
+                match self.items.get(item_name).cloned().map(|item| item.data) {
+                    Some(semantics::ItemData::StructFormat(arity, field_declarations)) => self
+                        .read_struct_format_field(
+                            reader,
+                            &field_declarations,
+                            &elims[..arity],
+                            field_path,
+                        ),
+                    Some(_) | None => Err(ReadError::UnknownField {
+                        label: field_path[0].to_owned(),
+                    }),
+                }
+            }
+            (_, field_path) => Err(ReadError::UnknownField {
+                label: field_path[0].to_owned(),
+            }),
+        }
+    }
+
     #[debug_ensures(self.items.len() == old(self.items.len()))]
     #[debug_ensures(self.locals.size() == old(self.locals.size()))]
     fn read_format(
@@ -166,11 +341,29 @@ impl<'globals> Context<'globals> {
         reader: &mut FormatReader<'_>,
         format: &Value,
     ) -> Result<Value, ReadError> {
+        self.steps += 1;
+        match self.step_limit {
+            Some(limit) if self.steps > limit => return Err(ReadError::ReadTimeout { limit }),
+            Some(_) | None => {}
+        }
+
         match format {
             Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
+                ("U16", [])
+                | ("U32", [])
+                | ("U64", [])
+                | ("S16", [])
+                | ("S32", [])
+                | ("S64", []) => {
+                    let suffix = self.default_endianness.format_suffix();
+                    let format = Value::global(format!("{}{}", name, suffix), Vec::new());
+                    self.read_format(reader, &format)
+                }
                 ("U8", []) => Ok(Value::int(reader.read::<fathom_runtime::U8>()?)),
                 ("U16Le", []) => Ok(Value::int(reader.read::<fathom_runtime::U16Le>()?)),
                 ("U16Be", []) => Ok(Value::int(reader.read::<fathom_runtime::U16Be>()?)),
+                ("U24Le", []) => Ok(Value::int(reader.read::<fathom_runtime::U24Le>()?)),
+                ("U24Be", []) => Ok(Value::int(reader.read::<fathom_runtime::U24Be>()?)),
                 ("U32Le", []) => Ok(Value::int(reader.read::<fathom_runtime::U32Le>()?)),
                 ("U32Be", []) => Ok(Value::int(reader.read::<fathom_runtime::U32Be>()?)),
                 ("U64Le", []) => Ok(Value::int(reader.read::<fathom_runtime::U64Le>()?)),
@@ -178,10 +371,17 @@ impl<'globals> Context<'globals> {
                 ("S8", []) => Ok(Value::int(reader.read::<fathom_runtime::I8>()?)),
                 ("S16Le", []) => Ok(Value::int(reader.read::<fathom_runtime::I16Le>()?)),
                 ("S16Be", []) => Ok(Value::int(reader.read::<fathom_runtime::I16Be>()?)),
+                ("S24Le", []) => Ok(Value::int(reader.read::<fathom_runtime::I24Le>()?)),
+                ("S24Be", []) => Ok(Value::int(reader.read::<fathom_runtime::I24Be>()?)),
                 ("S32Le", []) => Ok(Value::int(reader.read::<fathom_runtime::I32Le>()?)),
                 ("S32Be", []) => Ok(Value::int(reader.read::<fathom_runtime::I32Be>()?)),
                 ("S64Le", []) => Ok(Value::int(reader.read::<fathom_runtime::I64Le>()?)),
                 ("S64Be", []) => Ok(Value::int(reader.read::<fathom_runtime::I64Be>()?)),
+                ("FormatULeb128", []) => Ok(Value::int(read_uleb128(reader)?)),
+                ("FormatVlq", []) => Ok(Value::int(read_vlq(reader)?)),
+                ("FormatSLeb128", []) => Ok(Value::int(read_sleb128(reader)?)),
+                ("F16Le", []) => Ok(Value::f16(reader.read::<fathom_runtime::U16Le>()?)),
+                ("F16Be", []) => Ok(Value::f16(reader.read::<fathom_runtime::U16Be>()?)),
                 ("F32Le", []) => Ok(Value::f32(reader.read::<fathom_runtime::F32Le>()?)),
                 ("F32Be", []) => Ok(Value::f32(reader.read::<fathom_runtime::F32Be>()?)),
                 ("F64Le", []) => Ok(Value::f64(reader.read::<fathom_runtime::F64Le>()?)),
@@ -199,6 +399,317 @@ impl<'globals> Context<'globals> {
                         _ => Err(ReadError::InvalidDataDescription),
                     }
                 }
+                ("FormatArrayMapIndex", [Elim::Function(len), Elim::Function(elem_type)]) => {
+                    match len.as_ref() {
+                        Value::Primitive(Primitive::Int(len)) => match len.to_usize() {
+                            Some(len) => Ok(Value::ArrayTerm(
+                                (0..len)
+                                    .map(|index| {
+                                        let elem_type = semantics::function_elim(
+                                            elem_type.clone(),
+                                            Arc::new(Value::int(index)),
+                                        );
+                                        Ok(Arc::new(self.read_format(reader, &elem_type)?))
+                                    })
+                                    .collect::<Result<_, ReadError>>()?,
+                            )),
+                            None => Err(ReadError::InvalidDataDescription),
+                        },
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                (
+                    "FormatArrayChunked",
+                    [Elim::Function(rows), Elim::Function(cols), Elim::Function(elem_type)],
+                ) => {
+                    let (rows, cols) = match (rows.as_ref(), cols.as_ref()) {
+                        (
+                            Value::Primitive(Primitive::Int(rows)),
+                            Value::Primitive(Primitive::Int(cols)),
+                        ) => (rows.to_usize(), cols.to_usize()),
+                        (_, _) => return Err(ReadError::InvalidDataDescription),
+                    };
+                    let (rows, cols, len) = match (rows, cols) {
+                        (Some(rows), Some(cols)) => match rows.checked_mul(cols) {
+                            Some(len) => (rows, cols, len),
+                            None => return Err(ReadError::InvalidDataDescription),
+                        },
+                        (_, _) => return Err(ReadError::InvalidDataDescription),
+                    };
+
+                    // Read the elements as a flat array before grouping them into rows.
+                    let elem_values = (0..len)
+                        .map(|_| Ok(Arc::new(self.read_format(reader, elem_type)?)))
+                        .collect::<Result<Vec<_>, ReadError>>()?;
+                    let row_values = match cols {
+                        0 => (0..rows)
+                            .map(|_| Arc::new(Value::ArrayTerm(Vec::new())))
+                            .collect(),
+                        cols => (elem_values.chunks(cols))
+                            .map(|row| Arc::new(Value::ArrayTerm(row.to_vec())))
+                            .collect(),
+                    };
+
+                    Ok(Value::ArrayTerm(row_values))
+                }
+                ("FormatArrayReverse", [Elim::Function(format)]) => {
+                    match self.read_format(reader, format)? {
+                        Value::ArrayTerm(mut elem_values) => {
+                            elem_values.reverse();
+                            Ok(Value::ArrayTerm(elem_values))
+                        }
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                ("FormatArraySorted", [Elim::Function(format)]) => {
+                    let value = self.read_format(reader, format)?;
+                    check_sorted(&value, None)?;
+                    Ok(value)
+                }
+                ("FormatArraySortedBy", [Elim::Function(key_label), Elim::Function(format)]) => {
+                    let key_label = to_key_label(key_label)?;
+                    let value = self.read_format(reader, format)?;
+                    check_sorted(&value, Some(key_label))?;
+                    Ok(value)
+                }
+                ("FormatArrayUnique", [Elim::Function(format)]) => {
+                    let value = self.read_format(reader, format)?;
+                    check_unique(&value, None)?;
+                    Ok(value)
+                }
+                ("FormatArrayUniqueBy", [Elim::Function(key_label), Elim::Function(format)]) => {
+                    let key_label = to_key_label(key_label)?;
+                    let value = self.read_format(reader, format)?;
+                    check_unique(&value, Some(key_label))?;
+                    Ok(value)
+                }
+                ("FormatArraySort", [Elim::Function(format)]) => {
+                    let value = self.read_format(reader, format)?;
+                    sort_elems(value, None)
+                }
+                ("FormatArraySortBy", [Elim::Function(key_label), Elim::Function(format)]) => {
+                    let key_label = to_key_label(key_label)?;
+                    let value = self.read_format(reader, format)?;
+                    sort_elems(value, Some(key_label))
+                }
+                ("FormatArrayDedup", [Elim::Function(format)]) => {
+                    let value = self.read_format(reader, format)?;
+                    dedup_elems(value, None)
+                }
+                ("FormatArrayDedupBy", [Elim::Function(key_label), Elim::Function(format)]) => {
+                    let key_label = to_key_label(key_label)?;
+                    let value = self.read_format(reader, format)?;
+                    dedup_elems(value, Some(key_label))
+                }
+                (
+                    "FormatArrayFold",
+                    [Elim::Function(init), Elim::Function(function), Elim::Function(format)],
+                ) => {
+                    let elem_values = match self.read_format(reader, format)? {
+                        Value::ArrayTerm(elem_values) => elem_values,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+                    let mut result = init.clone();
+                    for elem_value in &elem_values {
+                        let partial = semantics::function_elim(function.clone(), result);
+                        result = semantics::function_elim(partial, elem_value.clone());
+                        if !matches!(result.as_ref(), Value::Primitive(Primitive::Int(_))) {
+                            return Err(ReadError::InvalidDataDescription);
+                        }
+                    }
+                    let mut fields = BTreeMap::new();
+                    fields.insert("elems".to_owned(), Arc::new(Value::ArrayTerm(elem_values)));
+                    fields.insert("result".to_owned(), result);
+                    Ok(Value::StructTerm(fields))
+                }
+                ("FormatReprCoerce", [Elim::Function(format)]) => {
+                    let (scope, remaining) = (reader.scope(), reader.remaining());
+                    let value = self.read_format(reader, format)?;
+                    let consumed = remaining.saturating_sub(reader.remaining());
+                    let raw = (scope.data()[..consumed].iter())
+                        .map(|byte| Arc::new(Value::int(*byte)))
+                        .collect();
+
+                    let mut fields = BTreeMap::new();
+                    fields.insert("raw".to_owned(), Arc::new(Value::ArrayTerm(raw)));
+                    fields.insert("value".to_owned(), Arc::new(value));
+                    Ok(Value::StructTerm(fields))
+                }
+                ("FormatArraySplit", [Elim::Function(at), Elim::Function(format)]) => {
+                    let at = match at.as_ref() {
+                        Value::Primitive(Primitive::Int(at)) => at.to_usize(),
+                        _ => None,
+                    };
+                    let at = at.ok_or(ReadError::InvalidDataDescription)?;
+                    let mut head_values = match self.read_format(reader, format)? {
+                        Value::ArrayTerm(elem_values) => elem_values,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+                    if at > head_values.len() {
+                        return Err(ReadError::ArrayIndexOutOfBounds {
+                            index: at,
+                            len: head_values.len(),
+                        });
+                    }
+                    let tail_values = head_values.split_off(at);
+
+                    let mut fields = BTreeMap::new();
+                    fields.insert("head".to_owned(), Arc::new(Value::ArrayTerm(head_values)));
+                    fields.insert("tail".to_owned(), Arc::new(Value::ArrayTerm(tail_values)));
+                    Ok(Value::StructTerm(fields))
+                }
+                ("FormatArrayTake", [Elim::Function(count), Elim::Function(format)])
+                | ("FormatArrayDrop", [Elim::Function(count), Elim::Function(format)]) => {
+                    let count = match count.as_ref() {
+                        Value::Primitive(Primitive::Int(count)) => count.to_usize(),
+                        _ => None,
+                    };
+                    let count = count.ok_or(ReadError::InvalidDataDescription)?;
+                    let mut elem_values = match self.read_format(reader, format)? {
+                        Value::ArrayTerm(elem_values) => elem_values,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+                    if count > elem_values.len() {
+                        return Err(ReadError::ArrayIndexOutOfBounds {
+                            index: count,
+                            len: elem_values.len(),
+                        });
+                    }
+                    let dropped_values = elem_values.split_off(count);
+
+                    match name.as_str() {
+                        "FormatArrayTake" => Ok(Value::ArrayTerm(elem_values)),
+                        _ => Ok(Value::ArrayTerm(dropped_values)),
+                    }
+                }
+                ("FormatArrayIndexed", [Elim::Function(format)]) => {
+                    match self.read_format(reader, format)? {
+                        Value::ArrayTerm(elem_values) => {
+                            let indexed =
+                                elem_values.into_iter().enumerate().map(|(index, value)| {
+                                    let mut fields = BTreeMap::new();
+                                    fields.insert("index".to_owned(), Arc::new(Value::int(index)));
+                                    fields.insert("value".to_owned(), value);
+                                    Arc::new(Value::StructTerm(fields))
+                                });
+                            Ok(Value::ArrayTerm(indexed.collect()))
+                        }
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                ("FormatArrayConcat", [Elim::Function(format0), Elim::Function(format1)]) => {
+                    match (
+                        self.read_format(reader, format0)?,
+                        self.read_format(reader, format1)?,
+                    ) {
+                        (Value::ArrayTerm(mut elem_values0), Value::ArrayTerm(elem_values1)) => {
+                            elem_values0.extend(elem_values1);
+                            Ok(Value::ArrayTerm(elem_values0))
+                        }
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                ("FormatArrayZip", [Elim::Function(format0), Elim::Function(format1)]) => {
+                    match (
+                        self.read_format(reader, format0)?,
+                        self.read_format(reader, format1)?,
+                    ) {
+                        (Value::ArrayTerm(elem_values0), Value::ArrayTerm(elem_values1)) => {
+                            if elem_values0.len() != elem_values1.len() {
+                                return Err(ReadError::MismatchedArrayLengths {
+                                    first: elem_values0.len(),
+                                    second: elem_values1.len(),
+                                });
+                            }
+                            let pairs = Iterator::zip(elem_values0.into_iter(), elem_values1).map(
+                                |(fst, snd)| {
+                                    let mut fields = BTreeMap::new();
+                                    fields.insert("fst".to_owned(), fst);
+                                    fields.insert("snd".to_owned(), snd);
+                                    Arc::new(Value::StructTerm(fields))
+                                },
+                            );
+                            Ok(Value::ArrayTerm(pairs.collect()))
+                        }
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                ("FormatArrayWindows", [Elim::Function(size), Elim::Function(format)]) => {
+                    let size = match size.as_ref() {
+                        Value::Primitive(Primitive::Int(size)) => size.to_usize(),
+                        _ => None,
+                    };
+                    let size =
+                        (size.filter(|size| *size > 0)).ok_or(ReadError::InvalidDataDescription)?;
+                    let elem_values = match self.read_format(reader, format)? {
+                        Value::ArrayTerm(elem_values) => elem_values,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+                    binary::windows(size, &elem_values).ok_or(ReadError::ArrayIndexOutOfBounds {
+                        index: size,
+                        len: elem_values.len(),
+                    })
+                }
+                ("FormatArrayTranspose", [Elim::Function(format)]) => {
+                    match binary::transpose(&self.read_format(reader, format)?) {
+                        Ok(value) => Ok(value),
+                        Err(TransposeError::NotAnArray) => Err(ReadError::InvalidDataDescription),
+                        Err(TransposeError::MismatchedRowLength(index)) => {
+                            Err(ReadError::MismatchedRowLength { index })
+                        }
+                    }
+                }
+                ("FormatArrayRemaining", [Elim::Function(elem_type)]) => {
+                    let mut elem_values = Vec::new();
+                    while reader.remaining() > 0 {
+                        let remaining = reader.remaining();
+                        elem_values.push(Arc::new(self.read_format(reader, elem_type)?));
+                        // Avoid looping forever on elements that consume no data.
+                        if reader.remaining() == remaining {
+                            return Err(ReadError::InvalidDataDescription);
+                        }
+                    }
+                    Ok(Value::ArrayTerm(elem_values))
+                }
+                ("FormatRepeatUntil", [Elim::Function(predicate), Elim::Function(elem_type)])
+                | (
+                    "FormatRepeatUntilInclusive",
+                    [Elim::Function(predicate), Elim::Function(elem_type)],
+                ) => {
+                    let mut elem_values = Vec::new();
+                    loop {
+                        let remaining = reader.remaining();
+                        let elem_value = Arc::new(self.read_format(reader, elem_type)?);
+                        // Avoid looping forever on elements that consume no data.
+                        if reader.remaining() == remaining {
+                            return Err(ReadError::InvalidDataDescription);
+                        }
+                        let is_sentinel =
+                            semantics::function_elim(predicate.clone(), elem_value.clone());
+                        match is_sentinel.try_global() {
+                            Some(("true", [])) => {
+                                if name == "FormatRepeatUntilInclusive" {
+                                    elem_values.push(elem_value);
+                                }
+                                break;
+                            }
+                            Some(("false", [])) => elem_values.push(elem_value),
+                            _ => return Err(ReadError::InvalidDataDescription),
+                        }
+                    }
+                    Ok(Value::ArrayTerm(elem_values))
+                }
+                ("FormatLengthPrefixed", [Elim::Function(len_type), Elim::Function(elem_type)]) => {
+                    let len = match self.read_format(reader, len_type)? {
+                        Value::Primitive(Primitive::Int(len)) => len.to_usize(),
+                        _ => None,
+                    };
+                    let len = len.ok_or(ReadError::InvalidDataDescription)?;
+                    let elem_values = (0..len)
+                        .map(|_| Ok(Arc::new(self.read_format(reader, elem_type)?)))
+                        .collect::<Result<_, ReadError>>()?;
+                    Ok(Value::ArrayTerm(elem_values))
+                }
                 ("CurrentPos", []) => match reader.current_pos() {
                     Some(offset) => Ok(Value::Primitive(Primitive::Pos(offset))),
                     None => Err(ReadError::OverflowingPosition),
@@ -217,12 +728,252 @@ impl<'globals> Context<'globals> {
 
                     let position = (offset + base)
                         .to_usize()
-                        .ok_or(ReadError::OverflowingPosition)?;
+                        .ok_or(ReadError::ArithmeticOverflow { field: None })?;
 
                     self.pending_links.push_back((position, format.clone()));
 
                     Ok(Value::Primitive(Primitive::Pos(position)))
                 }
+                (
+                    "FormatAtOffsets",
+                    [Elim::Function(base), Elim::Function(offsets), Elim::Function(format)],
+                ) => {
+                    let base = match base.as_ref() {
+                        Value::Primitive(Primitive::Pos(base)) => *base,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+                    let offsets = match self.read_format(reader, offsets)? {
+                        Value::ArrayTerm(offsets) => offsets,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+
+                    let mut elem_values = Vec::with_capacity(offsets.len());
+                    for offset in offsets {
+                        let position = match offset.as_ref() {
+                            Value::Primitive(Primitive::Int(offset)) => (offset + base)
+                                .to_usize()
+                                .ok_or(ReadError::ArithmeticOverflow { field: None })?,
+                            _ => return Err(ReadError::InvalidDataDescription),
+                        };
+                        // Read from a separate reader, leaving the position of
+                        // the current reader after the offsets.
+                        let mut inner_reader = reader.scope_at_pos(position).reader();
+                        let elem_value = self.read_format(&mut inner_reader, format)?;
+                        elem_values.push(Arc::new(elem_value));
+                    }
+                    Ok(Value::ArrayTerm(elem_values))
+                }
+                ("FormatAlign", [Elim::Function(align)]) => {
+                    let align = match align.as_ref() {
+                        Value::Primitive(Primitive::Int(align)) => align.to_usize(),
+                        _ => None,
+                    };
+                    let align = (align.filter(|align| *align > 0))
+                        .ok_or(ReadError::InvalidDataDescription)?;
+                    let offset = reader.current_pos().ok_or(ReadError::OverflowingPosition)?;
+                    let padding = (align - offset % align) % align;
+                    reader
+                        .read_scope(padding)
+                        .map_err(|_| ReadError::AlignmentOverflow { offset, align })?;
+                    Ok(Value::global("unit", Vec::new()))
+                }
+                ("FormatFixed", [Elim::Function(frac_bits), Elim::Function(format)]) => {
+                    let scale =
+                        binary::fixed_scale(frac_bits).ok_or(ReadError::InvalidDataDescription)?;
+                    match self.read_format(reader, format)? {
+                        Value::Primitive(Primitive::Int(int)) => match int.to_f64() {
+                            Some(int) => Ok(Value::f64(int / scale)),
+                            None => Err(ReadError::InvalidDataDescription),
+                        },
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                ("FormatConst", [Elim::Function(expected), Elim::Function(format)]) => {
+                    let expected = match expected.as_ref() {
+                        Value::Primitive(Primitive::Int(expected)) => expected,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+
+                    match self.read_format(reader, format)? {
+                        Value::Primitive(Primitive::Int(found)) if found == *expected => {
+                            Ok(Value::Primitive(Primitive::Int(found)))
+                        }
+                        Value::Primitive(Primitive::Int(found)) => {
+                            Err(ReadError::UnexpectedConst {
+                                expected: expected.to_string(),
+                                found: found.to_string(),
+                            })
+                        }
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                ("FormatAssertSize", [Elim::Function(len), Elim::Function(format)]) => {
+                    let len = match len.as_ref() {
+                        Value::Primitive(Primitive::Int(len)) => len.to_usize(),
+                        _ => None,
+                    };
+                    let len = len.ok_or(ReadError::InvalidDataDescription)?;
+
+                    let remaining = reader.remaining();
+                    let value = self.read_format(reader, format)?;
+                    match remaining - reader.remaining() {
+                        consumed if consumed == len => Ok(value),
+                        consumed => Err(ReadError::SizeMismatch {
+                            expected: len,
+                            found: consumed,
+                        }),
+                    }
+                }
+                (
+                    "FormatNested",
+                    [Elim::Function(len), Elim::Function(allow_trailing), Elim::Function(format)],
+                ) => {
+                    let len = match len.as_ref() {
+                        Value::Primitive(Primitive::Int(len)) => len.to_usize(),
+                        _ => None,
+                    };
+                    let allow_trailing = match allow_trailing.try_global() {
+                        Some(("true", [])) => true,
+                        Some(("false", [])) => false,
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+
+                    let len = len.ok_or(ReadError::InvalidDataDescription)?;
+                    let mut nested_reader = reader.read_scope(len)?.reader();
+                    let value = self.read_format(&mut nested_reader, format)?;
+
+                    match nested_reader.remaining() {
+                        count if count > 0 && !allow_trailing => {
+                            Err(ReadError::TrailingBytes { count })
+                        }
+                        _ => Ok(value),
+                    }
+                }
+                (
+                    "FormatMinVersion",
+                    [Elim::Function(major), Elim::Function(minor), Elim::Function(patch), Elim::Function(format)],
+                ) => {
+                    let required = match (major.as_ref(), minor.as_ref(), patch.as_ref()) {
+                        (
+                            Value::Primitive(Primitive::Int(major)),
+                            Value::Primitive(Primitive::Int(minor)),
+                            Value::Primitive(Primitive::Int(patch)),
+                        ) => [major.clone(), minor.clone(), patch.clone()],
+                        _ => return Err(ReadError::InvalidDataDescription),
+                    };
+
+                    let mut found = Vec::with_capacity(3);
+                    for _ in 0..3 {
+                        match self.read_format(reader, format)? {
+                            Value::Primitive(Primitive::Int(part)) => found.push(part),
+                            _ => return Err(ReadError::InvalidDataDescription),
+                        }
+                    }
+
+                    if found.as_slice() < &required[..] {
+                        return Err(ReadError::UnsupportedVersion {
+                            found: found.iter().format(".").to_string(),
+                            required: required.iter().format(".").to_string(),
+                        });
+                    }
+
+                    let mut fields = BTreeMap::new();
+                    for (label, part) in Iterator::zip(["major", "minor", "patch"].iter(), found) {
+                        fields.insert(label.to_string(), Arc::new(Value::int(part)));
+                    }
+                    Ok(Value::StructTerm(fields))
+                }
+                ("FormatPresentIf", [Elim::Function(is_present), Elim::Function(format)]) => {
+                    match is_present.try_global() {
+                        Some(("true", [])) => Ok(Value::global(
+                            "some",
+                            vec![Elim::Function(Arc::new(self.read_format(reader, format)?))],
+                        )),
+                        Some(("false", [])) => Ok(Value::global("none", Vec::new())),
+                        _ => Err(ReadError::InvalidDataDescription),
+                    }
+                }
+                ("FormatBom", []) => Ok(Value::int(read_bom(reader))),
+                ("FormatNever", []) => Err(ReadError::ReachedNever),
+                (
+                    "FormatPadded",
+                    [Elim::Function(len), Elim::Function(fill), Elim::Function(format)],
+                ) => {
+                    let (len, fill) = match (len.as_ref(), fill.as_ref()) {
+                        (
+                            Value::Primitive(Primitive::Int(len)),
+                            Value::Primitive(Primitive::Int(fill)),
+                        ) => (len.to_usize(), fill.to_u8()),
+                        _ => (None, None),
+                    };
+                    let len = len.ok_or(ReadError::InvalidDataDescription)?;
+                    let fill = fill.ok_or(ReadError::InvalidDataDescription)?;
+
+                    let mut padded_reader = reader.read_scope(len)?.reader();
+                    let value = self.read_format(&mut padded_reader, format)?;
+                    while padded_reader.remaining() > 0 {
+                        let index = len - padded_reader.remaining();
+                        match padded_reader.read::<fathom_runtime::U8>()? {
+                            byte if byte == fill => {}
+                            found => {
+                                return Err(ReadError::InvalidPadding {
+                                    index,
+                                    expected: fill,
+                                    found,
+                                });
+                            }
+                        }
+                    }
+
+                    Ok(value)
+                }
+                ("FormatUtf8", [Elim::Function(len)]) => {
+                    let len = match len.as_ref() {
+                        Value::Primitive(Primitive::Int(len)) => len.to_usize(),
+                        _ => None,
+                    };
+                    let len = len.ok_or(ReadError::InvalidDataDescription)?;
+                    match std::str::from_utf8(reader.read_scope(len)?.data()) {
+                        Ok(string) => Ok(Value::Primitive(Primitive::String(string.to_owned()))),
+                        Err(error) => Err(ReadError::InvalidUtf8 {
+                            offset: error.valid_up_to(),
+                        }),
+                    }
+                }
+                ("FormatAsciiLine", []) => Ok(Value::ArrayTerm(
+                    read_ascii_line(reader)?
+                        .into_iter()
+                        .map(|byte| Arc::new(Value::int(byte)))
+                        .collect(),
+                )),
+                ("FormatCStr", []) => Ok(Value::ArrayTerm(
+                    read_c_str(reader)?
+                        .into_iter()
+                        .map(|byte| Arc::new(Value::int(byte)))
+                        .collect(),
+                )),
+                ("FormatStrFixed", [Elim::Function(len), Elim::Function(fill)]) => {
+                    let (len, fill) = match (len.as_ref(), fill.as_ref()) {
+                        (
+                            Value::Primitive(Primitive::Int(len)),
+                            Value::Primitive(Primitive::Int(fill)),
+                        ) => (len.to_usize(), fill.to_u8()),
+                        _ => (None, None),
+                    };
+                    let len = len.ok_or(ReadError::InvalidDataDescription)?;
+                    let fill = fill.ok_or(ReadError::InvalidDataDescription)?;
+
+                    let bytes = reader.read_scope(len)?.data();
+                    let trimmed_len = (bytes.iter())
+                        .rposition(|byte| *byte != fill)
+                        .map_or(0, |index| index + 1);
+                    Ok(Value::ArrayTerm(
+                        bytes[..trimmed_len]
+                            .iter()
+                            .map(|byte| Arc::new(Value::int(*byte)))
+                            .collect(),
+                    ))
+                }
                 (_, _) => Err(ReadError::InvalidDataDescription),
             },
             Value::Stuck(Head::Item(item_name), elims) => {
@@ -261,3 +1012,301 @@ impl<'globals> Context<'globals> {
         }
     }
 }
+
+/// Detect the byte order mark at the current position, consuming it if it is
+/// present. Returns `0` if no byte order mark was found.
+fn read_bom(reader: &mut FormatReader<'_>) -> u8 {
+    for (bom, encoding) in BYTE_ORDER_MARKS.iter() {
+        let mut bom_reader = reader.clone();
+        let is_match = bom
+            .iter()
+            .all(|byte| bom_reader.read::<fathom_runtime::U8>().ok() == Some(*byte));
+
+        if is_match {
+            *reader = bom_reader;
+            return *encoding;
+        }
+    }
+
+    0
+}
+
+/// Compare two array elements, returning `None` if they can not be ordered.
+///
+/// Numbers, positions, and strings are compared by value, and arrays are compared
+/// lexicographically.
+fn compare_values(value0: &Value, value1: &Value) -> Option<Ordering> {
+    match (value0, value1) {
+        (Value::Primitive(primitive0), Value::Primitive(primitive1)) => {
+            match (primitive0, primitive1) {
+                (Primitive::Int(int0), Primitive::Int(int1)) => Some(int0.cmp(int1)),
+                (Primitive::F16(float0), Primitive::F16(float1)) => {
+                    let (float0, float1) = (f16_to_f32(*float0), f16_to_f32(*float1));
+                    float0.partial_cmp(&float1)
+                }
+                (Primitive::F32(float0), Primitive::F32(float1)) => float0.partial_cmp(float1),
+                (Primitive::F64(float0), Primitive::F64(float1)) => float0.partial_cmp(float1),
+                (Primitive::Pos(pos0), Primitive::Pos(pos1)) => Some(pos0.cmp(pos1)),
+                (Primitive::String(string0), Primitive::String(string1)) => {
+                    Some(string0.cmp(string1))
+                }
+                (_, _) => None,
+            }
+        }
+        (Value::ArrayTerm(elem_values0), Value::ArrayTerm(elem_values1)) => {
+            for (elem_value0, elem_value1) in Iterator::zip(elem_values0.iter(), elem_values1) {
+                match compare_values(elem_value0, elem_value1)? {
+                    Ordering::Equal => {}
+                    ordering => return Some(ordering),
+                }
+            }
+            Some(elem_values0.len().cmp(&elem_values1.len()))
+        }
+        (_, _) => None,
+    }
+}
+
+/// Check if a term refers to the local variable with the given index.
+fn mentions_local(term: &core::Term, index: usize) -> bool {
+    match &term.data {
+        core::TermData::Local(local_index) => local_index.to_usize() == index,
+        core::TermData::Global(_)
+        | core::TermData::Item(_)
+        | core::TermData::Sort(_)
+        | core::TermData::Primitive(_)
+        | core::TermData::FormatType
+        | core::TermData::Repr
+        | core::TermData::Error => false,
+        core::TermData::Ann(term, r#type) => {
+            mentions_local(term, index) || mentions_local(r#type, index)
+        }
+        core::TermData::FunctionType(param_type, body_type) => {
+            mentions_local(param_type, index) || mentions_local(body_type, index)
+        }
+        core::TermData::FunctionElim(head, argument) => {
+            mentions_local(head, index) || mentions_local(argument, index)
+        }
+        core::TermData::StructTerm(field_definitions) => (field_definitions.iter())
+            .any(|field_definition| mentions_local(&field_definition.term, index)),
+        core::TermData::StructElim(head, _) => mentions_local(head, index),
+        core::TermData::ArrayTerm(elem_terms) => {
+            (elem_terms.iter()).any(|elem_term| mentions_local(elem_term, index))
+        }
+        core::TermData::BoolElim(head, if_true, if_false) => {
+            mentions_local(head, index)
+                || mentions_local(if_true, index)
+                || mentions_local(if_false, index)
+        }
+        core::TermData::IntElim(head, branches, default) => {
+            mentions_local(head, index)
+                || (branches.values()).any(|branch| mentions_local(branch, index))
+                || mentions_local(default, index)
+        }
+    }
+}
+
+/// The label of the field that array elements are compared by.
+fn to_key_label(value: &Value) -> Result<&str, ReadError> {
+    match value {
+        Value::Primitive(Primitive::String(label)) => Ok(label),
+        _ => Err(ReadError::InvalidDataDescription),
+    }
+}
+
+/// The keys that the elements of an array are compared by. These are either
+/// the elements themselves, or the values of one of their fields.
+fn elem_keys<'a>(value: &'a Value, key_label: Option<&str>) -> Result<Vec<&'a Value>, ReadError> {
+    let elem_values = match value {
+        Value::ArrayTerm(elem_values) => elem_values,
+        _ => return Err(ReadError::InvalidDataDescription),
+    };
+    (elem_values.iter())
+        .map(|elem_value| match (elem_value.as_ref(), key_label) {
+            (elem_value, None) => Ok(elem_value),
+            (Value::StructTerm(fields), Some(label)) => match fields.get(label) {
+                Some(field_value) => Ok(field_value.as_ref()),
+                None => Err(ReadError::InvalidDataDescription),
+            },
+            (_, Some(_)) => Err(ReadError::InvalidDataDescription),
+        })
+        .collect()
+}
+
+/// The indices of the keys in ascending order of the keys. Equal keys are kept
+/// in their original order.
+fn sorted_indices(keys: &[&Value]) -> Result<Vec<usize>, ReadError> {
+    let mut indices = (0..keys.len()).collect::<Vec<_>>();
+    let mut is_comparable = true;
+    indices.sort_by(|index0, index1| {
+        compare_values(keys[*index0], keys[*index1]).unwrap_or_else(|| {
+            is_comparable = false;
+            Ordering::Equal
+        })
+    });
+    match is_comparable {
+        true => Ok(indices),
+        false => Err(ReadError::InvalidDataDescription),
+    }
+}
+
+/// Check that the elements of an array are in ascending order of their keys,
+/// reporting the first element that is out of order.
+fn check_sorted(value: &Value, key_label: Option<&str>) -> Result<(), ReadError> {
+    let keys = elem_keys(value, key_label)?;
+    for index in 1..keys.len() {
+        match compare_values(keys[index - 1], keys[index]) {
+            Some(Ordering::Greater) => return Err(ReadError::UnsortedElement { index }),
+            Some(_) => {}
+            None => return Err(ReadError::InvalidDataDescription),
+        }
+    }
+    Ok(())
+}
+
+/// Check that no two elements of an array have equal keys, reporting the
+/// first element whose key is equal to the key of an earlier element.
+fn check_unique(value: &Value, key_label: Option<&str>) -> Result<(), ReadError> {
+    let keys = elem_keys(value, key_label)?;
+    // Equal keys are next to each other once sorted, with the earliest
+    // element of each run of equal keys first.
+    let indices = sorted_indices(&keys)?;
+    let duplicate_index = (indices.windows(2))
+        .filter(|pair| compare_values(keys[pair[0]], keys[pair[1]]) == Some(Ordering::Equal))
+        .map(|pair| pair[1])
+        .min();
+    match duplicate_index {
+        Some(index) => Err(ReadError::DuplicateElement { index }),
+        None => Ok(()),
+    }
+}
+
+/// Sort the elements of an array in ascending order of their keys. Elements
+/// with equal keys are kept in their original order.
+fn sort_elems(value: Value, key_label: Option<&str>) -> Result<Value, ReadError> {
+    let indices = sorted_indices(&elem_keys(&value, key_label)?)?;
+    match value {
+        Value::ArrayTerm(elem_values) => Ok(Value::ArrayTerm(
+            (indices.into_iter())
+                .map(|index| elem_values[index].clone())
+                .collect(),
+        )),
+        _ => Err(ReadError::InvalidDataDescription),
+    }
+}
+
+/// Remove the elements of an array whose keys are equal to the key of an
+/// earlier element, keeping the remaining elements in their original order.
+fn dedup_elems(value: Value, key_label: Option<&str>) -> Result<Value, ReadError> {
+    let is_first = {
+        let keys = elem_keys(&value, key_label)?;
+        // Equal keys are next to each other once sorted, with the earliest
+        // element of each run of equal keys first.
+        let indices = sorted_indices(&keys)?;
+        let mut is_first = vec![false; keys.len()];
+        if let Some(index) = indices.first() {
+            is_first[*index] = true;
+        }
+        for pair in indices.windows(2) {
+            if compare_values(keys[pair[0]], keys[pair[1]]) != Some(Ordering::Equal) {
+                is_first[pair[1]] = true;
+            }
+        }
+        is_first
+    };
+    match value {
+        Value::ArrayTerm(elem_values) => Ok(Value::ArrayTerm(
+            (elem_values.into_iter().zip(is_first))
+                .filter_map(|(elem_value, is_first)| if is_first { Some(elem_value) } else { None })
+                .collect(),
+        )),
+        _ => Err(ReadError::InvalidDataDescription),
+    }
+}
+
+/// Read the bytes up to the next newline, consuming the newline. A carriage
+/// return directly preceding the newline is also stripped from the result.
+fn read_ascii_line(reader: &mut FormatReader<'_>) -> Result<Vec<u8>, ReadError> {
+    let mut bytes = Vec::new();
+    loop {
+        match reader.read::<fathom_runtime::U8>()? {
+            b'\n' => break,
+            byte => bytes.push(byte),
+        }
+    }
+
+    if bytes.last() == Some(&b'\r') {
+        bytes.pop();
+    }
+
+    Ok(bytes)
+}
+
+/// Read the bytes up to the next NUL byte, consuming the NUL byte.
+fn read_c_str(reader: &mut FormatReader<'_>) -> Result<Vec<u8>, ReadError> {
+    let mut bytes = Vec::new();
+    loop {
+        match reader.read::<fathom_runtime::U8>()? {
+            0 => return Ok(bytes),
+            byte => bytes.push(byte),
+        }
+    }
+}
+
+/// Read an unsigned [LEB128] integer, made up of groups of 7 bits, least
+/// significant group first. The high bit of each byte is set if there are more
+/// bytes to follow.
+///
+/// [LEB128]: https://en.wikipedia.org/wiki/LEB128
+fn read_uleb128(reader: &mut FormatReader<'_>) -> Result<u64, ReadError> {
+    let mut value = 0;
+    for (index, shift) in (0..64).step_by(7).enumerate() {
+        let byte = reader.read::<fathom_runtime::U8>()?;
+        let group = u64::from(byte & 0x7f);
+        if (group << shift) >> shift != group {
+            return Err(ReadError::OverflowingInteger { count: index + 1 });
+        }
+        value |= group << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(ReadError::OverflowingInteger { count: 10 })
+}
+
+/// Read a variable-length quantity, as used in MIDI files. This is made up of
+/// groups of 7 bits, most significant group first. The high bit of each byte
+/// is set if there are more bytes to follow.
+fn read_vlq(reader: &mut FormatReader<'_>) -> Result<u32, ReadError> {
+    let mut value = 0u32;
+    for index in 0..5 {
+        let byte = reader.read::<fathom_runtime::U8>()?;
+        if value >> 25 != 0 {
+            return Err(ReadError::OverflowingInteger { count: index + 1 });
+        }
+        value = (value << 7) | u32::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(ReadError::OverflowingInteger { count: 5 })
+}
+
+/// Read a signed [LEB128] integer. This is encoded like an unsigned LEB128
+/// integer, but is sign extended from the high bit of the last group.
+///
+/// [LEB128]: https://en.wikipedia.org/wiki/LEB128
+fn read_sleb128(reader: &mut FormatReader<'_>) -> Result<i64, ReadError> {
+    let mut value = 0;
+    for (index, shift) in (0..70).step_by(7).enumerate() {
+        let byte = reader.read::<fathom_runtime::U8>()?;
+        value |= i128::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            if byte & 0x40 != 0 {
+                value |= -1 << (shift + 7);
+            }
+            return i64::try_from(value)
+                .map_err(|_| ReadError::OverflowingInteger { count: index + 1 });
+        }
+    }
+    Err(ReadError::OverflowingInteger { count: 10 })
+}
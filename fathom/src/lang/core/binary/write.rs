@@ -0,0 +1,812 @@
+use fathom_runtime::{FormatWriter, WriteError};
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::lang::core;
+use crate::lang::core::binary::{self, Endianness, BYTE_ORDER_MARKS};
+use crate::lang::core::semantics::{self, Elim, Head, Value};
+use crate::lang::core::{FieldDeclaration, Globals, ItemData, Module, Primitive};
+
+/// Contextual information to be used when writing items.
+pub struct Context<'globals> {
+    globals: &'globals Globals,
+    items: HashMap<String, semantics::Item>,
+    default_endianness: Endianness,
+}
+
+impl<'globals> Context<'globals> {
+    /// Create a new context.
+    pub fn new(globals: &'globals Globals, module: &Module) -> Context<'globals> {
+        let mut context = Context {
+            globals,
+            items: HashMap::new(),
+            default_endianness: Endianness::default(),
+        };
+
+        for item in &module.items {
+            let (name, item_data) = match &item.data {
+                ItemData::Constant(constant) => (
+                    constant.name.clone(),
+                    semantics::ItemData::Constant(
+                        context.eval(&mut core::Locals::new(), &constant.term),
+                    ),
+                ),
+                ItemData::StructType(struct_type) => (
+                    struct_type.name.clone(),
+                    semantics::ItemData::StructType(
+                        struct_type.params.len(),
+                        struct_type.fields.clone(),
+                    ),
+                ),
+                ItemData::StructFormat(struct_format) => (
+                    struct_format.name.clone(),
+                    semantics::ItemData::StructFormat(
+                        struct_format.params.len(),
+                        struct_format.fields.clone(),
+                    ),
+                ),
+            };
+
+            let item = semantics::Item::new(item.location, item_data);
+            context.items.insert(name, item);
+        }
+
+        context
+    }
+
+    /// Set the byte order to use for the integer formats that do not specify
+    /// one, for example `U16` rather than `U16Be` or `U16Le`. This defaults to
+    /// [`Endianness::Big`].
+    pub fn set_default_endianness(&mut self, default_endianness: Endianness) {
+        self.default_endianness = default_endianness;
+    }
+
+    /// Evaluate a term using the supplied local environment.
+    fn eval(&mut self, locals: &mut core::Locals<Arc<Value>>, term: &core::Term) -> Arc<Value> {
+        semantics::eval(self.globals, &self.items, locals, term)
+    }
+
+    /// Write a value to the binary data, using the format described by a
+    /// module item.
+    ///
+    /// Data that was only referred to using links is not written.
+    pub fn write_item(
+        &mut self,
+        writer: &mut FormatWriter,
+        name: &str,
+        value: &Value,
+    ) -> Result<(), WriteError> {
+        match self.items.get(name).cloned().map(|item| item.data) {
+            Some(semantics::ItemData::Constant(format)) => {
+                self.write_format(writer, &format, value)
+            }
+            Some(semantics::ItemData::StructFormat(0, field_declarations)) => {
+                self.write_struct_format(writer, &field_declarations, &[], value)
+            }
+            Some(semantics::ItemData::StructFormat(_, _))
+            | Some(semantics::ItemData::StructType(_, _))
+            | None => Err(WriteError::InvalidDataDescription),
+        }
+    }
+
+    fn write_struct_format(
+        &mut self,
+        writer: &mut FormatWriter,
+        field_declarations: &[FieldDeclaration],
+        elims: &[Elim],
+        value: &Value,
+    ) -> Result<(), WriteError> {
+        let fields = match value {
+            Value::StructTerm(fields) => fields,
+            _ => return Err(WriteError::InvalidDataDescription),
+        };
+        // Local environment for evaluating the field formats with the
+        // values of the preceding fields.
+        let mut format_locals = core::Locals::new();
+
+        for elim in elims {
+            match elim {
+                Elim::Function(value) => format_locals.push(value.clone()),
+                _ => panic!("invalid elimination"),
+            }
+        }
+
+        for field_declaration in field_declarations.iter() {
+            let format = self.eval(&mut format_locals, &field_declaration.type_);
+            let value = match fields.get(&field_declaration.label.data) {
+                Some(value) => value,
+                None => return Err(WriteError::InvalidDataDescription),
+            };
+            self.write_format(writer, &format, value)?;
+
+            format_locals.push(value.clone());
+        }
+
+        Ok(())
+    }
+
+    fn write_format(
+        &mut self,
+        writer: &mut FormatWriter,
+        format: &Value,
+        value: &Value,
+    ) -> Result<(), WriteError> {
+        use fathom_runtime::{
+            F32Be, F32Le, F64Be, F64Le, I16Be, I16Le, I24Be, I24Le, I32Be, I32Le, I64Be, I64Le,
+            U16Be, U16Le, U24Be, U24Le, U32Be, U32Le, U64Be, U64Le, I8, U8,
+        };
+
+        match format {
+            Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
+                ("U16", [])
+                | ("U32", [])
+                | ("U64", [])
+                | ("S16", [])
+                | ("S32", [])
+                | ("S64", []) => {
+                    let suffix = self.default_endianness.format_suffix();
+                    let format = Value::global(format!("{}{}", name, suffix), Vec::new());
+                    self.write_format(writer, &format, value)?;
+                }
+                ("U8", []) => writer.write::<U8>(to_int(value, BigInt::to_u8)?),
+                ("U16Le", []) => writer.write::<U16Le>(to_int(value, BigInt::to_u16)?),
+                ("U16Be", []) => writer.write::<U16Be>(to_int(value, BigInt::to_u16)?),
+                ("U24Le", []) => writer.write::<U24Le>(to_int(value, to_u24)?),
+                ("U24Be", []) => writer.write::<U24Be>(to_int(value, to_u24)?),
+                ("U32Le", []) => writer.write::<U32Le>(to_int(value, BigInt::to_u32)?),
+                ("U32Be", []) => writer.write::<U32Be>(to_int(value, BigInt::to_u32)?),
+                ("U64Le", []) => writer.write::<U64Le>(to_int(value, BigInt::to_u64)?),
+                ("U64Be", []) => writer.write::<U64Be>(to_int(value, BigInt::to_u64)?),
+                ("S8", []) => writer.write::<I8>(to_int(value, BigInt::to_i8)?),
+                ("S16Le", []) => writer.write::<I16Le>(to_int(value, BigInt::to_i16)?),
+                ("S16Be", []) => writer.write::<I16Be>(to_int(value, BigInt::to_i16)?),
+                ("S24Le", []) => writer.write::<I24Le>(to_int(value, to_s24)?),
+                ("S24Be", []) => writer.write::<I24Be>(to_int(value, to_s24)?),
+                ("S32Le", []) => writer.write::<I32Le>(to_int(value, BigInt::to_i32)?),
+                ("S32Be", []) => writer.write::<I32Be>(to_int(value, BigInt::to_i32)?),
+                ("S64Le", []) => writer.write::<I64Le>(to_int(value, BigInt::to_i64)?),
+                ("S64Be", []) => writer.write::<I64Be>(to_int(value, BigInt::to_i64)?),
+                ("FormatULeb128", []) => {
+                    let mut value = to_int(value, BigInt::to_u64)?;
+                    while value >= 0x80 {
+                        writer.write::<U8>((value & 0x7f) as u8 | 0x80);
+                        value >>= 7;
+                    }
+                    writer.write::<U8>(value as u8);
+                }
+                ("FormatVlq", []) => {
+                    let value = to_int(value, BigInt::to_u32)?;
+                    let mut shift = (31 - value.leading_zeros() as i32).max(0) / 7 * 7;
+                    while shift > 0 {
+                        writer.write::<U8>((value >> shift) as u8 & 0x7f | 0x80);
+                        shift -= 7;
+                    }
+                    writer.write::<U8>(value as u8 & 0x7f);
+                }
+                ("FormatSLeb128", []) => {
+                    let mut value = to_int(value, BigInt::to_i64)?;
+                    loop {
+                        let byte = (value & 0x7f) as u8;
+                        value >>= 7;
+                        match (value, byte & 0x40) {
+                            (0, 0) | (-1, 0x40) => break writer.write::<U8>(byte),
+                            (_, _) => writer.write::<U8>(byte | 0x80),
+                        }
+                    }
+                }
+                ("F16Le", []) => writer.write::<U16Le>(to_f16(value)?),
+                ("F16Be", []) => writer.write::<U16Be>(to_f16(value)?),
+                ("F32Le", []) => writer.write::<F32Le>(to_f32(value)?),
+                ("F32Be", []) => writer.write::<F32Be>(to_f32(value)?),
+                ("F64Le", []) => writer.write::<F64Le>(to_f64(value)?),
+                ("F64Be", []) => writer.write::<F64Be>(to_f64(value)?),
+                ("FormatArray", [Elim::Function(len), Elim::Function(elem_type)]) => {
+                    for elem_value in to_elems(len, value)? {
+                        self.write_format(writer, elem_type, elem_value)?;
+                    }
+                }
+                ("FormatArrayMapIndex", [Elim::Function(len), Elim::Function(elem_type)]) => {
+                    for (index, elem_value) in to_elems(len, value)?.iter().enumerate() {
+                        let index = Arc::new(Value::int(index));
+                        let elem_type = semantics::function_elim(elem_type.clone(), index);
+                        self.write_format(writer, &elem_type, elem_value)?;
+                    }
+                }
+                (
+                    "FormatArrayChunked",
+                    [Elim::Function(rows), Elim::Function(cols), Elim::Function(elem_type)],
+                ) => {
+                    for row_value in to_elems(rows, value)? {
+                        for elem_value in to_elems(cols, row_value)? {
+                            self.write_format(writer, elem_type, elem_value)?;
+                        }
+                    }
+                }
+                ("FormatArrayReverse", [Elim::Function(format)]) => match value {
+                    Value::ArrayTerm(elem_values) => {
+                        let elem_values = elem_values.iter().rev().cloned().collect();
+                        self.write_format(writer, format, &Value::ArrayTerm(elem_values))?;
+                    }
+                    _ => return Err(WriteError::InvalidDataDescription),
+                },
+                // NOTE: The result of the fold is recomputed when reading, so
+                // only the elements need to be written.
+                ("FormatArrayFold", [_, _, Elim::Function(format)]) => match value {
+                    Value::StructTerm(fields) => match fields.get("elems") {
+                        Some(elem_values) => self.write_format(writer, format, elem_values)?,
+                        None => return Err(WriteError::InvalidDataDescription),
+                    },
+                    _ => return Err(WriteError::InvalidDataDescription),
+                },
+                // NOTE: The raw bytes are recaptured when reading, so only the
+                // structured value needs to be written.
+                ("FormatReprCoerce", [Elim::Function(format)]) => match value {
+                    Value::StructTerm(fields) => match fields.get("value") {
+                        Some(value) => self.write_format(writer, format, value)?,
+                        None => return Err(WriteError::InvalidDataDescription),
+                    },
+                    _ => return Err(WriteError::InvalidDataDescription),
+                },
+                ("FormatArraySplit", [_, Elim::Function(format)]) => {
+                    let (head_values, tail_values) = match value {
+                        Value::StructTerm(fields) => match (fields.get("head"), fields.get("tail"))
+                        {
+                            (Some(head_value), Some(tail_value)) => {
+                                match (head_value.as_ref(), tail_value.as_ref()) {
+                                    (
+                                        Value::ArrayTerm(head_values),
+                                        Value::ArrayTerm(tail_values),
+                                    ) => (head_values, tail_values),
+                                    _ => return Err(WriteError::InvalidDataDescription),
+                                }
+                            }
+                            _ => return Err(WriteError::InvalidDataDescription),
+                        },
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let elem_values = Iterator::chain(head_values.iter(), tail_values).cloned();
+                    self.write_format(writer, format, &Value::ArrayTerm(elem_values.collect()))?;
+                }
+                // NOTE: The elements that were left out when reading are
+                // filled in with the default value of the element type.
+                ("FormatArrayTake", [Elim::Function(count), Elim::Function(format)])
+                | ("FormatArrayDrop", [Elim::Function(count), Elim::Function(format)]) => {
+                    let format_repr = semantics::repr(format.clone());
+                    let (len, elem_type) = match format_repr.try_global() {
+                        Some(("Array", [Elim::Function(len), Elim::Function(elem_type)])) => {
+                            match len.as_ref() {
+                                Value::Primitive(Primitive::Int(len)) => {
+                                    (len.to_usize(), elem_type)
+                                }
+                                _ => (None, elem_type),
+                            }
+                        }
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let count = match count.as_ref() {
+                        Value::Primitive(Primitive::Int(count)) => count.to_usize(),
+                        _ => None,
+                    };
+                    let (len, count) = match (len, count) {
+                        (Some(len), Some(count)) if count <= len => (len, count),
+                        (_, _) => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let elem_values = match value {
+                        Value::ArrayTerm(elem_values) => elem_values,
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let (is_take, kept_len) = match name.as_str() {
+                        "FormatArrayTake" => (true, count),
+                        _ => (false, len - count),
+                    };
+                    if elem_values.len() != kept_len {
+                        return Err(WriteError::InvalidDataDescription);
+                    }
+                    let default_value =
+                        semantics::repr_default(self.globals, &self.items, elem_type)
+                            .ok_or(WriteError::InvalidDataDescription)?;
+                    let default_values = std::iter::repeat_n(default_value, len - kept_len);
+                    let elem_values =
+                        match is_take {
+                            true => Iterator::chain(elem_values.iter().cloned(), default_values)
+                                .collect(),
+                            false => Iterator::chain(default_values, elem_values.iter().cloned())
+                                .collect(),
+                        };
+                    self.write_format(writer, format, &Value::ArrayTerm(elem_values))?;
+                }
+                ("FormatArrayIndexed", [Elim::Function(format)]) => {
+                    let elem_values = match value {
+                        Value::ArrayTerm(elem_values) => elem_values.iter(),
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let elem_values = elem_values
+                        .map(|elem_value| match elem_value.as_ref() {
+                            Value::StructTerm(fields) => fields.get("value").cloned(),
+                            _ => None,
+                        })
+                        .collect::<Option<_>>()
+                        .ok_or(WriteError::InvalidDataDescription)?;
+                    self.write_format(writer, format, &Value::ArrayTerm(elem_values))?;
+                }
+                ("FormatArrayConcat", [Elim::Function(format0), Elim::Function(format1)]) => {
+                    let format0_repr = semantics::repr(format0.clone());
+                    let len0 = match format0_repr.try_global() {
+                        Some(("Array", [Elim::Function(len0), _])) => match len0.as_ref() {
+                            Value::Primitive(Primitive::Int(len0)) => len0.to_usize(),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    let (elem_values0, elem_values1) = match (value, len0) {
+                        (Value::ArrayTerm(elem_values), Some(len0))
+                            if len0 <= elem_values.len() =>
+                        {
+                            elem_values.split_at(len0)
+                        }
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    self.write_format(writer, format0, &Value::ArrayTerm(elem_values0.to_vec()))?;
+                    self.write_format(writer, format1, &Value::ArrayTerm(elem_values1.to_vec()))?;
+                }
+                ("FormatArrayZip", [Elim::Function(format0), Elim::Function(format1)]) => {
+                    let pairs = match value {
+                        Value::ArrayTerm(pairs) => pairs,
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let mut elem_values0 = Vec::with_capacity(pairs.len());
+                    let mut elem_values1 = Vec::with_capacity(pairs.len());
+                    for pair in pairs {
+                        match pair.as_ref() {
+                            Value::StructTerm(fields) => {
+                                match (fields.get("fst"), fields.get("snd")) {
+                                    (Some(fst), Some(snd)) => {
+                                        elem_values0.push(fst.clone());
+                                        elem_values1.push(snd.clone());
+                                    }
+                                    _ => return Err(WriteError::InvalidDataDescription),
+                                }
+                            }
+                            _ => return Err(WriteError::InvalidDataDescription),
+                        }
+                    }
+                    self.write_format(writer, format0, &Value::ArrayTerm(elem_values0))?;
+                    self.write_format(writer, format1, &Value::ArrayTerm(elem_values1))?;
+                }
+                // NOTE: The windows are assumed to overlap consistently, so
+                // the elements are recovered from the first window, followed by
+                // the last element of each of the remaining windows.
+                ("FormatArrayWindows", [Elim::Function(size), Elim::Function(format)]) => {
+                    let size = match size.as_ref() {
+                        Value::Primitive(Primitive::Int(size)) => size.to_usize(),
+                        _ => None,
+                    };
+                    let elem_values = match (size, value) {
+                        (Some(size), Value::ArrayTerm(window_values)) => {
+                            binary::unwindows(size, window_values)
+                        }
+                        (_, _) => None,
+                    };
+                    let elem_values = elem_values.ok_or(WriteError::InvalidDataDescription)?;
+                    self.write_format(writer, format, &elem_values)?;
+                }
+                ("FormatArrayTranspose", [Elim::Function(format)]) => {
+                    let value =
+                        binary::transpose(value).map_err(|_| WriteError::InvalidDataDescription)?;
+                    self.write_format(writer, format, &value)?;
+                }
+                ("FormatArraySorted", [Elim::Function(format)])
+                | ("FormatArraySortedBy", [_, Elim::Function(format)])
+                | ("FormatArrayUnique", [Elim::Function(format)])
+                | ("FormatArrayUniqueBy", [_, Elim::Function(format)]) => {
+                    self.write_format(writer, format, value)?;
+                }
+                // NOTE: The original order and duplicates of the elements are
+                // discarded when these formats are read, so they can't be
+                // recovered when writing.
+                ("FormatArraySort", [Elim::Function(_)])
+                | ("FormatArraySortBy", [_, Elim::Function(_)])
+                | ("FormatArrayDedup", [Elim::Function(_)])
+                | ("FormatArrayDedupBy", [_, Elim::Function(_)]) => {
+                    return Err(WriteError::NonInvertibleFormat {
+                        format: name.clone(),
+                    });
+                }
+                ("FormatArrayRemaining", [Elim::Function(elem_type)]) => match value {
+                    Value::ArrayTerm(elem_values) => {
+                        for elem_value in elem_values {
+                            self.write_format(writer, elem_type, elem_value)?;
+                        }
+                    }
+                    _ => return Err(WriteError::InvalidDataDescription),
+                },
+                // NOTE: The sentinel is not part of the value, so the default
+                // value of the element type is written in its place.
+                ("FormatRepeatUntil", [Elim::Function(predicate), Elim::Function(elem_type)]) => {
+                    let elem_values = match value {
+                        Value::ArrayTerm(elem_values) => elem_values,
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let is_sentinel = |elem_value: &Arc<Value>| {
+                        let is_sentinel =
+                            semantics::function_elim(predicate.clone(), elem_value.clone());
+                        match is_sentinel.try_global() {
+                            Some(("true", [])) => Ok(true),
+                            Some(("false", [])) => Ok(false),
+                            _ => Err(WriteError::InvalidDataDescription),
+                        }
+                    };
+                    for elem_value in elem_values {
+                        match elem_value.as_ref() {
+                            Value::Primitive(Primitive::Int(int)) if is_sentinel(elem_value)? => {
+                                return Err(unrepresentable(int));
+                            }
+                            Value::Primitive(Primitive::Int(_)) => {}
+                            _ => return Err(WriteError::InvalidDataDescription),
+                        }
+                        self.write_format(writer, elem_type, elem_value)?;
+                    }
+                    let elem_repr = semantics::repr(elem_type.clone());
+                    let sentinel = semantics::repr_default(self.globals, &self.items, &elem_repr)
+                        .ok_or(WriteError::InvalidDataDescription)?;
+                    if !is_sentinel(&sentinel)? {
+                        return Err(WriteError::NonInvertibleFormat {
+                            format: name.clone(),
+                        });
+                    }
+                    self.write_format(writer, elem_type, &sentinel)?;
+                }
+                // NOTE: The sentinel is the last element of the value, and
+                // must be the only element that satisfies the predicate.
+                (
+                    "FormatRepeatUntilInclusive",
+                    [Elim::Function(predicate), Elim::Function(elem_type)],
+                ) => {
+                    let elem_values = match value {
+                        Value::ArrayTerm(elem_values) => elem_values,
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    let last_index = match elem_values.len().checked_sub(1) {
+                        Some(last_index) => last_index,
+                        None => return Err(unrepresentable("[]")),
+                    };
+                    for (index, elem_value) in elem_values.iter().enumerate() {
+                        let is_sentinel =
+                            semantics::function_elim(predicate.clone(), elem_value.clone());
+                        let is_sentinel = match is_sentinel.try_global() {
+                            Some(("true", [])) => true,
+                            Some(("false", [])) => false,
+                            _ => return Err(WriteError::InvalidDataDescription),
+                        };
+                        match elem_value.as_ref() {
+                            Value::Primitive(Primitive::Int(int))
+                                if is_sentinel != (index == last_index) =>
+                            {
+                                return Err(unrepresentable(int));
+                            }
+                            Value::Primitive(Primitive::Int(_)) => {}
+                            _ => return Err(WriteError::InvalidDataDescription),
+                        }
+                        self.write_format(writer, elem_type, elem_value)?;
+                    }
+                }
+                ("FormatLengthPrefixed", [Elim::Function(len_type), Elim::Function(elem_type)]) => {
+                    match value {
+                        Value::ArrayTerm(elem_values) => {
+                            let len = Value::int(elem_values.len());
+                            self.write_format(writer, len_type, &len)?;
+                            for elem_value in elem_values {
+                                self.write_format(writer, elem_type, elem_value)?;
+                            }
+                        }
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    }
+                }
+                // NOTE: Positions are determined by the data that precedes
+                // them, and the data that links refer to is not written.
+                ("CurrentPos", []) => {}
+                ("Link", [Elim::Function(_), Elim::Function(_), Elim::Function(_)]) => {}
+                // NOTE: Only the values at each offset are kept when reading,
+                // so the offsets themselves can't be recovered when writing.
+                ("FormatAtOffsets", [_, _, _]) => {
+                    return Err(WriteError::NonInvertibleFormat {
+                        format: name.clone(),
+                    });
+                }
+                ("FormatConst", [Elim::Function(_), Elim::Function(format)]) => {
+                    self.write_format(writer, format, value)?;
+                }
+                // NOTE: Padding is computed from the number of bytes written so
+                // far, so this assumes that the writer starts at position zero.
+                ("FormatAlign", [Elim::Function(align)]) => {
+                    let align = match align.as_ref() {
+                        Value::Primitive(Primitive::Int(align)) => align.to_usize(),
+                        _ => None,
+                    };
+                    let align = (align.filter(|align| *align > 0))
+                        .ok_or(WriteError::InvalidDataDescription)?;
+                    match value.try_global() {
+                        Some(("unit", [])) => {}
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    }
+                    let offset = writer.buffer().len();
+                    for _ in 0..(align - offset % align) % align {
+                        writer.write::<U8>(0);
+                    }
+                }
+                // NOTE: Values that can't be represented exactly with the
+                // given number of fractional bits are rejected, rather than
+                // being rounded, so that they round-trip.
+                ("FormatFixed", [Elim::Function(frac_bits), Elim::Function(format)]) => {
+                    let scale =
+                        binary::fixed_scale(frac_bits).ok_or(WriteError::InvalidDataDescription)?;
+                    let float = to_f64(value)?;
+                    let scaled = float * scale;
+                    let int = match scaled.fract() == 0.0 {
+                        true => BigInt::from_f64(scaled),
+                        false => None,
+                    };
+                    let int = int.ok_or_else(|| unrepresentable(float))?;
+                    self.write_format(writer, format, &Value::int(int))?;
+                }
+                ("FormatAssertSize", [Elim::Function(len), Elim::Function(format)]) => {
+                    let len = match len.as_ref() {
+                        Value::Primitive(Primitive::Int(len)) => len.to_usize(),
+                        _ => None,
+                    };
+                    let len = len.ok_or(WriteError::InvalidDataDescription)?;
+
+                    let mut sized_writer = FormatWriter::new(Vec::with_capacity(len));
+                    self.write_format(&mut sized_writer, format, value)?;
+                    let sized_buffer = sized_writer.into_buffer();
+                    if sized_buffer.len() != len {
+                        return Err(WriteError::InvalidDataDescription);
+                    }
+                    for byte in sized_buffer {
+                        writer.write::<U8>(byte);
+                    }
+                }
+                (
+                    "FormatNested",
+                    [Elim::Function(len), Elim::Function(_), Elim::Function(format)],
+                ) => {
+                    let len = match len.as_ref() {
+                        Value::Primitive(Primitive::Int(len)) => len.to_usize(),
+                        _ => None,
+                    };
+                    let len = len.ok_or(WriteError::InvalidDataDescription)?;
+
+                    let mut nested_writer = FormatWriter::new(Vec::with_capacity(len));
+                    self.write_format(&mut nested_writer, format, value)?;
+                    let mut nested_buffer = nested_writer.into_buffer();
+                    if nested_buffer.len() > len {
+                        return Err(WriteError::InvalidDataDescription);
+                    }
+                    // Trailing bytes are not preserved when reading, so the
+                    // remainder of the region is filled with zeros.
+                    nested_buffer.resize(len, 0);
+                    for byte in nested_buffer {
+                        writer.write::<U8>(byte);
+                    }
+                }
+                // NOTE: There are no values of type `Void` to write.
+                ("FormatNever", []) => return Err(WriteError::InvalidDataDescription),
+                ("FormatBom", []) => match to_int(value, BigInt::to_u8)? {
+                    0 => {}
+                    encoding => {
+                        let (bom, _) = BYTE_ORDER_MARKS
+                            .iter()
+                            .find(|(_, bom_encoding)| *bom_encoding == encoding)
+                            .ok_or_else(|| unrepresentable(encoding))?;
+                        for byte in bom.iter() {
+                            writer.write::<U8>(*byte);
+                        }
+                    }
+                },
+                (
+                    "FormatPadded",
+                    [Elim::Function(len), Elim::Function(fill), Elim::Function(format)],
+                ) => {
+                    let (len, fill) = match (len.as_ref(), fill.as_ref()) {
+                        (
+                            Value::Primitive(Primitive::Int(len)),
+                            Value::Primitive(Primitive::Int(fill)),
+                        ) => (len.to_usize(), fill.to_u8()),
+                        _ => (None, None),
+                    };
+                    let len = len.ok_or(WriteError::InvalidDataDescription)?;
+                    let fill = fill.ok_or(WriteError::InvalidDataDescription)?;
+
+                    let mut padded_writer = FormatWriter::new(Vec::with_capacity(len));
+                    self.write_format(&mut padded_writer, format, value)?;
+                    let mut padded_buffer = padded_writer.into_buffer();
+                    if padded_buffer.len() > len {
+                        return Err(WriteError::InvalidDataDescription);
+                    }
+                    padded_buffer.resize(len, fill);
+                    for byte in padded_buffer {
+                        writer.write::<U8>(byte);
+                    }
+                }
+                ("FormatUtf8", [Elim::Function(len)]) => {
+                    let len = match len.as_ref() {
+                        Value::Primitive(Primitive::Int(len)) => len.to_usize(),
+                        _ => None,
+                    };
+                    match value {
+                        Value::Primitive(Primitive::String(string))
+                            if Some(string.len()) == len =>
+                        {
+                            for byte in string.bytes() {
+                                writer.write::<U8>(byte);
+                            }
+                        }
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    }
+                }
+                ("FormatAsciiLine", []) => match value {
+                    Value::ArrayTerm(elem_values) => {
+                        for elem_value in elem_values {
+                            match to_int(elem_value, BigInt::to_u8)? {
+                                b'\n' => return Err(unrepresentable(b'\n')),
+                                byte => writer.write::<U8>(byte),
+                            }
+                        }
+                        writer.write::<U8>(b'\n');
+                    }
+                    _ => return Err(WriteError::InvalidDataDescription),
+                },
+                ("FormatCStr", []) => match value {
+                    Value::ArrayTerm(elem_values) => {
+                        for elem_value in elem_values {
+                            match to_int(elem_value, BigInt::to_u8)? {
+                                0 => return Err(unrepresentable(0)),
+                                byte => writer.write::<U8>(byte),
+                            }
+                        }
+                        writer.write::<U8>(0);
+                    }
+                    _ => return Err(WriteError::InvalidDataDescription),
+                },
+                // NOTE: Trailing fill bytes would be stripped when reading, so
+                // they can not be written.
+                ("FormatStrFixed", [Elim::Function(len), Elim::Function(fill)]) => {
+                    let (len, fill) = match (len.as_ref(), fill.as_ref()) {
+                        (
+                            Value::Primitive(Primitive::Int(len)),
+                            Value::Primitive(Primitive::Int(fill)),
+                        ) => (len.to_usize(), fill.to_u8()),
+                        _ => (None, None),
+                    };
+                    let len = len.ok_or(WriteError::InvalidDataDescription)?;
+                    let fill = fill.ok_or(WriteError::InvalidDataDescription)?;
+
+                    let bytes = match value {
+                        Value::ArrayTerm(elem_values) => (elem_values.iter())
+                            .map(|elem_value| to_int(elem_value, BigInt::to_u8))
+                            .collect::<Result<Vec<_>, _>>()?,
+                        _ => return Err(WriteError::InvalidDataDescription),
+                    };
+                    if bytes.len() > len {
+                        return Err(WriteError::InvalidDataDescription);
+                    }
+                    if bytes.last() == Some(&fill) {
+                        return Err(unrepresentable(fill));
+                    }
+                    for byte in bytes.iter().chain(std::iter::repeat(&fill)).take(len) {
+                        writer.write::<U8>(*byte);
+                    }
+                }
+                (
+                    "FormatMinVersion",
+                    [Elim::Function(_), Elim::Function(_), Elim::Function(_), Elim::Function(format)],
+                ) => match value {
+                    Value::StructTerm(fields) => {
+                        for label in &["major", "minor", "patch"] {
+                            match fields.get(*label) {
+                                Some(part) => self.write_format(writer, format, part)?,
+                                None => return Err(WriteError::InvalidDataDescription),
+                            }
+                        }
+                    }
+                    _ => return Err(WriteError::InvalidDataDescription),
+                },
+                ("FormatPresentIf", [Elim::Function(is_present), Elim::Function(format)]) => {
+                    match (is_present.try_global(), value.try_global()) {
+                        (Some(("true", [])), Some(("some", [Elim::Function(value)]))) => {
+                            self.write_format(writer, format, value)?;
+                        }
+                        (Some(("false", [])), Some(("none", []))) => {}
+                        (_, _) => return Err(WriteError::InvalidDataDescription),
+                    }
+                }
+                (_, _) => return Err(WriteError::InvalidDataDescription),
+            },
+            Value::Stuck(Head::Item(item_name), elims) => {
+                match self.items.get(item_name).cloned().map(|item| item.data) {
+                    Some(semantics::ItemData::StructFormat(arity, field_declarations)) => {
+                        self.write_struct_format(
+                            writer,
+                            &field_declarations,
+                            &elims[..arity],
+                            value,
+                        )?;
+                    }
+                    Some(_) | None => return Err(WriteError::InvalidDataDescription),
+                }
+            }
+            Value::Stuck(Head::Local(_), _)
+            | Value::Stuck(Head::Error, _)
+            | Value::Sort(_)
+            | Value::FunctionType(_, _)
+            | Value::StructTerm(_)
+            | Value::ArrayTerm(_)
+            | Value::Primitive(_)
+            | Value::FormatType
+            | Value::Repr
+            | Value::Error => return Err(WriteError::InvalidDataDescription),
+        }
+
+        Ok(())
+    }
+}
+
+fn unrepresentable(value: impl ToString) -> WriteError {
+    WriteError::UnrepresentableValue {
+        value: value.to_string(),
+    }
+}
+
+/// Convert an integer value to its host representation.
+fn to_int<T>(value: &Value, convert: impl FnOnce(&BigInt) -> Option<T>) -> Result<T, WriteError> {
+    match value {
+        Value::Primitive(Primitive::Int(int)) => convert(int).ok_or_else(|| unrepresentable(int)),
+        _ => Err(WriteError::InvalidDataDescription),
+    }
+}
+
+/// Convert an integer to a `u32`, if it fits in 24 bits.
+fn to_u24(int: &BigInt) -> Option<u32> {
+    int.to_u32().filter(|int| *int < (1 << 24))
+}
+
+/// Convert an integer to an `i32`, if it fits in 24 bits.
+fn to_s24(int: &BigInt) -> Option<i32> {
+    int.to_i32()
+        .filter(|int| (-(1 << 23)..(1 << 23)).contains(int))
+}
+
+/// Get the elements of an array value, checking that it has the expected length.
+fn to_elems<'a>(len: &Value, value: &'a Value) -> Result<&'a [Arc<Value>], WriteError> {
+    match (len, value) {
+        (Value::Primitive(Primitive::Int(len)), Value::ArrayTerm(elem_values))
+            if *len == elem_values.len().into() =>
+        {
+            Ok(elem_values)
+        }
+        _ => Err(WriteError::InvalidDataDescription),
+    }
+}
+
+fn to_f16(value: &Value) -> Result<u16, WriteError> {
+    match value {
+        Value::Primitive(Primitive::F16(bits)) => Ok(*bits),
+        _ => Err(WriteError::InvalidDataDescription),
+    }
+}
+
+fn to_f32(value: &Value) -> Result<f32, WriteError> {
+    match value {
+        Value::Primitive(Primitive::F32(float)) => Ok(*float),
+        _ => Err(WriteError::InvalidDataDescription),
+    }
+}
+
+fn to_f64(value: &Value) -> Result<f64, WriteError> {
+    match value {
+        Value::Primitive(Primitive::F64(float)) => Ok(*float),
+        _ => Err(WriteError::InvalidDataDescription),
+    }
+}
@@ -0,0 +1,225 @@
+//! Structural hashing of the items in the core language.
+//!
+//! The hash of an item depends only on the shape of its definition, so it is
+//! unaffected by source locations, doc comments, and the names of the item and
+//! its parameters. Field labels are included, because they are part of the
+//! host representation of a struct. References to other items are hashed
+//! using the hash of the referenced item, so a change to an item also changes
+//! the hash of every item that depends on it.
+//!
+//! The hash uses [FNV-1a], so that it is stable between runs and between
+//! versions of the Rust standard library.
+//!
+//! [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::lang::core::{FieldDeclaration, ItemData, Module, Primitive, Sort, Term, TermData};
+
+/// Compute the structural hashes of the items in a module, indexed by their
+/// names.
+pub fn item_hashes(module: &Module) -> HashMap<String, u64> {
+    let mut item_hashes = HashMap::with_capacity(module.items.len());
+
+    for item in &module.items {
+        let mut hasher = Hasher::new(&item_hashes);
+        let name = match &item.data {
+            ItemData::Constant(constant) => {
+                hasher.write_u8(0);
+                hasher.term(&constant.term);
+                &constant.name
+            }
+            ItemData::StructType(struct_type) => {
+                hasher.write_u8(1);
+                hasher.params(&struct_type.params);
+                hasher.fields(&struct_type.fields);
+                &struct_type.name
+            }
+            ItemData::StructFormat(struct_format) => {
+                hasher.write_u8(2);
+                hasher.params(&struct_format.params);
+                hasher.fields(&struct_format.fields);
+                &struct_format.name
+            }
+        };
+        let hash = hasher.finish();
+        item_hashes.insert(name.clone(), hash);
+    }
+
+    item_hashes
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+struct Hasher<'item_hashes> {
+    item_hashes: &'item_hashes HashMap<String, u64>,
+    state: u64,
+}
+
+impl<'item_hashes> Hasher<'item_hashes> {
+    fn new(item_hashes: &'item_hashes HashMap<String, u64>) -> Hasher<'item_hashes> {
+        Hasher {
+            item_hashes,
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write(&(value as u64).to_le_bytes());
+    }
+
+    /// Write a length-prefixed sequence of bytes, so that adjacent strings can
+    /// not be confused with each other.
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_usize(bytes.len());
+        self.write(bytes);
+    }
+
+    fn params<Name>(&mut self, params: &[(Name, Arc<Term>)]) {
+        self.write_usize(params.len());
+        for (_, r#type) in params {
+            self.term(r#type);
+        }
+    }
+
+    fn fields(&mut self, fields: &[FieldDeclaration]) {
+        self.write_usize(fields.len());
+        for field in fields {
+            self.write_bytes(field.label.data.as_bytes());
+            self.term(&field.type_);
+        }
+    }
+
+    fn term(&mut self, term: &Term) {
+        match &term.data {
+            TermData::Global(name) => {
+                self.write_u8(0);
+                self.write_bytes(name.as_bytes());
+            }
+            TermData::Item(name) => {
+                self.write_u8(1);
+                match self.item_hashes.get(name) {
+                    Some(hash) => self.write(&hash.to_le_bytes()),
+                    // Unbound items are hashed by name, as they have no
+                    // definition to describe their structure.
+                    None => self.write_bytes(name.as_bytes()),
+                }
+            }
+            TermData::Local(index) => {
+                self.write_u8(2);
+                self.write_usize(index.to_usize());
+            }
+            TermData::Ann(term, r#type) => {
+                self.write_u8(3);
+                self.term(term);
+                self.term(r#type);
+            }
+            TermData::Sort(sort) => {
+                self.write_u8(4);
+                self.write_u8(match sort {
+                    Sort::Type => 0,
+                    Sort::Kind => 1,
+                });
+            }
+            TermData::FunctionType(param_type, body_type) => {
+                self.write_u8(5);
+                self.term(param_type);
+                self.term(body_type);
+            }
+            TermData::FunctionElim(head, argument) => {
+                self.write_u8(6);
+                self.term(head);
+                self.term(argument);
+            }
+            TermData::StructTerm(field_definitions) => {
+                self.write_u8(7);
+                self.write_usize(field_definitions.len());
+                for field_definition in field_definitions {
+                    self.write_bytes(field_definition.label.data.as_bytes());
+                    self.term(&field_definition.term);
+                }
+            }
+            TermData::StructElim(head, label) => {
+                self.write_u8(8);
+                self.term(head);
+                self.write_bytes(label.as_bytes());
+            }
+            TermData::ArrayTerm(elem_terms) => {
+                self.write_u8(9);
+                self.write_usize(elem_terms.len());
+                for elem_term in elem_terms {
+                    self.term(elem_term);
+                }
+            }
+            TermData::Primitive(primitive) => {
+                self.write_u8(10);
+                self.primitive(primitive);
+            }
+            TermData::BoolElim(head, if_true, if_false) => {
+                self.write_u8(11);
+                self.term(head);
+                self.term(if_true);
+                self.term(if_false);
+            }
+            TermData::IntElim(head, branches, default) => {
+                self.write_u8(12);
+                self.term(head);
+                self.write_usize(branches.len());
+                for (value, branch) in branches {
+                    self.write_bytes(&value.to_signed_bytes_le());
+                    self.term(branch);
+                }
+                self.term(default);
+            }
+            TermData::FormatType => self.write_u8(13),
+            TermData::Repr => self.write_u8(14),
+            TermData::Error => self.write_u8(15),
+        }
+    }
+
+    fn primitive(&mut self, primitive: &Primitive) {
+        match primitive {
+            Primitive::Int(value) => {
+                self.write_u8(0);
+                self.write_bytes(&value.to_signed_bytes_le());
+            }
+            Primitive::F16(bits) => {
+                self.write_u8(1);
+                self.write(&bits.to_le_bytes());
+            }
+            Primitive::F32(value) => {
+                self.write_u8(2);
+                self.write(&value.to_bits().to_le_bytes());
+            }
+            Primitive::F64(value) => {
+                self.write_u8(3);
+                self.write(&value.to_bits().to_le_bytes());
+            }
+            Primitive::String(value) => {
+                self.write_u8(4);
+                self.write_bytes(value.as_bytes());
+            }
+            Primitive::Pos(value) => {
+                self.write_u8(5);
+                self.write_usize(*value);
+            }
+        }
+    }
+}
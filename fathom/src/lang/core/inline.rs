@@ -0,0 +1,133 @@
+//! Inlining of trivial aliases in the core language.
+//!
+//! A constant is a trivial alias if its definition is a bare global, item, or
+//! primitive, for example `const Length : Format = U16Be` or
+//! `const Header2 : Format = Header`. These constants are removed from the
+//! module, and each reference to them is replaced with their definition. Chains
+//! of aliases are collapsed to their final definition.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::lang::core::{
+    FieldDeclaration, FieldDefinition, Item, ItemData, Module, StructFormat, StructType, Term,
+    TermData,
+};
+
+/// Inline the trivial aliases in a module.
+pub fn inline_aliases(module: &Module) -> Module {
+    let mut aliases = HashMap::new();
+    let mut items = Vec::with_capacity(module.items.len());
+
+    for item in &module.items {
+        let item_data = match &item.data {
+            ItemData::Constant(constant) => {
+                let term = inline_term(&aliases, &constant.term);
+                if let Some(alias) = alias_definition(&term) {
+                    aliases.insert(constant.name.clone(), alias.data.clone());
+                    continue;
+                }
+
+                let mut constant = constant.clone();
+                constant.term = Arc::new(term);
+                ItemData::Constant(constant)
+            }
+            ItemData::StructType(struct_type) => ItemData::StructType(StructType {
+                params: inline_params(&aliases, &struct_type.params),
+                fields: inline_fields(&aliases, &struct_type.fields),
+                ..struct_type.clone()
+            }),
+            ItemData::StructFormat(struct_format) => ItemData::StructFormat(StructFormat {
+                params: inline_params(&aliases, &struct_format.params),
+                fields: inline_fields(&aliases, &struct_format.fields),
+                ..struct_format.clone()
+            }),
+        };
+        items.push(Item::new(item.location, item_data));
+    }
+
+    Module {
+        doc: module.doc.clone(),
+        items,
+    }
+}
+
+/// Returns the definition of a constant if it is a trivial alias.
+fn alias_definition(term: &Term) -> Option<&Term> {
+    match &term.data {
+        TermData::Ann(term, _) => alias_definition(term),
+        TermData::Global(_) | TermData::Item(_) | TermData::Primitive(_) => Some(term),
+        _ => None,
+    }
+}
+
+fn inline_params<Name: Clone>(
+    aliases: &HashMap<String, TermData>,
+    params: &[(Name, Arc<Term>)],
+) -> Vec<(Name, Arc<Term>)> {
+    (params.iter())
+        .map(|(name, r#type)| (name.clone(), Arc::new(inline_term(aliases, r#type))))
+        .collect()
+}
+
+fn inline_fields(
+    aliases: &HashMap<String, TermData>,
+    fields: &[FieldDeclaration],
+) -> Arc<[FieldDeclaration]> {
+    (fields.iter())
+        .map(|field| FieldDeclaration {
+            type_: Arc::new(inline_term(aliases, &field.type_)),
+            ..field.clone()
+        })
+        .collect()
+}
+
+fn inline_term(aliases: &HashMap<String, TermData>, term: &Term) -> Term {
+    let inline = |term: &Arc<Term>| Arc::new(inline_term(aliases, term));
+
+    let term_data = match &term.data {
+        TermData::Item(name) => match aliases.get(name) {
+            Some(term_data) => term_data.clone(),
+            None => TermData::Item(name.clone()),
+        },
+        TermData::Global(_)
+        | TermData::Local(_)
+        | TermData::Sort(_)
+        | TermData::Primitive(_)
+        | TermData::FormatType
+        | TermData::Repr
+        | TermData::Error => term.data.clone(),
+
+        TermData::Ann(term, r#type) => TermData::Ann(inline(term), inline(r#type)),
+        TermData::FunctionType(param_type, body_type) => {
+            TermData::FunctionType(inline(param_type), inline(body_type))
+        }
+        TermData::FunctionElim(head, argument) => {
+            TermData::FunctionElim(inline(head), inline(argument))
+        }
+        TermData::StructTerm(field_definitions) => TermData::StructTerm(
+            (field_definitions.iter())
+                .map(|field_definition| FieldDefinition {
+                    label: field_definition.label.clone(),
+                    term: inline(&field_definition.term),
+                })
+                .collect(),
+        ),
+        TermData::StructElim(head, label) => TermData::StructElim(inline(head), label.clone()),
+        TermData::ArrayTerm(elem_terms) => {
+            TermData::ArrayTerm(elem_terms.iter().map(inline).collect())
+        }
+        TermData::BoolElim(head, if_true, if_false) => {
+            TermData::BoolElim(inline(head), inline(if_true), inline(if_false))
+        }
+        TermData::IntElim(head, branches, default) => TermData::IntElim(
+            inline(head),
+            (branches.iter())
+                .map(|(value, branch)| (value.clone(), inline(branch)))
+                .collect(),
+            inline(default),
+        ),
+    };
+
+    Term::new(term.location, term_data)
+}
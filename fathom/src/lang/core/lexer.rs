@@ -27,6 +27,8 @@ pub enum Token<'source> {
     BoolElim,
     #[token("const")]
     Const,
+    #[token("f16")]
+    F16,
     #[token("f32")]
     F32,
     #[token("f64")]
@@ -47,6 +49,8 @@ pub enum Token<'source> {
     Local,
     #[token("repr")]
     Repr,
+    #[token("string")]
+    String,
     #[token("struct")]
     Struct,
     #[token("Type")]
@@ -102,6 +106,7 @@ impl<'source> fmt::Display for Token<'source> {
             Token::Array => write!(f, "array"),
             Token::BoolElim => write!(f, "bool_elim"),
             Token::Const => write!(f, "const"),
+            Token::F16 => write!(f, "f16"),
             Token::F32 => write!(f, "f32"),
             Token::F64 => write!(f, "f64"),
             Token::Format => write!(f, "Format"),
@@ -112,6 +117,7 @@ impl<'source> fmt::Display for Token<'source> {
             Token::Kind => write!(f, "Kind"),
             Token::Local => write!(f, "local"),
             Token::Repr => write!(f, "repr"),
+            Token::String => write!(f, "string"),
             Token::Struct => write!(f, "struct"),
             Token::Type => write!(f, "Type"),
 
@@ -2,10 +2,15 @@
 
 use contracts::debug_ensures;
 use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+#[cfg(feature = "eval-stats")]
+use std::cell::Cell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::ops::AddAssign;
 use std::sync::Arc;
 
+use crate::ieee754;
 use crate::lang::core::{
     FieldDeclaration, FieldDefinition, Globals, LocalLevel, LocalSize, Locals, Primitive, Sort,
     Term, TermData,
@@ -47,6 +52,14 @@ impl ItemData {
     }
 }
 
+/// Get the field declarations of a value that refers to one of the global
+/// struct types, like `Pair`.
+pub fn global_field_declarations(globals: &Globals, value: &Value) -> Option<FieldDeclarations> {
+    let (name, elims) = value.try_global()?;
+    let (arity, fields) = globals.get_struct_type(name)?;
+    ItemData::StructType(*arity, fields.clone()).try_field_declarations(elims)
+}
+
 /// Allows field declarations to be iterated over.
 pub struct FieldDeclarations {
     is_format: bool,
@@ -85,6 +98,33 @@ impl FieldDeclarations {
         }
     }
 
+    /// Check if a field with the given label is declared.
+    pub fn contains_field(&self, label: &str) -> bool {
+        self.fields.iter().any(|field| field.label.data == label)
+    }
+
+    /// Get the types of all the field declarations, in order, where the
+    /// fields are projected from the given head.
+    pub fn get_field_types(
+        mut self,
+        globals: &Globals,
+        items: &HashMap<String, Item>,
+        head: Arc<Value>,
+    ) -> Vec<(String, Arc<Value>)> {
+        let mut field_types = Vec::with_capacity(self.fields.len());
+        for field_declaration in self.fields.iter() {
+            let r#type = eval(globals, items, &mut self.locals, &field_declaration.type_);
+            let r#type = match self.is_format {
+                true => repr(r#type),
+                false => r#type,
+            };
+            field_types.push((field_declaration.label.data.clone(), r#type));
+            let value = struct_elim(head.clone(), &field_declaration.label.data);
+            self.locals.push(value);
+        }
+        field_types
+    }
+
     /// Get the type of a field declaration.
     pub fn get_field_type(
         mut self,
@@ -168,6 +208,11 @@ impl Value {
         Value::Primitive(Primitive::Int(data.into()))
     }
 
+    /// Create a 16-bit float primitive from its bits.
+    pub fn f16(bits: u16) -> Value {
+        Value::Primitive(Primitive::F16(bits))
+    }
+
     /// Create a 32-bit float primitive.
     pub fn f32(data: impl Into<f32>) -> Value {
         Value::Primitive(Primitive::F32(data.into()))
@@ -261,6 +306,88 @@ pub fn normalize(
     read_back(globals, items, locals.size(), &value)
 }
 
+/// Counts of the work done by the evaluator, for diagnosing performance
+/// problems like formats that cause values to be duplicated exponentially.
+///
+/// The counts are only recorded when the `eval-stats` feature is enabled.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct EvalStats {
+    /// The number of terms that were evaluated.
+    pub evals: usize,
+    /// The number of function eliminations that were computed.
+    pub function_elims: usize,
+    /// The number of values that were allocated, including copies made when
+    /// modifying shared values.
+    pub value_allocs: usize,
+    /// The number of `Arc` clones made to share existing values.
+    pub arc_clones: usize,
+}
+
+impl AddAssign for EvalStats {
+    fn add_assign(&mut self, other: EvalStats) {
+        self.evals += other.evals;
+        self.function_elims += other.function_elims;
+        self.value_allocs += other.value_allocs;
+        self.arc_clones += other.arc_clones;
+    }
+}
+
+#[cfg(feature = "eval-stats")]
+thread_local! {
+    static EVAL_STATS: Cell<Option<EvalStats>> = const { Cell::new(None) };
+}
+
+/// Start counting the work done by the evaluator on the current thread.
+pub fn start_eval_stats() {
+    #[cfg(feature = "eval-stats")]
+    EVAL_STATS.with(|eval_stats| eval_stats.set(Some(EvalStats::default())));
+}
+
+/// Stop counting the work done by the evaluator on the current thread,
+/// returning the counts since [`start_eval_stats`] was called, if it was and
+/// the `eval-stats` feature is enabled.
+pub fn finish_eval_stats() -> Option<EvalStats> {
+    #[cfg(feature = "eval-stats")]
+    return EVAL_STATS.with(|eval_stats| eval_stats.take());
+    #[cfg(not(feature = "eval-stats"))]
+    None
+}
+
+#[cfg(feature = "eval-stats")]
+fn record_eval_stats(record: impl FnOnce(&mut EvalStats)) {
+    EVAL_STATS.with(|eval_stats| {
+        if let Some(mut stats) = eval_stats.get() {
+            record(&mut stats);
+            eval_stats.set(Some(stats));
+        }
+    });
+}
+
+#[cfg(not(feature = "eval-stats"))]
+#[inline(always)]
+fn record_eval_stats(_record: impl FnOnce(&mut EvalStats)) {}
+
+/// Allocate a new value.
+fn alloc_value(value: Value) -> Arc<Value> {
+    record_eval_stats(|stats| stats.value_allocs += 1);
+    Arc::new(value)
+}
+
+/// Share an existing value.
+fn share_value(value: &Arc<Value>) -> Arc<Value> {
+    record_eval_stats(|stats| stats.arc_clones += 1);
+    value.clone()
+}
+
+/// Get a mutable reference to a value, copying it first if it is shared.
+fn make_mut_value(value: &mut Arc<Value>) -> &mut Value {
+    record_eval_stats(|stats| match Arc::strong_count(value) {
+        1 => {}
+        _ => stats.value_allocs += 1,
+    });
+    Arc::make_mut(value)
+}
+
 /// Evaluate a [`core::Term`] into a [`Value`].
 ///
 /// [`Value`]: crate::lang::core::semantics::Value
@@ -272,44 +399,57 @@ pub fn eval(
     locals: &mut Locals<Arc<Value>>,
     term: &Term,
 ) -> Arc<Value> {
+    record_eval_stats(|stats| stats.evals += 1);
+
     match &term.data {
         TermData::Global(global_name) => match globals.get(global_name) {
-            None => Arc::new(Value::Error),
+            None => alloc_value(Value::Error),
             Some((_, global_term)) => match global_term {
                 Some(global_term) => eval(globals, items, locals, global_term),
-                None => Arc::new(Value::global(global_name.clone(), Vec::new())),
+                None => alloc_value(Value::global(global_name.clone(), Vec::new())),
             },
         },
         TermData::Item(item_name) => match items.get(item_name.as_str()) {
-            None => Arc::new(Value::Error),
+            None => alloc_value(Value::Error),
             Some(item) => match &item.data {
-                ItemData::Constant(value) => value.clone(),
+                ItemData::Constant(value) => share_value(value),
                 ItemData::StructType(_, _) | ItemData::StructFormat(_, _) => {
-                    Arc::new(Value::item(item_name.clone(), Vec::new()))
+                    alloc_value(Value::item(item_name.clone(), Vec::new()))
                 }
             },
         },
         TermData::Local(local_index) => match locals.get(*local_index) {
-            Some(value) => value.clone(),
+            Some(value) => share_value(value),
             None => {
                 let local_level = locals.size().index_to_level(*local_index).unwrap();
-                Arc::new(Value::local(local_level, Vec::new()))
+                alloc_value(Value::local(local_level, Vec::new()))
             }
         },
 
         TermData::Ann(term, _) => eval(globals, items, locals, term),
-        TermData::Sort(sort) => Arc::new(Value::Sort(*sort)),
+        TermData::Sort(sort) => alloc_value(Value::Sort(*sort)),
 
         TermData::FunctionType(param_type, body_type) => {
             let param_type = eval(globals, items, locals, param_type);
             let body_type = eval(globals, items, locals, body_type);
 
-            Arc::new(Value::FunctionType(param_type, body_type))
+            alloc_value(Value::FunctionType(param_type, body_type))
         }
         TermData::FunctionElim(head, argument) => {
             let head = eval(globals, items, locals, head);
             let argument = eval(globals, items, locals, argument);
-            function_elim(head, argument)
+            let value = function_elim(head, argument);
+            // The size of a format depends on the items it refers to, so it
+            // can't be computed in `global_step`.
+            match value.try_global() {
+                Some(("FormatSize", [Elim::Function(format)])) => {
+                    match format_size(globals, items, format) {
+                        Some(size) => alloc_value(Value::Primitive(Primitive::Int(size))),
+                        None => value,
+                    }
+                }
+                _ => value,
+            }
         }
 
         TermData::StructTerm(field_definitions) => {
@@ -323,7 +463,7 @@ pub fn eval(
                 })
                 .collect();
 
-            Arc::new(Value::StructTerm(field_definitions))
+            alloc_value(Value::StructTerm(field_definitions))
         }
         TermData::StructElim(head, field) => {
             let head = eval(globals, items, locals, head);
@@ -336,10 +476,10 @@ pub fn eval(
                 .map(|elem_term| eval(globals, items, locals, elem_term))
                 .collect();
 
-            Arc::new(Value::ArrayTerm(elem_values))
+            alloc_value(Value::ArrayTerm(elem_values))
         }
 
-        TermData::Primitive(primitive) => Arc::new(Value::Primitive(primitive.clone())),
+        TermData::Primitive(primitive) => alloc_value(Value::Primitive(primitive.clone())),
         TermData::BoolElim(head, if_true, if_false) => {
             let head = eval(globals, items, locals, head);
             bool_elim(globals, items, locals, head, if_true, if_false)
@@ -349,36 +489,156 @@ pub fn eval(
             int_elim(globals, items, locals, head, branches, default)
         }
 
-        TermData::FormatType => Arc::new(Value::FormatType),
+        TermData::FormatType => alloc_value(Value::FormatType),
 
-        TermData::Repr => Arc::new(Value::Repr),
+        TermData::Repr => alloc_value(Value::Repr),
 
-        TermData::Error => Arc::new(Value::Error),
+        TermData::Error => alloc_value(Value::Error),
     }
 }
 
-fn function_elim(mut head: Arc<Value>, argument: Arc<Value>) -> Arc<Value> {
-    match Arc::make_mut(&mut head) {
+/// Apply a function value to an argument.
+pub fn function_elim(mut head: Arc<Value>, argument: Arc<Value>) -> Arc<Value> {
+    record_eval_stats(|stats| stats.function_elims += 1);
+
+    match make_mut_value(&mut head) {
         Value::Repr => repr(argument),
+        Value::Stuck(Head::Global(name), elims) => {
+            elims.push(Elim::Function(argument));
+            match global_step(name, elims) {
+                Some(value) => value,
+                None => head,
+            }
+        }
         Value::Stuck(_, elims) => {
             elims.push(Elim::Function(argument));
             head
         }
-        _ => Arc::new(Value::Error),
+        _ => alloc_value(Value::Error),
+    }
+}
+
+/// Attempt to compute the result of a fully applied global, returning `None`
+/// if the global does not compute, or if its arguments are not yet known.
+fn global_step(name: &str, elims: &[Elim]) -> Option<Arc<Value>> {
+    match (name, elims) {
+        // NOTE: Unlike `if`, both branches have already been evaluated by
+        // the time they are passed to `BoolSelect`.
+        (
+            "BoolSelect",
+            [Elim::Function(head), Elim::Function(if_true), Elim::Function(if_false)],
+        ) => match head.try_global() {
+            Some(("true", [])) => Some(share_value(if_true)),
+            Some(("false", [])) => Some(share_value(if_false)),
+            _ => None,
+        },
+        ("IntAdd", [Elim::Function(value0), Elim::Function(value1)]) => {
+            match (value0.as_ref(), value1.as_ref()) {
+                (
+                    Value::Primitive(Primitive::Int(value0)),
+                    Value::Primitive(Primitive::Int(value1)),
+                ) => Some(alloc_value(Value::int(value0 + value1))),
+                _ => None,
+            }
+        }
+        ("IntMax", [Elim::Function(value0), Elim::Function(value1)]) => {
+            match (value0.as_ref(), value1.as_ref()) {
+                (
+                    Value::Primitive(Primitive::Int(value0)),
+                    Value::Primitive(Primitive::Int(value1)),
+                ) => Some(alloc_value(Value::int(
+                    std::cmp::max(value0, value1).clone(),
+                ))),
+                _ => None,
+            }
+        }
+        ("IntDiv", [Elim::Function(value0), Elim::Function(value1)]) => {
+            match (value0.as_ref(), value1.as_ref()) {
+                (
+                    Value::Primitive(Primitive::Int(value0)),
+                    Value::Primitive(Primitive::Int(value1)),
+                ) if *value1 != BigInt::from(0) => Some(alloc_value(Value::int(value0 / value1))),
+                _ => None,
+            }
+        }
+        ("F16ToF32", [Elim::Function(value)]) => match value.as_ref() {
+            Value::Primitive(Primitive::F16(bits)) => {
+                Some(alloc_value(Value::f32(ieee754::f16_to_f32(*bits))))
+            }
+            _ => None,
+        },
+        ("F32ToF16", [Elim::Function(value)]) => match value.as_ref() {
+            Value::Primitive(Primitive::F32(value)) => {
+                Some(alloc_value(Value::f16(ieee754::f32_to_f16(*value))))
+            }
+            _ => None,
+        },
+        ("IntTestBits", [Elim::Function(value), Elim::Function(mask)]) => {
+            match (value.as_ref(), mask.as_ref()) {
+                (
+                    Value::Primitive(Primitive::Int(value)),
+                    Value::Primitive(Primitive::Int(mask)),
+                ) => {
+                    let is_set = (value & mask) != BigInt::from(0);
+                    Some(alloc_value(Value::global(is_set.to_string(), Vec::new())))
+                }
+                _ => None,
+            }
+        }
+        // Integer formats with a known endianness and width compute to the
+        // corresponding integer format, for example `FormatUInt big 16`
+        // computes to `U16Be`.
+        ("FormatUInt", [Elim::Function(endianness), Elim::Function(width)])
+        | ("FormatSInt", [Elim::Function(endianness), Elim::Function(width)]) => {
+            let prefix = match name {
+                "FormatUInt" => "U",
+                _ => "S",
+            };
+            let suffix = match endianness.try_global() {
+                Some(("big", [])) => "Be",
+                Some(("little", [])) => "Le",
+                _ => return None,
+            };
+            let name = match width.as_ref() {
+                Value::Primitive(Primitive::Int(width)) if *width == BigInt::from(8) => {
+                    format!("{}8", prefix)
+                }
+                Value::Primitive(Primitive::Int(width))
+                    if [16, 24, 32, 64].iter().any(|w| *width == BigInt::from(*w)) =>
+                {
+                    format!("{}{}{}", prefix, width, suffix)
+                }
+                _ => return None,
+            };
+            Some(alloc_value(Value::global(name, Vec::new())))
+        }
+        ("IntEq", [Elim::Function(value0), Elim::Function(value1)]) => {
+            match (value0.as_ref(), value1.as_ref()) {
+                (
+                    Value::Primitive(Primitive::Int(value0)),
+                    Value::Primitive(Primitive::Int(value1)),
+                ) => {
+                    let is_equal = value0 == value1;
+                    Some(alloc_value(Value::global(is_equal.to_string(), Vec::new())))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
     }
 }
 
 fn struct_elim(mut head: Arc<Value>, field_name: &str) -> Arc<Value> {
-    match Arc::make_mut(&mut head) {
+    match make_mut_value(&mut head) {
         Value::StructTerm(fields) => match fields.get(field_name) {
-            Some(field) => field.clone(),
-            None => Arc::new(Value::Error),
+            Some(field) => share_value(field),
+            None => alloc_value(Value::Error),
         },
         Value::Stuck(_, elims) => {
             elims.push(Elim::Struct(field_name.to_owned()));
             head
         }
-        _ => Arc::new(Value::Error),
+        _ => alloc_value(Value::Error),
     }
 }
 
@@ -391,11 +651,11 @@ fn bool_elim(
     if_true: &Arc<Term>,
     if_false: &Arc<Term>,
 ) -> Arc<Value> {
-    match Arc::make_mut(&mut head) {
+    match make_mut_value(&mut head) {
         Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
             ("true", []) => eval(globals, items, locals, if_true),
             ("false", []) => eval(globals, items, locals, if_false),
-            _ => Arc::new(Value::Error),
+            _ => alloc_value(Value::Error),
         },
         Value::Stuck(_, elims) => {
             elims.push(Elim::Bool(
@@ -405,7 +665,7 @@ fn bool_elim(
             ));
             head
         }
-        _ => Arc::new(Value::Error),
+        _ => alloc_value(Value::Error),
     }
 }
 
@@ -418,7 +678,7 @@ fn int_elim(
     branches: &BTreeMap<BigInt, Arc<Term>>,
     default: &Arc<Term>,
 ) -> Arc<Value> {
-    match Arc::make_mut(&mut head) {
+    match make_mut_value(&mut head) {
         Value::Primitive(Primitive::Int(value)) => match branches.get(&value) {
             Some(term) => eval(globals, items, locals, term),
             None => eval(globals, items, locals, default),
@@ -427,53 +687,632 @@ fn int_elim(
             elims.push(Elim::Int(locals.clone(), branches.clone(), default.clone()));
             head
         }
-        _ => Arc::new(Value::Error),
+        _ => alloc_value(Value::Error),
     }
 }
 
 pub fn repr(mut head: Arc<Value>) -> Arc<Value> {
-    match Arc::make_mut(&mut head) {
+    match make_mut_value(&mut head) {
         Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
-            ("U8", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("U16Be", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("U16Le", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("U32Le", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("U32Be", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("U64Le", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("U64Be", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("S8", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("S16Le", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("S16Be", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("S32Le", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("S32Be", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("S64Le", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("S64Be", []) => Arc::new(Value::global("Int", Vec::new())),
-            ("F32Le", []) => Arc::new(Value::global("F32", Vec::new())),
-            ("F32Be", []) => Arc::new(Value::global("F32", Vec::new())),
-            ("F64Le", []) => Arc::new(Value::global("F64", Vec::new())),
-            ("F64Be", []) => Arc::new(Value::global("F64", Vec::new())),
+            ("U8", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U16", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U16Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U16Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U24Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U24Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U32", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U32Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U32Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U64", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U64Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("U64Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S8", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S16", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S16Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S16Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S32", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S24Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S24Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S32Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S32Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S64", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S64Le", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("S64Be", []) => alloc_value(Value::global("Int", Vec::new())),
+            // Integer formats whose endianness or width is not yet known are
+            // still represented by `Int`.
+            ("FormatUInt", [Elim::Function(_), Elim::Function(_)])
+            | ("FormatSInt", [Elim::Function(_), Elim::Function(_)]) => {
+                alloc_value(Value::global("Int", Vec::new()))
+            }
+            ("FormatULeb128", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("FormatSLeb128", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("FormatVlq", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("F16Le", []) => alloc_value(Value::global("F16", Vec::new())),
+            ("F16Be", []) => alloc_value(Value::global("F16", Vec::new())),
+            ("F32Le", []) => alloc_value(Value::global("F32", Vec::new())),
+            ("F32Be", []) => alloc_value(Value::global("F32", Vec::new())),
+            ("F64Le", []) => alloc_value(Value::global("F64", Vec::new())),
+            ("F64Be", []) => alloc_value(Value::global("F64", Vec::new())),
             ("FormatArray", [Elim::Function(len), Elim::Function(elem_type)]) => {
-                Arc::new(Value::global(
+                alloc_value(Value::global(
+                    "Array",
+                    vec![
+                        Elim::Function(share_value(len)),
+                        Elim::Function(repr(share_value(elem_type))),
+                    ],
+                ))
+            }
+            // NOTE: Types can not depend on the index of an element, so the
+            // representation of the first element is used for all of them.
+            // Element formats whose representations do depend on the index are
+            // rejected during elaboration.
+            ("FormatArrayMapIndex", [Elim::Function(len), Elim::Function(elem_type)]) => {
+                let first_elem_type =
+                    function_elim(share_value(elem_type), alloc_value(Value::int(0)));
+                alloc_value(Value::global(
                     "Array",
                     vec![
-                        Elim::Function(len.clone()),
-                        Elim::Function(repr(elem_type.clone())),
+                        Elim::Function(share_value(len)),
+                        Elim::Function(repr(first_elem_type)),
                     ],
                 ))
             }
+            (
+                "FormatArrayChunked",
+                [Elim::Function(rows), Elim::Function(cols), Elim::Function(elem_type)],
+            ) => alloc_value(Value::global(
+                "Array",
+                vec![
+                    Elim::Function(share_value(rows)),
+                    Elim::Function(alloc_value(Value::global(
+                        "Array",
+                        vec![
+                            Elim::Function(share_value(cols)),
+                            Elim::Function(repr(share_value(elem_type))),
+                        ],
+                    ))),
+                ],
+            )),
+            ("FormatArrayReverse", [Elim::Function(format)])
+            | ("FormatArraySorted", [Elim::Function(format)])
+            | ("FormatArraySortedBy", [_, Elim::Function(format)])
+            | ("FormatArrayUnique", [Elim::Function(format)])
+            | ("FormatArrayUniqueBy", [_, Elim::Function(format)])
+            | ("FormatArraySort", [Elim::Function(format)])
+            | ("FormatArraySortBy", [_, Elim::Function(format)]) => repr(share_value(format)),
+            ("FormatArrayFold", [Elim::Function(_), Elim::Function(_), Elim::Function(format)]) => {
+                alloc_value(Value::global(
+                    "Folded",
+                    vec![Elim::Function(repr(share_value(format)))],
+                ))
+            }
+            ("FormatReprCoerce", [Elim::Function(format)]) => alloc_value(Value::global(
+                "Captured",
+                vec![Elim::Function(repr(share_value(format)))],
+            )),
+            ("FormatArraySplit", [Elim::Function(_), Elim::Function(format)]) => {
+                let format_repr = repr(share_value(format));
+                if let Some(("Array", [Elim::Function(_), Elim::Function(elem_type)])) =
+                    format_repr.try_global()
+                {
+                    let elem_type = Elim::Function(share_value(elem_type));
+                    return alloc_value(Value::global("Split", vec![elem_type]));
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            ("FormatArrayTake", [Elim::Function(count), Elim::Function(format)]) => {
+                let format_repr = repr(share_value(format));
+                if let Some(("Array", [Elim::Function(_), Elim::Function(elem_type)])) =
+                    format_repr.try_global()
+                {
+                    let elims = vec![
+                        Elim::Function(share_value(count)),
+                        Elim::Function(share_value(elem_type)),
+                    ];
+                    return alloc_value(Value::global("Array", elims));
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            ("FormatArrayDrop", [Elim::Function(_), Elim::Function(format)]) => {
+                let format_repr = repr(share_value(format));
+                if let Some(("Array", [Elim::Function(_), Elim::Function(elem_type)])) =
+                    format_repr.try_global()
+                {
+                    let elem_type = Elim::Function(share_value(elem_type));
+                    return alloc_value(Value::global("List", vec![elem_type]));
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            ("FormatArrayIndexed", [Elim::Function(format)]) => {
+                let format_repr = repr(share_value(format));
+                if let Some(("Array", [Elim::Function(len), Elim::Function(elem_type)])) =
+                    format_repr.try_global()
+                {
+                    let indexed_type =
+                        Value::global("Indexed", vec![Elim::Function(share_value(elem_type))]);
+                    return alloc_value(Value::global(
+                        "Array",
+                        vec![
+                            Elim::Function(share_value(len)),
+                            Elim::Function(alloc_value(indexed_type)),
+                        ],
+                    ));
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            ("FormatArrayZip", [Elim::Function(format0), Elim::Function(format1)]) => {
+                let (format0_repr, format1_repr) =
+                    (repr(share_value(format0)), repr(share_value(format1)));
+                if let (
+                    Some(("Array", [Elim::Function(len), Elim::Function(elem_type0)])),
+                    Some(("Array", [_, Elim::Function(elem_type1)])),
+                ) = (format0_repr.try_global(), format1_repr.try_global())
+                {
+                    let pair_type = Value::global(
+                        "Pair",
+                        vec![
+                            Elim::Function(share_value(elem_type0)),
+                            Elim::Function(share_value(elem_type1)),
+                        ],
+                    );
+                    return alloc_value(Value::global(
+                        "Array",
+                        vec![
+                            Elim::Function(share_value(len)),
+                            Elim::Function(alloc_value(pair_type)),
+                        ],
+                    ));
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            ("FormatArrayConcat", [Elim::Function(format0), Elim::Function(format1)]) => {
+                let (format0_repr, format1_repr) =
+                    (repr(share_value(format0)), repr(share_value(format1)));
+                if let (
+                    Some(("Array", [Elim::Function(len0), Elim::Function(elem_type)])),
+                    Some(("Array", [Elim::Function(len1), _])),
+                ) = (format0_repr.try_global(), format1_repr.try_global())
+                {
+                    let len = alloc_value(Value::global("IntAdd", Vec::new()));
+                    let len = function_elim(len, share_value(len0));
+                    let len = function_elim(len, share_value(len1));
+                    return alloc_value(Value::global(
+                        "Array",
+                        vec![Elim::Function(len), Elim::Function(share_value(elem_type))],
+                    ));
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            (
+                "FormatAtOffsets",
+                [Elim::Function(_), Elim::Function(offsets), Elim::Function(format)],
+            ) => {
+                let offsets_repr = repr(share_value(offsets));
+                if let Some(("Array", [Elim::Function(len), Elim::Function(_)])) =
+                    offsets_repr.try_global()
+                {
+                    return alloc_value(Value::global(
+                        "Array",
+                        vec![
+                            Elim::Function(share_value(len)),
+                            Elim::Function(repr(share_value(format))),
+                        ],
+                    ));
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            ("FormatArrayWindows", [Elim::Function(size), Elim::Function(format)]) => {
+                let format_repr = repr(share_value(format));
+                if let Some(("Array", [Elim::Function(len), Elim::Function(elem_type)])) =
+                    format_repr.try_global()
+                {
+                    if let (
+                        Value::Primitive(Primitive::Int(size)),
+                        Value::Primitive(Primitive::Int(len)),
+                    ) = (size.as_ref(), len.as_ref())
+                    {
+                        // Empty windows, or windows that are longer than the
+                        // array, are an error when the array is read.
+                        if *size <= BigInt::from(0) || size > len {
+                            elims.push(Elim::Repr);
+                            return head;
+                        }
+
+                        let window_type = Value::global(
+                            "Array",
+                            vec![
+                                Elim::Function(alloc_value(Value::int(size.clone()))),
+                                Elim::Function(share_value(elem_type)),
+                            ],
+                        );
+                        return alloc_value(Value::global(
+                            "Array",
+                            vec![
+                                Elim::Function(alloc_value(Value::int(len - size + 1))),
+                                Elim::Function(alloc_value(window_type)),
+                            ],
+                        ));
+                    }
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            ("FormatArrayTranspose", [Elim::Function(format)]) => {
+                let format_repr = repr(share_value(format));
+                if let Some(("Array", [Elim::Function(rows), Elim::Function(row_type)])) =
+                    format_repr.try_global()
+                {
+                    if let Some(("Array", [Elim::Function(cols), Elim::Function(elem_type)])) =
+                        row_type.try_global()
+                    {
+                        let col_type = Value::global(
+                            "Array",
+                            vec![
+                                Elim::Function(share_value(rows)),
+                                Elim::Function(share_value(elem_type)),
+                            ],
+                        );
+                        return alloc_value(Value::global(
+                            "Array",
+                            vec![
+                                Elim::Function(share_value(cols)),
+                                Elim::Function(alloc_value(col_type)),
+                            ],
+                        ));
+                    }
+                }
+                elims.push(Elim::Repr);
+                head
+            }
+            // NOTE: Removing duplicates changes the length of the array, so the
+            // result is a list of the elements of the original array.
+            ("FormatArrayDedup", [Elim::Function(format)])
+            | ("FormatArrayDedupBy", [_, Elim::Function(format)]) => {
+                match repr(share_value(format)).try_global() {
+                    Some(("Array", [Elim::Function(_), Elim::Function(elem_type)]))
+                    | Some(("List", [Elim::Function(elem_type)])) => alloc_value(Value::global(
+                        "List",
+                        vec![Elim::Function(share_value(elem_type))],
+                    )),
+                    _ => {
+                        elims.push(Elim::Repr);
+                        head
+                    }
+                }
+            }
+            ("FormatArrayRemaining", [Elim::Function(elem_type)])
+            | ("FormatRepeatUntil", [Elim::Function(_), Elim::Function(elem_type)])
+            | ("FormatRepeatUntilInclusive", [Elim::Function(_), Elim::Function(elem_type)])
+            | ("FormatLengthPrefixed", [Elim::Function(_), Elim::Function(elem_type)]) => {
+                alloc_value(Value::global(
+                    "List",
+                    vec![Elim::Function(repr(share_value(elem_type)))],
+                ))
+            }
             ("CurrentPos", []) => {
-                Arc::new(Value::Stuck(Head::Global("Pos".to_owned()), Vec::new()))
+                alloc_value(Value::Stuck(Head::Global("Pos".to_owned()), Vec::new()))
             }
             ("Link", [Elim::Function(_), Elim::Function(_), Elim::Function(_)]) => {
-                Arc::new(Value::Stuck(Head::Global("Pos".to_owned()), Vec::new()))
+                alloc_value(Value::Stuck(Head::Global("Pos".to_owned()), Vec::new()))
+            }
+            ("FormatConst", [Elim::Function(_), Elim::Function(format)])
+            | ("FormatAssertSize", [Elim::Function(_), Elim::Function(format)]) => {
+                repr(share_value(format))
+            }
+            ("FormatAlign", [Elim::Function(_)]) => alloc_value(Value::global("Unit", Vec::new())),
+            ("FormatFixed", [Elim::Function(_), Elim::Function(_)]) => {
+                alloc_value(Value::global("F64", Vec::new()))
+            }
+            ("FormatNested", [Elim::Function(_), Elim::Function(_), Elim::Function(format)])
+            | ("FormatPadded", [Elim::Function(_), Elim::Function(_), Elim::Function(format)]) => {
+                repr(share_value(format))
+            }
+            (
+                "FormatMinVersion",
+                [Elim::Function(_), Elim::Function(_), Elim::Function(_), Elim::Function(format)],
+            ) => alloc_value(Value::global(
+                "Version",
+                vec![Elim::Function(repr(share_value(format)))],
+            )),
+            ("FormatPresentIf", [Elim::Function(_), Elim::Function(format)]) => alloc_value(
+                Value::global("Option", vec![Elim::Function(repr(share_value(format)))]),
+            ),
+            ("FormatBom", []) => alloc_value(Value::global("Int", Vec::new())),
+            ("FormatNever", []) => alloc_value(Value::global("Void", Vec::new())),
+            ("FormatUtf8", [Elim::Function(_)]) => alloc_value(Value::global("String", Vec::new())),
+            ("FormatAsciiLine", [])
+            | ("FormatCStr", [])
+            | ("FormatStrFixed", [Elim::Function(_), Elim::Function(_)]) => {
+                alloc_value(Value::global(
+                    "List",
+                    vec![Elim::Function(alloc_value(Value::global(
+                        "Int",
+                        Vec::new(),
+                    )))],
+                ))
             }
-            _ => Arc::new(Value::Error),
+            _ => alloc_value(Value::Error),
         },
         Value::Stuck(_, elims) => {
             elims.push(Elim::Repr);
             head
         }
-        _ => Arc::new(Value::Error),
+        _ => alloc_value(Value::Error),
+    }
+}
+
+/// The shape of a format, as determined by its head.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatClass {
+    /// A struct format, along with the name of the item that defines it.
+    Struct(String),
+    /// A format that reads a sequence of elements, along with the name of the
+    /// global that defines it.
+    Array(String),
+    /// A format that reads a single integer, float, boolean, or position, along
+    /// with the name of the global that defines it.
+    Scalar(String),
+    /// Some other global format, for example `FormatPresentIf`.
+    Other(String),
+    /// A format whose head is not yet known, for example a format parameter.
+    Unknown,
+}
+
+/// Classify a format by its head, without reading any data.
+///
+/// Formats are already in weak head normal form once they have been
+/// evaluated, so this only needs to inspect the head and its representation.
+pub fn classify_format(format: &Arc<Value>) -> FormatClass {
+    match format.as_ref() {
+        Value::Stuck(Head::Item(name), _) => FormatClass::Struct(name.clone()),
+        Value::Stuck(Head::Global(name), _) => match repr(format.clone()).try_global() {
+            Some(("Array", _)) | Some(("List", _)) => FormatClass::Array(name.clone()),
+            Some(("Int", [])) | Some(("F16", [])) | Some(("F32", [])) | Some(("F64", []))
+            | Some(("Bool", [])) | Some(("Pos", [])) => FormatClass::Scalar(name.clone()),
+            _ => FormatClass::Other(name.clone()),
+        },
+        _ => FormatClass::Unknown,
+    }
+}
+
+/// The longest array of index-dependent elements whose size will be computed
+/// by [`format_size`].
+const MAX_MAP_INDEX_SIZE_LEN: usize = 4096;
+
+/// Compute the number of bytes that a format will consume when read, returning
+/// `None` if this is not known until the format is read.
+pub fn format_size(
+    globals: &Globals,
+    items: &HashMap<String, Item>,
+    format: &Value,
+) -> Option<BigInt> {
+    match format {
+        Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
+            ("U8", []) | ("S8", []) => Some(BigInt::from(1)),
+            ("U16", [])
+            | ("U16Le", [])
+            | ("U16Be", [])
+            | ("S16", [])
+            | ("S16Le", [])
+            | ("S16Be", []) => Some(BigInt::from(2)),
+            ("U24Le", []) | ("U24Be", []) | ("S24Le", []) | ("S24Be", []) => Some(BigInt::from(3)),
+            ("U32", [])
+            | ("U32Le", [])
+            | ("U32Be", [])
+            | ("S32", [])
+            | ("S32Le", [])
+            | ("S32Be", []) => Some(BigInt::from(4)),
+            ("U64", [])
+            | ("U64Le", [])
+            | ("U64Be", [])
+            | ("S64", [])
+            | ("S64Le", [])
+            | ("S64Be", []) => Some(BigInt::from(8)),
+            ("F16Le", []) | ("F16Be", []) => Some(BigInt::from(2)),
+            ("F32Le", []) | ("F32Be", []) => Some(BigInt::from(4)),
+            ("F64Le", []) | ("F64Be", []) => Some(BigInt::from(8)),
+            ("FormatArray", [Elim::Function(len), Elim::Function(elem_type)]) => {
+                match len.as_ref() {
+                    Value::Primitive(Primitive::Int(len)) => {
+                        Some(len * format_size(globals, items, elem_type)?)
+                    }
+                    _ => None,
+                }
+            }
+            ("FormatArrayReverse", [Elim::Function(format)])
+            | ("FormatArraySorted", [Elim::Function(format)])
+            | ("FormatArraySortedBy", [_, Elim::Function(format)])
+            | ("FormatArrayUnique", [Elim::Function(format)])
+            | ("FormatArrayUniqueBy", [_, Elim::Function(format)])
+            | ("FormatArraySort", [Elim::Function(format)])
+            | ("FormatArraySortBy", [_, Elim::Function(format)])
+            | ("FormatArrayDedup", [Elim::Function(format)])
+            | ("FormatArrayDedupBy", [_, Elim::Function(format)])
+            | ("FormatArrayTranspose", [Elim::Function(format)])
+            | ("FormatArrayWindows", [_, Elim::Function(format)])
+            | ("FormatArrayIndexed", [Elim::Function(format)])
+            | ("FormatArraySplit", [_, Elim::Function(format)])
+            | ("FormatArrayTake", [_, Elim::Function(format)])
+            | ("FormatArrayDrop", [_, Elim::Function(format)])
+            | ("FormatArrayFold", [_, _, Elim::Function(format)])
+            | ("FormatReprCoerce", [Elim::Function(format)])
+            | ("FormatAtOffsets", [_, Elim::Function(format), _]) => {
+                format_size(globals, items, format)
+            }
+            ("FormatArrayZip", [Elim::Function(format0), Elim::Function(format1)])
+            | ("FormatArrayConcat", [Elim::Function(format0), Elim::Function(format1)]) => {
+                Some(format_size(globals, items, format0)? + format_size(globals, items, format1)?)
+            }
+            (
+                "FormatArrayChunked",
+                [Elim::Function(rows), Elim::Function(cols), Elim::Function(elem_type)],
+            ) => match (rows.as_ref(), cols.as_ref()) {
+                (
+                    Value::Primitive(Primitive::Int(rows)),
+                    Value::Primitive(Primitive::Int(cols)),
+                ) => Some(rows * cols * format_size(globals, items, elem_type)?),
+                _ => None,
+            },
+            ("FormatArrayMapIndex", [Elim::Function(len), Elim::Function(elem_type)]) => {
+                match len.as_ref() {
+                    Value::Primitive(Primitive::Int(len)) => {
+                        let len = len.to_usize()?;
+                        // The size of each element is computed separately, so
+                        // avoid doing so for arbitrarily long arrays.
+                        if len > MAX_MAP_INDEX_SIZE_LEN {
+                            return None;
+                        }
+                        let mut size = BigInt::from(0);
+                        for index in 0..len {
+                            let elem_type =
+                                function_elim(elem_type.clone(), Arc::new(Value::int(index)));
+                            size += format_size(globals, items, &elem_type)?;
+                        }
+                        Some(size)
+                    }
+                    _ => None,
+                }
+            }
+            ("CurrentPos", []) => Some(BigInt::from(0)),
+            ("Link", [Elim::Function(_), Elim::Function(_), Elim::Function(_)]) => {
+                Some(BigInt::from(0))
+            }
+            ("FormatConst", [Elim::Function(_), Elim::Function(format)])
+            | ("FormatFixed", [Elim::Function(_), Elim::Function(format)]) => {
+                format_size(globals, items, format)
+            }
+            ("FormatAssertSize", [Elim::Function(len), Elim::Function(_)])
+            | ("FormatNested", [Elim::Function(len), Elim::Function(_), Elim::Function(_)])
+            | ("FormatPadded", [Elim::Function(len), Elim::Function(_), Elim::Function(_)])
+            | ("FormatStrFixed", [Elim::Function(len), Elim::Function(_)])
+            | ("FormatUtf8", [Elim::Function(len)]) => match len.as_ref() {
+                Value::Primitive(Primitive::Int(len)) => Some(len.clone()),
+                _ => None,
+            },
+            (
+                "FormatMinVersion",
+                [Elim::Function(_), Elim::Function(_), Elim::Function(_), Elim::Function(format)],
+            ) => Some(3 * format_size(globals, items, format)?),
+            ("FormatPresentIf", [Elim::Function(is_present), Elim::Function(format)]) => {
+                match is_present.try_global() {
+                    Some(("true", [])) => format_size(globals, items, format),
+                    Some(("false", [])) => Some(BigInt::from(0)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        Value::Stuck(Head::Item(name), elims) => match items.get(name).map(|item| &item.data) {
+            Some(ItemData::StructFormat(arity, field_declarations)) => {
+                let mut locals = Locals::new();
+                for elim in elims.get(..*arity)? {
+                    match elim {
+                        Elim::Function(argument) => locals.push(argument.clone()),
+                        _ => return None,
+                    }
+                }
+
+                let mut size = BigInt::from(0);
+                for field_declaration in field_declarations.iter() {
+                    let format = eval(globals, items, &mut locals, &field_declaration.type_);
+                    size += format_size(globals, items, &format)?;
+                    // The values of the fields are not known until they are
+                    // read, so subsequent fields can only refer to them abstractly.
+                    locals.push(Arc::new(Value::local(
+                        locals.size().next_level(),
+                        Vec::new(),
+                    )));
+                }
+
+                Some(size)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Compute a canonical default value for the representation of a format.
+///
+/// Integers default to `0`, floats to `0.0`, booleans to `false`, positions to
+/// the start of the binary stream, strings to the empty string, lists to the
+/// empty list, and options to `none`. Arrays and structs are filled with the
+/// defaults of their elements and fields. Returns `None` if the type has no
+/// known default.
+pub fn repr_default(
+    globals: &Globals,
+    items: &HashMap<String, Item>,
+    r#type: &Value,
+) -> Option<Arc<Value>> {
+    match r#type {
+        Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
+            ("Int", []) => Some(Arc::new(Value::int(0))),
+            ("F16", []) => Some(Arc::new(Value::f16(0))),
+            ("F32", []) => Some(Arc::new(Value::f32(0.0))),
+            ("F64", []) => Some(Arc::new(Value::f64(0.0))),
+            ("Bool", []) => Some(Arc::new(Value::global("false", Vec::new()))),
+            ("Unit", []) => Some(Arc::new(Value::global("unit", Vec::new()))),
+            ("Pos", []) => Some(Arc::new(Value::pos(0))),
+            ("Array", [Elim::Function(len), Elim::Function(elem_type)]) => match len.as_ref() {
+                Value::Primitive(Primitive::Int(len)) => {
+                    let len = len.to_usize()?;
+                    let elem_value = repr_default(globals, items, elem_type)?;
+                    Some(Arc::new(Value::ArrayTerm(vec![elem_value; len])))
+                }
+                _ => None,
+            },
+            ("List", [Elim::Function(_)]) => Some(Arc::new(Value::ArrayTerm(Vec::new()))),
+            ("Option", [Elim::Function(_)]) => Some(Arc::new(Value::global("none", Vec::new()))),
+            ("String", []) => Some(Arc::new(Value::Primitive(Primitive::String(String::new())))),
+            // Built-in struct types, like `Pair`.
+            _ => struct_default(globals, items, global_field_declarations(globals, r#type)?),
+        },
+        Value::Stuck(Head::Item(name), elims) => {
+            let item_data = &items.get(name)?.data;
+            match (item_data, elims.last()) {
+                (ItemData::StructType(_, _), Some(Elim::Function(_)))
+                | (ItemData::StructType(_, _), None)
+                | (ItemData::StructFormat(_, _), Some(Elim::Repr)) => {}
+                (_, _) => return None,
+            }
+
+            struct_default(globals, items, item_data.try_field_declarations(elims)?)
+        }
+        _ => None,
+    }
+}
+
+/// Compute a default value for a struct, from the defaults of its fields.
+fn struct_default(
+    globals: &Globals,
+    items: &HashMap<String, Item>,
+    field_declarations: FieldDeclarations,
+) -> Option<Arc<Value>> {
+    let mut field_values = BTreeMap::new();
+    let mut is_complete = true;
+    field_declarations.for_each_field(globals, items, |label, r#type| {
+        match r#type.and_then(|r#type| repr_default(globals, items, &r#type)) {
+            Some(value) => {
+                field_values.insert(label.data.clone(), value.clone());
+                value
+            }
+            None => {
+                is_complete = false;
+                Arc::new(Value::Error)
+            }
+        }
+    });
+
+    match is_complete {
+        true => Some(Arc::new(Value::StructTerm(field_values))),
+        false => None,
     }
 }
 
@@ -124,8 +124,10 @@ impl<'globals> Context<'globals> {
     /// Force a value to resolve to some field declarations, returning `None`
     /// if the value did not refer to a valid struct type or struct format.
     fn force_field_declarations(&self, value: &Value) -> Option<semantics::FieldDeclarations> {
-        let (_, item, elims) = self.force_item(value)?;
-        item.try_field_declarations(&elims)
+        match self.force_item(value) {
+            Some((_, item, elims)) => item.try_field_declarations(elims),
+            None => semantics::global_field_declarations(self.globals, value),
+        }
     }
 
     /// Evaluate a [`core::Term`] into a [`Value`] in the current typing context.
@@ -430,6 +432,11 @@ impl<'globals> Context<'globals> {
                         }
                     }
                 }
+                Some(("List", [Elim::Function(elem_type)])) => {
+                    for elem_term in elem_terms {
+                        self.check_type(elem_term, elem_type);
+                    }
+                }
                 Some(_) | None => {
                     self.push_message(CoreTypingMessage::UnexpectedArrayTerm {
                         term_location: term.location,
@@ -593,9 +600,11 @@ impl<'globals> Context<'globals> {
 
             TermData::Primitive(primitive) => match primitive {
                 Primitive::Int(_) => Arc::new(Value::global("Int", Vec::new())),
+                Primitive::F16(_) => Arc::new(Value::global("F16", Vec::new())),
                 Primitive::F32(_) => Arc::new(Value::global("F32", Vec::new())),
                 Primitive::F64(_) => Arc::new(Value::global("F64", Vec::new())),
                 Primitive::Pos(_) => Arc::new(Value::global("Pos", Vec::new())),
+                Primitive::String(_) => Arc::new(Value::global("String", Vec::new())),
             },
             TermData::BoolElim(head, if_true, if_false) => {
                 let bool_type = Arc::new(Value::global("Bool", Vec::new()));
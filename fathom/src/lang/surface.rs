@@ -2,6 +2,7 @@
 
 use std::sync::Arc;
 
+use crate::lang::core::binary::Endianness;
 use crate::lang::{FileId, Located};
 use crate::reporting::Message;
 
@@ -75,6 +76,8 @@ pub struct Constant {
 pub struct StructType {
     /// Doc comment.
     pub doc: Arc<[String]>,
+    /// Attributes attached to this definition.
+    pub attributes: Vec<Attribute>,
     /// Name of this definition.
     pub name: Located<String>,
     /// Parameter telescope.
@@ -86,6 +89,19 @@ pub struct StructType {
     pub fields: Vec<FieldDeclaration>,
 }
 
+/// An attribute attached to an item.
+///
+/// ```text
+/// #[<name> = <value>]
+/// ```
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    /// Name of the attribute.
+    pub name: Located<String>,
+    /// Value of the attribute.
+    pub value: Term,
+}
+
 /// Patterns in the surface language.
 pub type Pattern = Located<PatternData>;
 
@@ -96,6 +112,8 @@ pub enum PatternData {
     Name(String),
     /// Numeric literals.
     NumberLiteral(String),
+    /// Tuple patterns, for matching against multiple scrutinees.
+    Tuple(Vec<Pattern>),
 }
 
 /// Terms in the surface language.
@@ -129,10 +147,12 @@ pub enum TermData {
 
     /// Numeric literals.
     NumberLiteral(String),
+    /// String literals.
+    StringLiteral(String),
     /// If-else expressions.
     If(Box<Term>, Box<Term>, Box<Term>),
-    /// Match expressions.
-    Match(Box<Term>, Vec<(Pattern, Term)>),
+    /// Match expressions, on one or more scrutinees.
+    Match(Vec<Term>, Vec<(Pattern, Term)>),
 
     /// Type of format descriptions.
     FormatType,
@@ -148,9 +168,15 @@ pub enum TermData {
 #[derive(Debug, Clone)]
 pub struct FieldDeclaration {
     pub doc: Arc<[String]>,
+    /// Whether the fields of the field's struct format should be spliced into
+    /// the enclosing struct format.
+    pub flatten: bool,
     pub label: Located<String>,
     // FIXME: can't use `r#type` in LALRPOP grammars
     pub type_: Term,
+    /// The byte order of the innermost `big_endian` or `little_endian` scope
+    /// that the field was declared in.
+    pub endianness: Option<Endianness>,
 }
 
 /// A field in a struct term.
@@ -21,6 +21,8 @@ pub enum Token<'source> {
     #[regex(r"[-+]?[0-9][a-zA-Z0-9_\.]*")]
     NumericLiteral(&'source str),
 
+    #[token("big_endian")]
+    BigEndian,
     #[token("bool_elim")]
     BoolElim,
     #[token("const")]
@@ -31,6 +33,8 @@ pub enum Token<'source> {
     F32,
     #[token("f64")]
     F64,
+    #[token("flatten")]
+    Flatten,
     #[token("Format")]
     Format,
     #[token("global")]
@@ -45,6 +49,8 @@ pub enum Token<'source> {
     Item,
     #[token("Kind")]
     Kind,
+    #[token("little_endian")]
+    LittleEndian,
     #[token("match")]
     Match,
     #[token("repr")]
@@ -79,6 +85,8 @@ pub enum Token<'source> {
     EqualsGreater,
     #[token(".")]
     FullStop,
+    #[token("#")]
+    Hash,
     #[token("->")]
     HyphenGreater,
     #[token(";")]
@@ -101,11 +109,13 @@ impl<'source> fmt::Display for Token<'source> {
             Token::StringLiteral(source) => write!(f, "{}", source),
             Token::NumericLiteral(source) => write!(f, "{}", source),
 
+            Token::BigEndian => write!(f, "big_endian"),
             Token::BoolElim => write!(f, "bool_elim"),
             Token::Const => write!(f, "const"),
             Token::Else => write!(f, "else"),
             Token::F32 => write!(f, "f32"),
             Token::F64 => write!(f, "f64"),
+            Token::Flatten => write!(f, "flatten"),
             Token::Format => write!(f, "Format"),
             Token::Global => write!(f, "global"),
             Token::If => write!(f, "if"),
@@ -113,6 +123,7 @@ impl<'source> fmt::Display for Token<'source> {
             Token::IntElim => write!(f, "int_elim"),
             Token::Item => write!(f, "item"),
             Token::Kind => write!(f, "Kind"),
+            Token::LittleEndian => write!(f, "little_endian"),
             Token::Match => write!(f, "match"),
             Token::Repr => write!(f, "repr"),
             Token::Struct => write!(f, "struct"),
@@ -131,6 +142,7 @@ impl<'source> fmt::Display for Token<'source> {
             Token::Equals => write!(f, "="),
             Token::EqualsGreater => write!(f, "=>"),
             Token::FullStop => write!(f, "."),
+            Token::Hash => write!(f, "#"),
             Token::HyphenGreater => write!(f, "->"),
             Token::Semi => write!(f, ";"),
 
@@ -218,24 +218,23 @@ impl<'source, 'messages> State<'source, 'messages> {
     /// - `Some(_)`: If the literal was parsed correctly.
     /// - `None`: If a fatal error when parsing the literal.
     pub fn number_to_float<T: Float + From<u8>>(mut self) -> Option<T> {
-        // NOTE: This could probably be improved a great deal.
-        // It might be worth looking at `lexical-core` crate as an alternative
-        // to implementing our own parser: https://github.com/Alexhuszagh/rust-lexical/
+        // NOTE: The literal is validated here, but the digits are converted
+        // using the standard library, which rounds correctly. Accumulating the
+        // value digit-by-digit would lose precision, meaning that printed
+        // floats would not always parse back to the same value.
 
         let mut lexer = NumericLiteral::lexer(self.source.as_bytes());
 
-        let add_digit = |sign, base: Base, float: T, digit: u8| match sign {
-            Sign::Positive => float * base.to_u8().into() + digit.into(),
-            Sign::Negative => float * base.to_u8().into() - digit.into(),
-        };
-
         let (sign, base, start_digit) = self.expect_numeric_literal_start(&mut lexer)?;
 
-        let mut float = T::zero();
+        let mut digits = String::new();
         let mut num_integer_digits = 0;
 
+        if sign == Sign::Negative {
+            digits.push('-');
+        }
         if let Some(digit) = start_digit {
-            float = add_digit(sign, base, float, digit);
+            digits.push(char::from(b'0' + digit));
             num_integer_digits += 1;
         }
 
@@ -248,7 +247,7 @@ impl<'source, 'messages> State<'source, 'messages> {
                 let location = self.token_location(&lexer);
                 match token {
                     Digit10::Digit(digit) if digit < base.to_u8() => {
-                        float = add_digit(sign, base, float, digit);
+                        digits.push(char::from(b'0' + digit));
                         num_integer_digits += 1;
                     }
                     Digit10::Separator if num_integer_digits != 0 => {}
@@ -276,14 +275,14 @@ impl<'source, 'messages> State<'source, 'messages> {
             }
 
             if has_fractional {
-                let mut frac = T::zero();
                 let mut num_frac_digits = 0;
+                digits.push('.');
 
                 while let Some(token) = lexer.next() {
                     let location = self.token_location(&lexer);
                     match token {
                         Digit10::Digit(digit) if digit < base.to_u8() => {
-                            frac = add_digit(sign, base, frac, digit);
+                            digits.push(char::from(b'0' + digit));
                             num_frac_digits += 1;
                         }
                         Digit10::Separator if num_frac_digits != 0 => {}
@@ -308,8 +307,6 @@ impl<'source, 'messages> State<'source, 'messages> {
                 if num_frac_digits == 0 {
                     return self.report(ExpectedDigit(self.token_location(&lexer), base));
                 }
-
-                float = float + frac / T::powi(base.to_u8().into(), num_frac_digits);
             }
 
             if has_exponent {
@@ -317,12 +314,84 @@ impl<'source, 'messages> State<'source, 'messages> {
                 return self.report(FloatLiteralExponentNotSupported(location));
             }
 
-            Some(float)
+            // The digits have already been validated, so this should not fail.
+            T::from_str_radix(&digits, 10).ok()
         } else {
             self.report(UnsupportedFloatLiteralBase(self.location, base))
         }
     }
 
+    /// Parse a quoted string literal into a string, decoding any escape
+    /// sequences that it contains.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(_)`: If the literal was parsed correctly.
+    /// - `None`: If a fatal error when parsing the literal.
+    pub fn string_to_string(mut self) -> Option<String> {
+        let contents = match self.source.strip_prefix('"') {
+            Some(contents) => contents.strip_suffix('"').unwrap_or(contents),
+            None => return self.report(ExpectedStartOfStringLiteral(self.location)),
+        };
+
+        let mut string = String::with_capacity(contents.len());
+        let mut chars = contents.char_indices();
+
+        while let Some((start, ch)) = chars.next() {
+            if ch != '\\' {
+                string.push(ch);
+                continue;
+            }
+
+            let escaped = match chars.next() {
+                Some((_, '\\')) => Some('\\'),
+                Some((_, '"')) => Some('"'),
+                Some((_, '\'')) => Some('\''),
+                Some((_, 'n')) => Some('\n'),
+                Some((_, 'r')) => Some('\r'),
+                Some((_, 't')) => Some('\t'),
+                Some((_, '0')) => Some('\0'),
+                Some((_, 'u')) => {
+                    let rest = &contents[start + 2..];
+                    let hex = rest
+                        .strip_prefix('{')
+                        .and_then(|rest| rest.split('}').next());
+                    match hex {
+                        Some(hex) if rest.len() > hex.len() + 1 => {
+                            chars.nth(hex.len() + 1);
+                            (u32::from_str_radix(hex, 16).ok()).and_then(char::from_u32)
+                        }
+                        _ => None,
+                    }
+                }
+                Some(_) | None => None,
+            };
+
+            match escaped {
+                Some(ch) => string.push(ch),
+                None => {
+                    let end = chars.clone().next().map_or(contents.len(), |(end, _)| end);
+                    // Offset by one to account for the opening quote.
+                    let location = self.range_location((start + 1)..(end + 1));
+                    return self.report(UnknownEscapeSequence(location));
+                }
+            }
+        }
+
+        Some(string)
+    }
+
+    /// Get the file-relative location of a range of the source string.
+    fn range_location(&self, range: std::ops::Range<usize>) -> Location {
+        match self.location {
+            Location::Generated => Location::Generated,
+            Location::FileRange(file_id, location_range) => Location::file_range(
+                file_id,
+                (location_range.start + range.start)..(location_range.start + range.end),
+            ),
+        }
+    }
+
     fn expect_numeric_literal_start(
         &mut self,
         lexer: &mut logos::Lexer<'source, NumericLiteral>,
@@ -4,5 +4,7 @@ pub mod surface_to_core;
 pub mod surface_to_doc;
 pub mod surface_to_pretty;
 
+pub mod core_to_dot;
 pub mod core_to_pretty;
 pub mod core_to_surface;
+pub mod core_to_typescript;
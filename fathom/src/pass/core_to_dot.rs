@@ -0,0 +1,130 @@
+//! Renders the item dependency graph of the core language as [Graphviz DOT].
+//!
+//! [Graphviz DOT]: https://graphviz.org/doc/info/lang.html
+
+use std::collections::BTreeSet;
+use std::io;
+use std::io::prelude::*;
+
+use crate::lang::core::{FieldDeclaration, Item, ItemData, Module, Term, TermData};
+
+/// Write the item dependency graph of a module.
+///
+/// Each item in the module is rendered as a node, with an edge from an item
+/// to every other item that it refers to.
+pub fn from_module(writer: &mut impl Write, module: &Module) -> io::Result<()> {
+    writeln!(writer, "digraph {{")?;
+
+    for item in &module.items {
+        let (name, kind) = item_name_kind(item);
+        writeln!(
+            writer,
+            "    {name} [label={name}, tooltip=\"{kind}\"];",
+            name = quote(name),
+            kind = kind,
+        )?;
+    }
+
+    for item in &module.items {
+        let (name, _) = item_name_kind(item);
+        for dependency in item_dependencies(item) {
+            writeln!(writer, "    {} -> {};", quote(name), quote(dependency))?;
+        }
+    }
+
+    writeln!(writer, "}}")?;
+
+    Ok(())
+}
+
+/// Returns the names of the items referred to by an item, in sorted order.
+pub fn item_dependencies(item: &Item) -> BTreeSet<&str> {
+    let mut dependencies = BTreeSet::new();
+
+    match &item.data {
+        ItemData::Constant(constant) => term_dependencies(&constant.term, &mut dependencies),
+        ItemData::StructType(struct_type) => {
+            for (_, r#type) in &struct_type.params {
+                term_dependencies(r#type, &mut dependencies);
+            }
+            fields_dependencies(&struct_type.fields, &mut dependencies);
+        }
+        ItemData::StructFormat(struct_format) => {
+            for (_, r#type) in &struct_format.params {
+                term_dependencies(r#type, &mut dependencies);
+            }
+            fields_dependencies(&struct_format.fields, &mut dependencies);
+        }
+    }
+
+    dependencies
+}
+
+fn item_name_kind(item: &Item) -> (&str, &'static str) {
+    match &item.data {
+        ItemData::Constant(constant) => (&constant.name, "const"),
+        ItemData::StructType(struct_type) => (&struct_type.name, "struct"),
+        ItemData::StructFormat(struct_format) => (&struct_format.name, "struct format"),
+    }
+}
+
+fn fields_dependencies<'a>(fields: &'a [FieldDeclaration], dependencies: &mut BTreeSet<&'a str>) {
+    for field in fields {
+        term_dependencies(&field.type_, dependencies);
+    }
+}
+
+fn term_dependencies<'a>(term: &'a Term, dependencies: &mut BTreeSet<&'a str>) {
+    match &term.data {
+        TermData::Item(name) => {
+            dependencies.insert(name);
+        }
+        TermData::Global(_)
+        | TermData::Local(_)
+        | TermData::Sort(_)
+        | TermData::Primitive(_)
+        | TermData::FormatType
+        | TermData::Repr
+        | TermData::Error => {}
+
+        TermData::Ann(term, r#type) => {
+            term_dependencies(term, dependencies);
+            term_dependencies(r#type, dependencies);
+        }
+        TermData::FunctionType(param_type, body_type) => {
+            term_dependencies(param_type, dependencies);
+            term_dependencies(body_type, dependencies);
+        }
+        TermData::FunctionElim(head, argument) => {
+            term_dependencies(head, dependencies);
+            term_dependencies(argument, dependencies);
+        }
+        TermData::StructTerm(field_definitions) => {
+            for field_definition in field_definitions {
+                term_dependencies(&field_definition.term, dependencies);
+            }
+        }
+        TermData::StructElim(head, _) => term_dependencies(head, dependencies),
+        TermData::ArrayTerm(elem_terms) => {
+            for elem_term in elem_terms {
+                term_dependencies(elem_term, dependencies);
+            }
+        }
+        TermData::BoolElim(head, if_true, if_false) => {
+            term_dependencies(head, dependencies);
+            term_dependencies(if_true, dependencies);
+            term_dependencies(if_false, dependencies);
+        }
+        TermData::IntElim(head, branches, default) => {
+            term_dependencies(head, dependencies);
+            for branch in branches.values() {
+                term_dependencies(branch, dependencies);
+            }
+            term_dependencies(default, dependencies);
+        }
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -1,3 +1,4 @@
+use crate::ieee754;
 use crate::lang::core::{
     Constant, FieldDeclaration, FieldDefinition, Item, ItemData, Module, Primitive, Sort,
     StructFormat, StructType, Term, TermData,
@@ -274,11 +275,23 @@ where
         }
     }
 
+    // Non-finite floats can only be referred to by name.
+    if let Some(name) = primitive.float_global_name() {
+        return (alloc.nil())
+            .append("global")
+            .append(alloc.space())
+            .append(name);
+    }
+
     match primitive {
         Primitive::Int(value) => (alloc.nil())
             .append("int")
             .append(alloc.space())
             .append(alloc.as_string(value)),
+        Primitive::F16(bits) => (alloc.nil())
+            .append("f16")
+            .append(alloc.space())
+            .append(format_float(ieee754::f16_to_f32(*bits))),
         Primitive::F32(value) => (alloc.nil())
             .append("f32")
             .append(alloc.space())
@@ -291,6 +304,10 @@ where
             .append("pos")
             .append(alloc.space())
             .append(format!("{:#x}", value)),
+        Primitive::String(value) => (alloc.nil())
+            .append("string")
+            .append(alloc.space())
+            .append(format!("{:?}", value)),
     }
 }
 
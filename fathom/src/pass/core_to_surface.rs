@@ -4,6 +4,7 @@
 //! conjunction with [`crate::pass::surface_to_pretty`] to render core terms to
 //! the user.
 
+use crate::ieee754;
 use crate::lang::core::{
     Item, ItemData, LocalIndex, LocalSize, Locals, Module, Primitive, Sort, Term, TermData,
 };
@@ -94,8 +95,10 @@ impl Context {
                     self.push_local(field_declaration.label.data.clone());
                     field_declarations.push(surface::FieldDeclaration {
                         doc: field_declaration.doc.clone(),
+                        flatten: false,
                         label: field_declaration.label.clone(),
                         type_: r#type,
+                        endianness: None,
                     });
                 }
 
@@ -104,6 +107,7 @@ impl Context {
 
                 surface::ItemData::StructType(surface::StructType {
                     doc: struct_type.doc.clone(),
+                    attributes: Vec::new(),
                     name: Located::generated(struct_type.name.clone()),
                     params,
                     type_: Some(surface::Term::generated(surface::TermData::TypeType)),
@@ -128,8 +132,10 @@ impl Context {
                     self.push_local(field_declaration.label.data.clone());
                     field_declarations.push(surface::FieldDeclaration {
                         doc: field_declaration.doc.clone(),
+                        flatten: false,
                         label: field_declaration.label.clone(),
                         type_: r#type,
+                        endianness: None,
                     });
                 }
 
@@ -138,6 +144,7 @@ impl Context {
 
                 surface::ItemData::StructType(surface::StructType {
                     doc: struct_format.doc.clone(),
+                    attributes: Vec::new(),
                     name: Located::generated(struct_format.name.clone()),
                     params,
                     type_: Some(surface::Term::generated(surface::TermData::FormatType)),
@@ -195,11 +202,22 @@ impl Context {
                     .collect(),
             ),
 
-            TermData::Primitive(primitive) => match primitive {
-                Primitive::Int(value) => surface::TermData::NumberLiteral(value.to_string()),
-                Primitive::F32(value) => surface::TermData::NumberLiteral(value.to_string()),
-                Primitive::F64(value) => surface::TermData::NumberLiteral(value.to_string()),
-                Primitive::Pos(_) => surface::TermData::Error, // TODO: Warning?
+            // Non-finite floats can only be referred to by name.
+            TermData::Primitive(primitive) => match primitive.float_global_name() {
+                Some(name) => surface::TermData::Name(name.to_owned()),
+                None => match primitive {
+                    Primitive::Int(value) => surface::TermData::NumberLiteral(value.to_string()),
+                    Primitive::F16(bits) => {
+                        let value = ieee754::f16_to_f32(*bits);
+                        surface::TermData::NumberLiteral(value.to_string())
+                    }
+                    Primitive::F32(value) => surface::TermData::NumberLiteral(value.to_string()),
+                    Primitive::F64(value) => surface::TermData::NumberLiteral(value.to_string()),
+                    Primitive::Pos(_) => surface::TermData::Error, // TODO: Warning?
+                    Primitive::String(value) => {
+                        surface::TermData::StringLiteral(format!("{:?}", value))
+                    }
+                },
             },
             TermData::BoolElim(head, if_true, if_false) => surface::TermData::If(
                 Box::new(self.from_term(head)),
@@ -210,7 +228,7 @@ impl Context {
                 let default = self.from_term(default);
 
                 surface::TermData::Match(
-                    Box::new(self.from_term(head)),
+                    vec![self.from_term(head)],
                     branches
                         .iter()
                         .map(|(value, term)| {
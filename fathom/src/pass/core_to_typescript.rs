@@ -0,0 +1,372 @@
+//! Emits [TypeScript] type definitions for the host representations of the
+//! items in a core module.
+//!
+//! Struct types and struct formats are emitted as interfaces, and annotated
+//! constants that define types or formats are emitted as type aliases.
+//!
+//! [TypeScript]: https://www.typescriptlang.org/
+
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::io;
+use std::io::prelude::*;
+use std::sync::Arc;
+
+use crate::lang::core::semantics::{self, Elim, Head, Value};
+use crate::lang::core::{
+    FieldDeclaration, Globals, ItemData, Locals, Module, Primitive, Sort, Term, TermData,
+};
+use crate::lang::Located;
+
+/// Write TypeScript type definitions for a module.
+pub fn from_module(writer: &mut impl Write, globals: &Globals, module: &Module) -> io::Result<()> {
+    let mut context = Context::new(globals);
+
+    for line in module.doc.iter() {
+        writeln!(writer, "//{}", line)?;
+    }
+
+    for item in &module.items {
+        match &item.data {
+            ItemData::Constant(constant) => {
+                let value = context.eval(&constant.term);
+                let r#type = match &constant.term.data {
+                    TermData::Ann(_, r#type) => match &r#type.data {
+                        TermData::Sort(Sort::Type) => Some(context.repr_type(&value)),
+                        TermData::FormatType => Some(context.format_type(&value)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(r#type) = r#type {
+                    writeln!(writer)?;
+                    write_doc(writer, "", &constant.doc)?;
+                    writeln!(writer, "export type {} = {};", constant.name, r#type)?;
+                }
+                context.items.insert(
+                    constant.name.clone(),
+                    semantics::Item::new(item.location, semantics::ItemData::Constant(value)),
+                );
+            }
+            ItemData::StructType(struct_type) => {
+                writeln!(writer)?;
+                write_doc(writer, "", &struct_type.doc)?;
+                context.write_interface(
+                    writer,
+                    &struct_type.name,
+                    &struct_type.params,
+                    &struct_type.fields,
+                    false,
+                )?;
+                context.items.insert(
+                    struct_type.name.clone(),
+                    semantics::Item::new(
+                        item.location,
+                        semantics::ItemData::StructType(
+                            struct_type.params.len(),
+                            struct_type.fields.clone(),
+                        ),
+                    ),
+                );
+            }
+            ItemData::StructFormat(struct_format) => {
+                writeln!(writer)?;
+                write_doc(writer, "", &struct_format.doc)?;
+                context.write_interface(
+                    writer,
+                    &struct_format.name,
+                    &struct_format.params,
+                    &struct_format.fields,
+                    true,
+                )?;
+                context.items.insert(
+                    struct_format.name.clone(),
+                    semantics::Item::new(
+                        item.location,
+                        semantics::ItemData::StructFormat(
+                            struct_format.params.len(),
+                            struct_format.fields.clone(),
+                        ),
+                    ),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+struct Context<'globals> {
+    globals: &'globals Globals,
+    items: HashMap<String, semantics::Item>,
+    /// The parameters of each item that are emitted as type parameters, along
+    /// with whether they are formats (`Some(true)`) or types (`Some(false)`).
+    item_type_params: HashMap<String, Vec<Option<bool>>>,
+    locals: Locals<Arc<Value>>,
+    /// The names of the local type parameters, indexed by level.
+    local_names: Vec<Option<String>>,
+}
+
+impl<'globals> Context<'globals> {
+    fn new(globals: &'globals Globals) -> Context<'globals> {
+        Context {
+            globals,
+            items: HashMap::new(),
+            item_type_params: HashMap::new(),
+            locals: Locals::new(),
+            local_names: Vec::new(),
+        }
+    }
+
+    fn eval(&mut self, term: &Term) -> Arc<Value> {
+        semantics::eval(self.globals, &self.items, &mut self.locals, term)
+    }
+
+    /// Push a stuck local, along with the name of the type parameter that it
+    /// corresponds to.
+    fn push_local(&mut self, name: Option<String>) {
+        let level = self.locals.size().next_level();
+        self.locals.push(Arc::new(Value::local(level, Vec::new())));
+        self.local_names.push(name);
+    }
+
+    fn truncate_locals(&mut self, len: usize) {
+        while self.local_names.len() > len {
+            self.locals.pop();
+            self.local_names.pop();
+        }
+    }
+
+    fn write_interface(
+        &mut self,
+        writer: &mut impl Write,
+        name: &str,
+        params: &[(Located<String>, Arc<Term>)],
+        fields: &[FieldDeclaration],
+        is_format: bool,
+    ) -> io::Result<()> {
+        let mut type_params = Vec::new();
+        let mut param_kinds = Vec::with_capacity(params.len());
+        for (param_name, param_type) in params {
+            let param_kind = match param_type.data {
+                TermData::FormatType => Some(true),
+                TermData::Sort(Sort::Type) => Some(false),
+                _ => None,
+            };
+            let param_name = param_kind.map(|_| param_name.data.clone());
+            type_params.extend(param_name.clone());
+            param_kinds.push(param_kind);
+            self.push_local(param_name);
+        }
+        self.item_type_params.insert(name.to_owned(), param_kinds);
+
+        match type_params.as_slice() {
+            [] => writeln!(writer, "export interface {} {{", name)?,
+            _ => writeln!(
+                writer,
+                "export interface {}<{}> {{",
+                name,
+                type_params.join(", "),
+            )?,
+        }
+        for field in fields {
+            let r#type = self.eval(&field.type_);
+            let r#type = match is_format {
+                true => self.format_type(&r#type),
+                false => self.repr_type(&r#type),
+            };
+            write_doc(writer, "    ", &field.doc)?;
+            writeln!(writer, "    {}: {};", field.label.data, r#type)?;
+            self.push_local(None);
+        }
+        writeln!(writer, "}}")?;
+
+        self.truncate_locals(0);
+
+        Ok(())
+    }
+
+    /// The TypeScript type of the host representation of a format.
+    ///
+    /// Integer formats that fit in a double precision float are represented
+    /// with `number`, and wider integer formats are represented with `bigint`.
+    fn format_type(&mut self, format: &Value) -> String {
+        // Every integer format is represented by `Int` on the host, so replace
+        // the narrower ones with a local named after their TypeScript type
+        // before computing the representation of the format.
+        let len = self.local_names.len();
+        let number_level = self.locals.size().next_level();
+        self.push_local(Some("number".to_owned()));
+        let number = Arc::new(Value::local(number_level, Vec::new()));
+
+        let format = replace_number_formats(format, &number);
+        let r#type = self.repr_type(&semantics::repr(format));
+
+        self.truncate_locals(len);
+
+        r#type
+    }
+
+    /// The TypeScript type of a host type.
+    fn repr_type(&mut self, r#type: &Value) -> String {
+        match r#type {
+            Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
+                ("Int", []) => "bigint".to_owned(),
+                ("F16", []) | ("F32", []) | ("F64", []) | ("Pos", []) => "number".to_owned(),
+                ("Bool", []) => "boolean".to_owned(),
+                ("String", []) => "string".to_owned(),
+                ("Void", []) => "never".to_owned(),
+                ("Unit", []) => "null".to_owned(),
+                ("Array", [Elim::Function(_), Elim::Function(elem_type)])
+                | ("List", [Elim::Function(elem_type)]) => array_type(self.repr_type(elem_type)),
+                ("Option", [Elim::Function(elem_type)]) => {
+                    format!("{} | null", self.repr_type(elem_type))
+                }
+                (name, elims) => match self.globals.get_struct_type(name) {
+                    Some((arity, fields)) if *arity == elims.len() => {
+                        let fields = fields.clone();
+                        self.struct_type(elims, &fields)
+                    }
+                    _ => "unknown".to_owned(),
+                },
+            },
+            Value::Stuck(Head::Item(name), elims) => {
+                let elims = match elims.split_last() {
+                    Some((Elim::Repr, elims)) => elims,
+                    _ => elims.as_slice(),
+                };
+                let param_kinds = self.item_type_params.get(name).cloned();
+                let mut type_args = Vec::new();
+                for (elim, param_kind) in Iterator::zip(elims.iter(), param_kinds.iter().flatten())
+                {
+                    match (elim, param_kind) {
+                        (Elim::Function(arg), Some(true)) => type_args.push(self.format_type(arg)),
+                        (Elim::Function(arg), Some(false)) => type_args.push(self.repr_type(arg)),
+                        (_, _) => {}
+                    }
+                }
+                match type_args.as_slice() {
+                    [] => name.clone(),
+                    _ => format!("{}<{}>", name, type_args.join(", ")),
+                }
+            }
+            Value::Stuck(Head::Local(level), elims) => match elims.as_slice() {
+                [] | [Elim::Repr] => (self.local_names.get(level.to_usize()).cloned())
+                    .flatten()
+                    .unwrap_or_else(|| "unknown".to_owned()),
+                _ => "unknown".to_owned(),
+            },
+            _ => "unknown".to_owned(),
+        }
+    }
+
+    /// An object type for one of the built-in struct types.
+    fn struct_type(&mut self, elims: &[Elim], fields: &[FieldDeclaration]) -> String {
+        let (locals, local_names) = (self.locals.clone(), self.local_names.clone());
+
+        for elim in elims {
+            if let Elim::Function(arg) = elim {
+                self.locals.push(arg.clone());
+                self.local_names.push(None);
+            }
+        }
+        let mut field_types = Vec::with_capacity(fields.len());
+        for field in fields {
+            let r#type = self.eval(&field.type_);
+            field_types.push(format!("{}: {}", field.label.data, self.repr_type(&r#type)));
+            self.push_local(None);
+        }
+
+        self.locals = locals;
+        self.local_names = local_names;
+
+        format!("{{ {} }}", field_types.join("; "))
+    }
+}
+
+/// Write a doc comment, if it is not empty.
+fn write_doc(writer: &mut impl Write, indent: &str, doc: &[String]) -> io::Result<()> {
+    if doc.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "{}/**", indent)?;
+    for line in doc {
+        writeln!(writer, "{} *{}", indent, line.trim_end())?;
+    }
+    writeln!(writer, "{} */", indent)
+}
+
+/// Replace the integer formats whose values fit in a TypeScript `number` with
+/// the given local, including those nested in the arguments of other formats.
+fn replace_number_formats(format: &Value, number: &Arc<Value>) -> Arc<Value> {
+    match format {
+        Value::Stuck(Head::Global(name), elims) => match (name.as_str(), elims.as_slice()) {
+            ("U8", [])
+            | ("U16", [])
+            | ("U16Le", [])
+            | ("U16Be", [])
+            | ("U24Le", [])
+            | ("U24Be", [])
+            | ("U32", [])
+            | ("U32Le", [])
+            | ("U32Be", [])
+            | ("S8", [])
+            | ("S16", [])
+            | ("S16Le", [])
+            | ("S16Be", [])
+            | ("S24Le", [])
+            | ("S24Be", [])
+            | ("S32", [])
+            | ("S32Le", [])
+            | ("S32Be", [])
+            | ("FormatVlq", [])
+            | ("FormatBom", []) => number.clone(),
+            // Integer formats are only left unevaluated when their endianness
+            // or width is not known, but the width alone is enough here.
+            ("FormatUInt", [Elim::Function(_), Elim::Function(width)])
+            | ("FormatSInt", [Elim::Function(_), Elim::Function(width)]) => match width.as_ref() {
+                Value::Primitive(Primitive::Int(width)) if *width <= BigInt::from(32) => {
+                    number.clone()
+                }
+                _ => Arc::new(format.clone()),
+            },
+            // The element formats are only known once they are applied to an
+            // index, and the first element is representative of the others.
+            ("FormatArrayMapIndex", [Elim::Function(len), Elim::Function(elem_type)]) => {
+                let first_elem_type =
+                    semantics::function_elim(elem_type.clone(), Arc::new(Value::int(0)));
+                let elims = vec![
+                    Elim::Function(len.clone()),
+                    Elim::Function(replace_number_formats(&first_elem_type, number)),
+                ];
+                Arc::new(Value::global("FormatArray", elims))
+            }
+            (_, elims) => Arc::new(Value::Stuck(
+                Head::Global(name.clone()),
+                replace_number_elims(elims, number),
+            )),
+        },
+        Value::Stuck(Head::Item(name), elims) => Arc::new(Value::Stuck(
+            Head::Item(name.clone()),
+            replace_number_elims(elims, number),
+        )),
+        _ => Arc::new(format.clone()),
+    }
+}
+
+fn replace_number_elims(elims: &[Elim], number: &Arc<Value>) -> Vec<Elim> {
+    (elims.iter())
+        .map(|elim| match elim {
+            Elim::Function(arg) => Elim::Function(replace_number_formats(arg, number)),
+            elim => elim.clone(),
+        })
+        .collect()
+}
+
+fn array_type(elem_type: String) -> String {
+    match elem_type.contains(' ') && !elem_type.starts_with('{') {
+        true => format!("({})[]", elem_type),
+        false => format!("{}[]", elem_type),
+    }
+}
@@ -14,10 +14,12 @@ use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::lang::core::semantics::{self, Elim, Value};
+use crate::ieee754;
+use crate::lang::core::binary::Endianness;
+use crate::lang::core::semantics::{self, Elim, Head, Value};
 use crate::lang::core::{self, Primitive, Sort};
 use crate::lang::surface::{ItemData, Module, Pattern, PatternData, StructType, Term, TermData};
-use crate::lang::Location;
+use crate::lang::{Located, Location};
 use crate::literal;
 use crate::pass::core_to_surface;
 use crate::reporting::{Message, SurfaceToCoreMessage};
@@ -38,6 +40,12 @@ pub struct Context<'globals> {
     core_to_surface: core_to_surface::Context,
     /// Diagnostic messages collected during elaboration.
     messages: Vec<Message>,
+    /// The representation types of the fields of the struct formats in the
+    /// most recently elaborated module, keyed by item name and field label.
+    field_repr_types: HashMap<String, HashMap<String, Term>>,
+    /// The byte order of the endianness scope of the field that is currently
+    /// being elaborated, used to resolve formats like `U16`.
+    ambient_endianness: Option<Endianness>,
 }
 
 impl<'globals> Context<'globals> {
@@ -51,6 +59,8 @@ impl<'globals> Context<'globals> {
             local_definitions: core::Locals::new(),
             core_to_surface: core_to_surface::Context::new(),
             messages: Vec::new(),
+            field_repr_types: HashMap::new(),
+            ambient_endianness: None,
         }
     }
 
@@ -87,7 +97,6 @@ impl<'globals> Context<'globals> {
     }
 
     /// Pop a local entry.
-    #[allow(dead_code)]
     fn pop_local(&mut self) {
         self.local_declarations.pop();
         self.local_definitions.pop();
@@ -101,6 +110,30 @@ impl<'globals> Context<'globals> {
         self.core_to_surface.truncate_locals(local_size);
     }
 
+    /// Find up to three names in scope that are similar to a name that could
+    /// not be found, ordered from the most to the least similar.
+    fn suggest_names(&self, name: &str) -> Vec<String> {
+        let max_distance = std::cmp::max(1, name.chars().count() / 3);
+
+        let local_names = self.local_declarations.iter().map(|(name, _)| name);
+        let item_names = self.item_declarations.keys();
+        let global_names = self.globals.entries().map(|(name, _)| name);
+
+        let mut suggestions = (local_names.chain(item_names).chain(global_names))
+            .filter_map(|candidate| {
+                let distance = edit_distance(name, candidate);
+                (distance <= max_distance).then(|| (distance, candidate.clone()))
+            })
+            .collect::<Vec<_>>();
+        suggestions.sort();
+        suggestions.dedup();
+
+        (suggestions.into_iter())
+            .map(|(_, candidate)| candidate)
+            .take(3)
+            .collect()
+    }
+
     /// Store a diagnostic message in the context for later reporting.
     fn push_message(&mut self, message: impl Into<Message>) {
         self.messages.push(message.into());
@@ -111,6 +144,12 @@ impl<'globals> Context<'globals> {
         self.messages.drain(..)
     }
 
+    /// The representation types of the fields of the struct formats in the
+    /// most recently elaborated module, keyed by item name and field label.
+    pub fn field_repr_types(&self) -> &HashMap<String, HashMap<String, Term>> {
+        &self.field_repr_types
+    }
+
     /// Force a value to resolve to an item, returning `None` if the value did
     /// not refer to an item.
     fn force_item<'context, 'value>(
@@ -127,8 +166,10 @@ impl<'globals> Context<'globals> {
     /// Force a value to resolve to some field declarations, returning `None`
     /// if the value did not refer to a valid struct type or struct format.
     fn force_field_declarations(&self, value: &Value) -> Option<semantics::FieldDeclarations> {
-        let (_, item, elims) = self.force_item(value)?;
-        item.try_field_declarations(&elims)
+        match self.force_item(value) {
+            Some((_, item, elims)) => item.try_field_declarations(elims),
+            None => semantics::global_field_declarations(self.globals, value),
+        }
     }
 
     /// Evaluate a [`core::Term`] into a [`Value`] in the current elaboration context.
@@ -166,6 +207,7 @@ impl<'globals> Context<'globals> {
     ///
     /// [`Value`]: crate::lang::core::semantics::Value
     /// [computationally equal]: https://ncatlab.org/nlab/show/equality#computational_equality
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn is_equal(&self, value0: &Value, value1: &Value) -> bool {
         semantics::is_equal(self.globals, &self.item_definitions, value0, value1)
     }
@@ -196,8 +238,10 @@ impl<'globals> Context<'globals> {
     #[debug_ensures(self.item_definitions.is_empty())]
     #[debug_ensures(self.local_declarations.is_empty())]
     #[debug_ensures(self.local_definitions.is_empty())]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn from_module(&mut self, surface_module: &Module) -> core::Module {
         let mut core_items = Vec::new();
+        self.field_repr_types.clear();
 
         for item in surface_module.items.iter() {
             use std::collections::hash_map::Entry;
@@ -277,6 +321,10 @@ impl<'globals> Context<'globals> {
                     core_items.push(core_item.clone());
                     self.item_declarations.insert(entry.key().clone(), r#type);
                     entry.insert(semantics::Item::new(item.location, item_data));
+
+                    if let ItemData::StructType(struct_type) = &item.data {
+                        self.check_struct_attributes(struct_type);
+                    }
                 }
                 Entry::Occupied(entry) => {
                     let original_location = entry.get().location;
@@ -298,6 +346,43 @@ impl<'globals> Context<'globals> {
         }
     }
 
+    /// Check the attributes of a struct definition that has been added to the
+    /// item definitions.
+    fn check_struct_attributes(&mut self, struct_type: &StructType) {
+        for attribute in &struct_type.attributes {
+            match attribute.name.data.as_str() {
+                "expected_size" => {
+                    let int_type = Arc::new(Value::global("Int", Vec::new()));
+                    let expected_size = self.check_type(&attribute.value, &int_type);
+                    let expected_size = match self.eval(&expected_size).as_ref() {
+                        Value::Primitive(Primitive::Int(expected_size)) => expected_size.clone(),
+                        _ => continue,
+                    };
+
+                    let format = Value::item(struct_type.name.data.clone(), Vec::new());
+                    let found_size =
+                        semantics::format_size(self.globals, &self.item_definitions, &format);
+
+                    if found_size.as_ref() != Some(&expected_size) {
+                        self.push_message(SurfaceToCoreMessage::ExpectedSizeMismatch {
+                            name: struct_type.name.data.clone(),
+                            attribute_location: Location::merge(
+                                attribute.name.location,
+                                attribute.value.location,
+                            ),
+                            expected_size,
+                            found_size,
+                        });
+                    }
+                }
+                _ => self.push_message(SurfaceToCoreMessage::UnknownAttribute {
+                    name: attribute.name.data.clone(),
+                    name_location: attribute.name.location,
+                }),
+            }
+        }
+    }
+
     fn is_struct_type(
         &mut self,
         struct_type: &StructType,
@@ -331,6 +416,9 @@ impl<'globals> Context<'globals> {
         for field in &struct_type.fields {
             let field_location = Location::merge(field.label.location, field.type_.location);
             let core_type = self.check_type(&field.type_, &type_type);
+            if field.flatten {
+                self.push_message(SurfaceToCoreMessage::InvalidFlattenedField { field_location });
+            }
 
             match seen_field_labels.entry(field.label.data.clone()) {
                 Entry::Vacant(entry) => {
@@ -407,16 +495,49 @@ impl<'globals> Context<'globals> {
         // Fields that have been elaborated into the core syntax.
         let mut core_field_declarations = Vec::with_capacity(struct_type.fields.len());
         let format_type = Arc::new(Value::FormatType);
+        // Representation types of the fields, for use in documentation.
+        let mut field_repr_types = HashMap::new();
 
         // Elaborate the field declarations
         for field in &struct_type.fields {
             let field_location = Location::merge(field.label.location, field.type_.location);
-            let core_type = self.check_type(&field.type_, &format_type);
+            self.ambient_endianness = field.endianness;
+            let core_type = match self.host_type_formats(&field.type_) {
+                Some(suggested_formats) => {
+                    self.push_message(SurfaceToCoreMessage::ExpectedFormatFoundType {
+                        term_location: field.type_.location,
+                        found_type: field.type_.clone(),
+                        suggested_formats: suggested_formats
+                            .iter()
+                            .map(|&name| name.to_owned())
+                            .collect(),
+                    });
+                    core::Term::new(field.type_.location, core::TermData::Error)
+                }
+                None => self.check_type(&field.type_, &format_type),
+            };
+            self.ambient_endianness = None;
+
+            if field.flatten {
+                let is_flattened = self.flatten_field(
+                    field_location,
+                    &core_type,
+                    &mut seen_field_labels,
+                    &mut core_field_declarations,
+                    &mut field_repr_types,
+                );
+                if is_flattened {
+                    continue;
+                }
+                self.push_message(SurfaceToCoreMessage::InvalidFlattenedField { field_location });
+            }
 
             match seen_field_labels.entry(field.label.data.clone()) {
                 Entry::Vacant(entry) => {
                     let core_type = Arc::new(core_type);
                     let r#type = semantics::repr(self.eval(&core_type));
+                    let repr_type = self.read_back_to_surface(&r#type);
+                    field_repr_types.insert(field.label.data.clone(), repr_type);
 
                     core_field_declarations.push(core::FieldDeclaration {
                         doc: field.doc.clone(),
@@ -456,15 +577,121 @@ impl<'globals> Context<'globals> {
             fields: core_field_declarations.clone(),
         });
         let item_data = semantics::ItemData::StructFormat(arity, core_field_declarations);
+        self.field_repr_types
+            .insert(struct_type.name.data.clone(), field_repr_types);
 
         (core_item_data, item_data, r#type)
     }
 
+    /// Returns the primitive formats that are represented by a host type, if
+    /// the host type was mistakenly used in place of a format.
+    fn host_type_formats(&self, surface_term: &Term) -> Option<&'static [&'static str]> {
+        let name = match &surface_term.data {
+            TermData::Name(name) => name,
+            _ => return None,
+        };
+        if self.get_local(name).is_some() || self.item_declarations.contains_key(name) {
+            return None;
+        }
+        match name.as_str() {
+            "Int" => Some(&[
+                "U8", "U16Be", "U32Be", "U64Be", "S8", "S16Be", "S32Be", "S64Be",
+            ]),
+            "F16" => Some(&["F16Be", "F16Le"]),
+            "F32" => Some(&["F32Be", "F32Le"]),
+            "F64" => Some(&["F64Be", "F64Le"]),
+            _ => None,
+        }
+    }
+
+    /// Splice the fields of a flattened field's struct format into the fields
+    /// of the enclosing struct format, returning `false` if the format of the
+    /// field is not a struct format.
+    ///
+    /// The types of the spliced fields are normalized, with the parameters of
+    /// the inner struct format replaced by their arguments.
+    fn flatten_field(
+        &mut self,
+        field_location: Location,
+        core_type: &core::Term,
+        seen_field_labels: &mut HashMap<String, Location>,
+        core_field_declarations: &mut Vec<core::FieldDeclaration>,
+        field_repr_types: &mut HashMap<String, Term>,
+    ) -> bool {
+        let format = self.eval(core_type);
+        let (inner_field_declarations, elims) = match format.as_ref() {
+            Value::Stuck(Head::Item(name), elims) => {
+                match self.item_definitions.get(name).map(|item| &item.data) {
+                    Some(semantics::ItemData::StructFormat(arity, field_declarations))
+                        if *arity == elims.len() =>
+                    {
+                        (field_declarations.clone(), elims)
+                    }
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        };
+
+        let mut locals = core::Locals::new();
+        for elim in elims {
+            match elim {
+                Elim::Function(argument) => locals.push(argument.clone()),
+                _ => return false,
+            }
+        }
+
+        // Splicing in some, but not all of the fields would leave the
+        // remaining field types referring to the wrong fields, so the
+        // flattened field is skipped if any of the labels collide.
+        let mut has_collisions = false;
+        for inner_field_declaration in inner_field_declarations.iter() {
+            let label = &inner_field_declaration.label.data;
+            if let Some(original_location) = seen_field_labels.get(label) {
+                self.push_message(SurfaceToCoreMessage::FieldRedeclaration {
+                    name: label.clone(),
+                    found_location: field_location,
+                    original_location: *original_location,
+                });
+                has_collisions = true;
+            }
+        }
+        if has_collisions {
+            return true;
+        }
+
+        for inner_field_declaration in inner_field_declarations.iter() {
+            let label = &inner_field_declaration.label.data;
+            let format = semantics::eval(
+                self.globals,
+                &self.item_definitions,
+                &mut locals,
+                &inner_field_declaration.type_,
+            );
+            let core_type =
+                semantics::read_back(self.globals, &self.item_definitions, self.size(), &format);
+            let r#type = semantics::repr(format);
+            let repr_type = self.read_back_to_surface(&r#type);
+            field_repr_types.insert(label.clone(), repr_type);
+
+            core_field_declarations.push(core::FieldDeclaration {
+                doc: inner_field_declaration.doc.clone(),
+                label: Located::new(field_location, label.clone()),
+                type_: Arc::new(core_type),
+            });
+            locals.push(self.push_local_param(label.clone(), r#type));
+            seen_field_labels.insert(label.clone(), field_location);
+        }
+
+        true
+    }
+
     /// Validate that a surface term is a type, and translate it into the core syntax.
     #[debug_ensures(self.item_declarations.len() == old(self.item_declarations.len()))]
     #[debug_ensures(self.item_definitions.len() == old(self.item_definitions.len()))]
     #[debug_ensures(self.local_declarations.len() == old(self.local_declarations.len()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn is_type(&mut self, surface_term: &Term) -> (core::Term, Option<core::Sort>) {
         let (core_term, core_type) = self.synth_type(surface_term);
         match core_type.as_ref() {
@@ -493,6 +720,7 @@ impl<'globals> Context<'globals> {
     #[debug_ensures(self.item_definitions.len() == old(self.item_definitions.len()))]
     #[debug_ensures(self.local_declarations.len() == old(self.local_declarations.len()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn check_type(&mut self, surface_term: &Term, expected_type: &Arc<Value>) -> core::Term {
         match (&surface_term.data, expected_type.as_ref()) {
             (TermData::Error, _) => core::Term::new(surface_term.location, core::TermData::Error),
@@ -515,12 +743,18 @@ impl<'globals> Context<'globals> {
                 };
 
                 // Initial pass over the fields, looking for duplicate fields.
+                // Only the first definition of each field is elaborated.
                 let mut pending_field_definitions = BTreeMap::new();
-                let mut duplicate_labels = Vec::new();
                 for field_definition in surface_field_definitions {
                     match pending_field_definitions.entry(&field_definition.label.data) {
                         Entry::Vacant(entry) => drop(entry.insert(field_definition)),
-                        Entry::Occupied(_) => duplicate_labels.push(field_definition.label.clone()),
+                        Entry::Occupied(entry) => {
+                            self.push_message(SurfaceToCoreMessage::FieldRedefinition {
+                                name: field_definition.label.data.clone(),
+                                found_location: field_definition.label.location,
+                                original_location: entry.get().label.location,
+                            });
+                        }
                     }
                 }
 
@@ -561,12 +795,6 @@ impl<'globals> Context<'globals> {
 
                 // Record any errors in the context.
                 let mut has_problems = false;
-                if !duplicate_labels.is_empty() {
-                    has_problems = true;
-                    self.push_message(SurfaceToCoreMessage::DuplicateStructFields {
-                        duplicate_labels,
-                    });
-                }
                 if !missing_labels.is_empty() {
                     has_problems = true;
                     self.push_message(SurfaceToCoreMessage::MissingStructFields {
@@ -620,6 +848,16 @@ impl<'globals> Context<'globals> {
                         }
                     }
                 }
+                Some(("List", [Elim::Function(elem_type)])) => {
+                    let elem_terms = surface_elem_terms
+                        .iter()
+                        .map(|surface_elem_term| {
+                            Arc::new(self.check_type(surface_elem_term, elem_type))
+                        })
+                        .collect();
+
+                    core::Term::new(surface_term.location, core::TermData::ArrayTerm(elem_terms))
+                }
                 Some(_) | None => {
                     let expected_type = self.read_back_to_surface(expected_type);
                     self.push_message(SurfaceToCoreMessage::UnexpectedSequenceTerm {
@@ -638,6 +876,10 @@ impl<'globals> Context<'globals> {
                         .number_to_big_int()
                         .map(Primitive::Int)
                         .map_or(core::TermData::Error, core::TermData::Primitive),
+                    Some(("F16", [])) => parse_state
+                        .number_to_float()
+                        .map(|value| Primitive::F16(ieee754::f32_to_f16(value)))
+                        .map_or(core::TermData::Error, core::TermData::Primitive),
                     Some(("F32", [])) => parse_state
                         .number_to_float()
                         .map(Primitive::F32)
@@ -660,15 +902,41 @@ impl<'globals> Context<'globals> {
             }
             (TermData::If(surface_head, surface_if_true, surface_if_false), _) => {
                 let bool_type = Arc::new(Value::global("Bool", Vec::new()));
-                let term_data = core::TermData::BoolElim(
-                    Arc::new(self.check_type(surface_head, &bool_type)),
-                    Arc::new(self.check_type(surface_if_true, expected_type)),
-                    Arc::new(self.check_type(surface_if_false, expected_type)),
-                );
+                let head = self.check_type(surface_head, &bool_type);
+                let if_true = self.check_type(surface_if_true, expected_type);
+                let if_false = self.check_type(surface_if_false, expected_type);
+
+                if let Value::FormatType = expected_type.as_ref() {
+                    self.check_branch_sizes(&if_true, &if_false);
+                }
+
+                let term_data =
+                    core::TermData::BoolElim(Arc::new(head), Arc::new(if_true), Arc::new(if_false));
 
                 core::Term::new(surface_term.location, term_data)
             }
-            (TermData::Match(surface_head, surface_branches), _) => {
+            (TermData::Match(surface_heads, surface_branches), _) => {
+                if surface_branches.is_empty() {
+                    for surface_head in surface_heads {
+                        self.synth_type(surface_head);
+                    }
+                    self.push_message(SurfaceToCoreMessage::EmptyMatch {
+                        match_location: surface_term.location,
+                    });
+                    return core::Term::new(surface_term.location, core::TermData::Error);
+                }
+
+                let surface_head = match surface_heads.as_slice() {
+                    [surface_head] => surface_head,
+                    _ => {
+                        return self.check_tuple_match(
+                            surface_term.location,
+                            surface_heads,
+                            surface_branches,
+                            expected_type,
+                        );
+                    }
+                };
                 let (head, head_type) = self.synth_type(surface_head);
                 if let Value::Error = head_type.as_ref() {
                     return core::Term::new(surface_term.location, core::TermData::Error);
@@ -726,6 +994,7 @@ impl<'globals> Context<'globals> {
     #[debug_ensures(self.item_definitions.len() == old(self.item_definitions.len()))]
     #[debug_ensures(self.local_declarations.len() == old(self.local_declarations.len()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn synth_type(&mut self, surface_term: &Term) -> (core::Term, Arc<Value>) {
         match &surface_term.data {
             TermData::Name(name) => {
@@ -740,7 +1009,10 @@ impl<'globals> Context<'globals> {
                     return (core_term, r#type.clone());
                 }
                 if let Some((r#type, _)) = self.globals.get(name) {
-                    let term_data = core::TermData::Global(name.to_owned());
+                    let name = (self.ambient_endianness)
+                        .and_then(|endianness| endianness.resolve_format_name(name))
+                        .unwrap_or_else(|| name.to_owned());
+                    let term_data = core::TermData::Global(name);
                     let core_term = core::Term::new(surface_term.location, term_data);
                     return (core_term, self.eval(r#type));
                 }
@@ -748,6 +1020,7 @@ impl<'globals> Context<'globals> {
                 self.push_message(SurfaceToCoreMessage::VarNameNotFound {
                     name: name.clone(),
                     name_location: surface_term.location,
+                    suggested_names: self.suggest_names(name),
                 });
                 (
                     core::Term::new(surface_term.location, core::TermData::Error),
@@ -843,6 +1116,19 @@ impl<'globals> Context<'globals> {
                     }
                 }
 
+                if !self.check_concat_elem_types(surface_term.location, &core_head)
+                    || !self.check_alignment(surface_term.location, &core_head)
+                    || !self.check_window_size(surface_term.location, &core_head)
+                    || !self.check_sentinel_elem_type(surface_term.location, &core_head)
+                    || !self.check_key_label(&core_head)
+                    || !self.check_map_index_elem_repr(surface_term.location, &core_head)
+                {
+                    return (
+                        core::Term::new(surface_term.location, core::TermData::Error),
+                        Arc::new(Value::Error),
+                    );
+                }
+
                 (core_head, head_type)
             }
 
@@ -909,6 +1195,18 @@ impl<'globals> Context<'globals> {
                 )
             }
 
+            TermData::StringLiteral(source) => {
+                let parse_state =
+                    literal::State::new(surface_term.location, source, &mut self.messages);
+                let term_data = parse_state
+                    .string_to_string()
+                    .map(Primitive::String)
+                    .map_or(core::TermData::Error, core::TermData::Primitive);
+                (
+                    core::Term::new(surface_term.location, term_data),
+                    Arc::new(Value::global("String", Vec::new())),
+                )
+            }
             TermData::NumberLiteral(_) => {
                 self.push_message(SurfaceToCoreMessage::AmbiguousNumericLiteral {
                     literal_location: surface_term.location,
@@ -978,6 +1276,358 @@ impl<'globals> Context<'globals> {
         }
     }
 
+    /// Check that the formats passed to `FormatArrayConcat` are represented as
+    /// arrays with the same element type, returning `false` if they are not.
+    fn check_concat_elem_types(&mut self, term_location: Location, core_term: &core::Term) -> bool {
+        let format = self.eval(core_term);
+        if let Some(("FormatArrayConcat", [Elim::Function(format0), Elim::Function(format1)])) =
+            format.try_global()
+        {
+            let format0_repr = semantics::repr(format0.clone());
+            let format1_repr = semantics::repr(format1.clone());
+            if let (
+                Some(("Array", [_, Elim::Function(elem_type0)])),
+                Some(("Array", [_, Elim::Function(elem_type1)])),
+            ) = (format0_repr.try_global(), format1_repr.try_global())
+            {
+                if !self.is_equal(elem_type0, elem_type1) {
+                    let first_elem_type = self.read_back_to_surface(elem_type0);
+                    let second_elem_type = self.read_back_to_surface(elem_type1);
+                    self.push_message(SurfaceToCoreMessage::MismatchedConcatElemTypes {
+                        term_location,
+                        first_elem_type,
+                        second_elem_type,
+                    });
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Check that an alignment format, if it is known, is not aligned to zero
+    /// bytes.
+    fn check_alignment(&mut self, term_location: Location, core_term: &core::Term) -> bool {
+        let format = self.eval(core_term);
+        if let Some(("FormatAlign", [Elim::Function(align)])) = format.try_global() {
+            if let Value::Primitive(Primitive::Int(align)) = align.as_ref() {
+                if *align == BigInt::from(0) {
+                    self.push_message(SurfaceToCoreMessage::ZeroAlignment { term_location });
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Check that the windows of an array, if their size is known, are not
+    /// empty, and are not longer than the array, if its length is known.
+    fn check_window_size(&mut self, term_location: Location, core_term: &core::Term) -> bool {
+        let format = self.eval(core_term);
+        let (size, format) = match format.try_global() {
+            Some(("FormatArrayWindows", [Elim::Function(size), Elim::Function(format)])) => {
+                match size.as_ref() {
+                    Value::Primitive(Primitive::Int(size)) => (size.clone(), format.clone()),
+                    _ => return true,
+                }
+            }
+            _ => return true,
+        };
+        let len = match semantics::repr(format).try_global() {
+            Some(("Array", [Elim::Function(len), _])) => match len.as_ref() {
+                Value::Primitive(Primitive::Int(len)) => Some(len.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let len = match len {
+            _ if size == BigInt::from(0) => None,
+            Some(len) if size > len => Some(len),
+            _ => return true,
+        };
+        self.push_message(SurfaceToCoreMessage::InvalidWindowSize {
+            term_location,
+            size,
+            len,
+        });
+        false
+    }
+
+    /// Check that the elements of a sentinel-terminated list, if they are
+    /// known, are represented by integers that the predicate can test.
+    fn check_sentinel_elem_type(
+        &mut self,
+        term_location: Location,
+        core_term: &core::Term,
+    ) -> bool {
+        let format = self.eval(core_term);
+        let elem_type = match format.try_global() {
+            Some(("FormatRepeatUntil", [Elim::Function(_), Elim::Function(elem_type)]))
+            | Some((
+                "FormatRepeatUntilInclusive",
+                [Elim::Function(_), Elim::Function(elem_type)],
+            )) => elem_type.clone(),
+            _ => return true,
+        };
+        let elem_repr = semantics::repr(elem_type);
+        match elem_repr.as_ref() {
+            Value::Stuck(Head::Global(name), elims) if name == "Int" && elims.is_empty() => true,
+            // The representation of unknown formats, like parameters, is not
+            // known until they are applied.
+            Value::Stuck(Head::Local(_), _) | Value::Error => true,
+            _ => {
+                let elem_type = self.read_back_to_surface(&elem_repr);
+                self.push_message(SurfaceToCoreMessage::NonIntegerSentinelElem {
+                    term_location,
+                    elem_type,
+                });
+                false
+            }
+        }
+    }
+
+    /// Check that the label that the elements of an array are compared by, if
+    /// the elements are known, refers to one of their fields.
+    fn check_key_label(&mut self, core_term: &core::Term) -> bool {
+        let (label_term, format_term) = match &core_term.data {
+            core::TermData::FunctionElim(head, format_term) => match &head.data {
+                core::TermData::FunctionElim(_, label_term) => (label_term, format_term),
+                _ => return true,
+            },
+            _ => return true,
+        };
+        let format = self.eval(core_term);
+        let (label, format) = match format.try_global() {
+            Some(("FormatArraySortedBy", [Elim::Function(label), Elim::Function(format)]))
+            | Some(("FormatArrayUniqueBy", [Elim::Function(label), Elim::Function(format)]))
+            | Some(("FormatArraySortBy", [Elim::Function(label), Elim::Function(format)]))
+            | Some(("FormatArrayDedupBy", [Elim::Function(label), Elim::Function(format)])) => {
+                match label.as_ref() {
+                    Value::Primitive(Primitive::String(label)) => (label.clone(), format.clone()),
+                    _ => return true,
+                }
+            }
+            _ => return true,
+        };
+        let format_repr = semantics::repr(format);
+        let elem_type = match format_repr.try_global() {
+            Some(("Array", [_, Elim::Function(elem_type)]))
+            | Some(("List", [Elim::Function(elem_type)])) => elem_type.clone(),
+            _ => return true,
+        };
+        match elem_type.as_ref() {
+            // The fields of unknown types, like parameters, are not known
+            // until they are applied.
+            Value::Stuck(Head::Local(_), _) | Value::Error => return true,
+            _ => {}
+        }
+        match self.force_field_declarations(&elem_type) {
+            Some(field_declarations) if field_declarations.contains_field(&label) => true,
+            _ => {
+                let head_type = self.read_back_to_surface(&elem_type);
+                self.push_message(SurfaceToCoreMessage::FieldNotFound {
+                    head_location: format_term.location,
+                    head_type,
+                    label: Located::new(label_term.location, label),
+                });
+                false
+            }
+        }
+    }
+
+    /// Check that the representation of the elements of an index-dependent
+    /// array is the same for every index.
+    fn check_map_index_elem_repr(
+        &mut self,
+        term_location: Location,
+        core_term: &core::Term,
+    ) -> bool {
+        let format = self.eval(core_term);
+        let elem_type = match format.try_global() {
+            Some(("FormatArrayMapIndex", [Elim::Function(_), Elim::Function(elem_type)])) => {
+                elem_type.clone()
+            }
+            _ => return true,
+        };
+
+        let int_type = Arc::new(Value::global("Int", Vec::new()));
+        let index = self.push_local_param("index".to_owned(), int_type);
+        let first_elem_repr = semantics::repr(semantics::function_elim(
+            elem_type.clone(),
+            Arc::new(Value::int(0)),
+        ));
+        let elem_repr = semantics::repr(semantics::function_elim(elem_type, index));
+        let is_same_repr = self.is_same_repr(&first_elem_repr, &elem_repr);
+        let elem_repr = self.read_back_to_surface(&elem_repr);
+        self.pop_local();
+
+        if !is_same_repr {
+            self.push_message(SurfaceToCoreMessage::IndexDependentElemRepr {
+                term_location,
+                elem_type: elem_repr,
+            });
+        }
+        is_same_repr
+    }
+
+    /// Check that two representations are equal, comparing the fields of
+    /// struct types rather than their parameters.
+    fn is_same_repr(&mut self, repr0: &Arc<Value>, repr1: &Arc<Value>) -> bool {
+        if self.is_equal(repr0, repr1) {
+            return true;
+        }
+
+        let (field_declarations0, field_declarations1) = match (
+            self.force_field_declarations(repr0),
+            self.force_field_declarations(repr1),
+        ) {
+            (Some(field_declarations0), Some(field_declarations1)) => {
+                (field_declarations0, field_declarations1)
+            }
+            (_, _) => return false,
+        };
+
+        // Project the fields of both structs from the same unknown value, so
+        // that fields that depend on earlier fields can be compared.
+        let head = self.push_local_param("elem".to_owned(), Arc::new(Value::Error));
+        let field_types0 =
+            field_declarations0.get_field_types(self.globals, &self.item_definitions, head.clone());
+        let field_types1 =
+            field_declarations1.get_field_types(self.globals, &self.item_definitions, head);
+        let is_same_repr = field_types0.len() == field_types1.len()
+            && Iterator::zip(field_types0.iter(), field_types1.iter()).all(
+                |((label0, type0), (label1, type1))| {
+                    label0 == label1 && self.is_same_repr(type0, type1)
+                },
+            );
+        self.pop_local();
+
+        is_same_repr
+    }
+
+    /// Warn if the branches of a conditional format are known to read
+    /// different numbers of bytes.
+    fn check_branch_sizes(&mut self, if_true: &core::Term, if_false: &core::Term) {
+        let if_true_format = self.eval(if_true);
+        let if_false_format = self.eval(if_false);
+        let if_true_size =
+            semantics::format_size(self.globals, &self.item_definitions, &if_true_format);
+        let if_false_size =
+            semantics::format_size(self.globals, &self.item_definitions, &if_false_format);
+
+        if let (Some(if_true_size), Some(if_false_size)) = (if_true_size, if_false_size) {
+            if if_true_size != if_false_size {
+                self.push_message(SurfaceToCoreMessage::BranchSizeMismatch {
+                    if_true_location: if_true.location,
+                    if_true_size,
+                    if_false_location: if_false.location,
+                    if_false_size,
+                });
+            }
+        }
+    }
+
+    /// Elaborate a match on multiple integer scrutinees into nested integer
+    /// eliminations, one for each scrutinee.
+    fn check_tuple_match(
+        &mut self,
+        location: Location,
+        surface_heads: &[Term],
+        surface_branches: &[(Pattern, Term)],
+        expected_type: &Arc<Value>,
+    ) -> core::Term {
+        let mut heads = Vec::with_capacity(surface_heads.len());
+        for surface_head in surface_heads {
+            let (head, head_type) = self.synth_type(surface_head);
+            if let Value::Error = head_type.as_ref() {
+                return core::Term::new(location, core::TermData::Error);
+            }
+            match head_type.try_global() {
+                Some(("Int", [])) => heads.push(Arc::new(head)),
+                _ => {
+                    let found_type = self.read_back_to_surface(&head_type);
+                    self.push_message(SurfaceToCoreMessage::UnsupportedPatternType {
+                        scrutinee_location: surface_head.location,
+                        found_type,
+                    });
+                    return core::Term::new(location, core::TermData::Error);
+                }
+            }
+        }
+
+        let mut rows = Vec::with_capacity(surface_branches.len());
+        for (pattern, surface_term) in surface_branches {
+            let body = Arc::new(self.check_type(surface_term, expected_type));
+            let row = match &pattern.data {
+                PatternData::Name(_) => Some(vec![None; heads.len()]),
+                PatternData::Tuple(patterns) if patterns.len() == heads.len() => (patterns.iter())
+                    .map(|pattern| self.int_pattern_value(pattern))
+                    .collect::<Option<Vec<_>>>(),
+                PatternData::Tuple(patterns) => {
+                    self.push_message(SurfaceToCoreMessage::MismatchedPatternArity {
+                        pattern_location: pattern.location,
+                        expected_count: heads.len(),
+                        found_count: patterns.len(),
+                    });
+                    None
+                }
+                PatternData::NumberLiteral(_) => {
+                    self.push_message(SurfaceToCoreMessage::MismatchedPatternArity {
+                        pattern_location: pattern.location,
+                        expected_count: heads.len(),
+                        found_count: 1,
+                    });
+                    None
+                }
+            };
+            rows.push((pattern.location, row, body));
+        }
+
+        let mut is_reachable = vec![false; rows.len()];
+        let pattern_rows = (rows.iter().enumerate())
+            .filter_map(|(index, (_, row, body))| Some((index, row.as_deref()?, body.clone())))
+            .collect();
+        let term = from_pattern_rows(location, &heads, pattern_rows, &mut is_reachable);
+
+        for ((pattern_location, row, _), is_reachable) in rows.iter().zip(is_reachable) {
+            if row.is_some() && !is_reachable {
+                self.push_message(SurfaceToCoreMessage::UnreachablePattern {
+                    pattern_location: *pattern_location,
+                });
+            }
+        }
+
+        match term {
+            Some(term) => Arc::try_unwrap(term).unwrap_or_else(|term| (*term).clone()),
+            None => {
+                self.push_message(SurfaceToCoreMessage::NoDefaultPattern {
+                    match_location: location,
+                });
+                core::Term::new(location, core::TermData::Error)
+            }
+        }
+    }
+
+    /// Elaborate a pattern for one of the scrutinees of a tuple match,
+    /// returning `Some(None)` if it matches any integer.
+    fn int_pattern_value(&mut self, pattern: &Pattern) -> Option<Option<BigInt>> {
+        match &pattern.data {
+            PatternData::Name(_) => Some(None),
+            PatternData::NumberLiteral(source) => {
+                let parse_state = literal::State::new(pattern.location, source, &mut self.messages);
+                Some(Some(parse_state.number_to_big_int()?))
+            }
+            PatternData::Tuple(patterns) => {
+                self.push_message(SurfaceToCoreMessage::MismatchedPatternArity {
+                    pattern_location: pattern.location,
+                    expected_count: 1,
+                    found_count: patterns.len(),
+                });
+                None
+            }
+        }
+    }
+
     fn from_int_branches(
         &mut self,
         location: Location,
@@ -1021,6 +1671,14 @@ impl<'globals> Context<'globals> {
                         Some(_) => self.push_message(unreachable_pattern()),
                     }
                 }
+                PatternData::Tuple(patterns) => {
+                    self.check_type(surface_term, expected_type);
+                    self.push_message(SurfaceToCoreMessage::MismatchedPatternArity {
+                        pattern_location: pattern.location,
+                        expected_count: 1,
+                        found_count: patterns.len(),
+                    });
+                }
             }
         }
 
@@ -1034,3 +1692,132 @@ impl<'globals> Context<'globals> {
         (branches, default)
     }
 }
+
+/// A row of a pattern matrix, made up of the index of the branch that the row
+/// came from, the values to compare against each of the remaining scrutinees
+/// (`None` matches any value), and the body of the branch.
+type PatternRow<'a> = (usize, &'a [Option<BigInt>], Arc<core::Term>);
+
+/// Compile the rows of a pattern matrix into nested integer eliminations,
+/// selecting the first row that matches. The branches that can be selected
+/// are recorded in `is_reachable`, and `None` is returned if there are
+/// combinations of values that are not matched by any row.
+///
+/// NOTE: Rows that match any value are copied into the branch for every value
+/// of the scrutinee, so the number of eliminations can grow with the product
+/// of the number of distinct values in each column. The bodies themselves are
+/// shared between the branches rather than being copied.
+fn from_pattern_rows(
+    location: Location,
+    heads: &[Arc<core::Term>],
+    rows: Vec<PatternRow<'_>>,
+    is_reachable: &mut [bool],
+) -> Option<Arc<core::Term>> {
+    let (head, heads) = match heads.split_first() {
+        Some((head, heads)) => (head, heads),
+        None => {
+            let (index, _, body) = rows.into_iter().next()?;
+            is_reachable[index] = true;
+            return Some(body);
+        }
+    };
+
+    let matching_rows = |value: Option<&BigInt>| {
+        (rows.iter())
+            .filter(|(_, row, _)| row[0].is_none() || row[0].as_ref() == value)
+            .map(|(index, row, body)| (*index, &row[1..], body.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    // Every branch is compiled, even after one is found to be non-exhaustive,
+    // so that the reachability of the remaining rows is still recorded.
+    let mut branches = BTreeMap::new();
+    for (_, row, _) in &rows {
+        if let Some(value) = &row[0] {
+            if !branches.contains_key(value) {
+                let rows = matching_rows(Some(value));
+                let branch = from_pattern_rows(location, heads, rows, is_reachable);
+                branches.insert(value.clone(), branch);
+            }
+        }
+    }
+    let default = from_pattern_rows(location, heads, matching_rows(None), is_reachable);
+
+    let branches = (branches.into_iter())
+        .map(|(value, branch)| Some((value, branch?)))
+        .collect::<Option<_>>()?;
+    let term_data = core::TermData::IntElim(head.clone(), branches, default?);
+    Some(Arc::new(core::Term::new(location, term_data)))
+}
+
+/// The number of single character insertions, deletions, and substitutions
+/// needed to turn one string into another.
+fn edit_distance(source: &str, target: &str) -> usize {
+    let target = target.chars().collect::<Vec<_>>();
+    let mut distances = (0..=target.len()).collect::<Vec<_>>();
+
+    for (i, source_char) in source.chars().enumerate() {
+        let mut previous_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, target_char) in target.iter().enumerate() {
+            let substitution = previous_diagonal + (source_char != *target_char) as usize;
+            previous_diagonal = distances[j + 1];
+            distances[j + 1] = substitution.min(distances[j] + 1).min(distances[j + 1] + 1);
+        }
+    }
+
+    distances[target.len()]
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::Mutex;
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata};
+
+    use super::*;
+
+    /// A subscriber that records the names of the spans that it sees.
+    #[derive(Default)]
+    struct SpanNames(Mutex<Vec<&'static str>>);
+
+    impl tracing::Subscriber for SpanNames {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            Id::from_u64(names.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn elaboration_emits_spans() {
+        let globals = core::Globals::default();
+        let mut messages = Vec::new();
+        let surface_module = Module::parse(
+            0,
+            "struct Pair : Format { first : U8, second : U16Be }",
+            &mut messages,
+        );
+        assert!(messages.is_empty());
+
+        let subscriber = Arc::new(SpanNames::default());
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            Context::new(&globals).from_module(&surface_module)
+        });
+
+        let names = subscriber.0.lock().unwrap();
+        assert_eq!(names.first(), Some(&"from_module"));
+        assert!(names.contains(&"check_type"));
+        assert!(names.contains(&"synth_type"));
+    }
+}
@@ -41,8 +41,17 @@ impl Context {
         None
     }
 
+    /// Write the documentation for a module.
+    ///
+    /// The representation types of the fields of struct formats are shown
+    /// alongside their formats, if they are found in `field_repr_types`.
     #[allow(clippy::write_literal)]
-    pub fn from_module(&mut self, writer: &mut impl Write, module: &Module) -> io::Result<()> {
+    pub fn from_module(
+        &mut self,
+        writer: &mut impl Write,
+        module: &Module,
+        field_repr_types: &HashMap<String, HashMap<String, Term>>,
+    ) -> io::Result<()> {
         write!(
             writer,
             r##"<!--
@@ -84,7 +93,10 @@ impl Context {
         for item in &module.items {
             let (name, item) = match &item.data {
                 ItemData::Constant(constant) => self.from_constant(writer, constant)?,
-                ItemData::StructType(struct_type) => self.from_struct_type(writer, struct_type)?,
+                ItemData::StructType(struct_type) => {
+                    let field_repr_types = field_repr_types.get(&struct_type.name.data);
+                    self.from_struct_type(writer, struct_type, field_repr_types)?
+                }
             };
 
             self.items.insert(name, item);
@@ -163,6 +175,7 @@ impl Context {
         &mut self,
         writer: &mut impl Write,
         struct_type: &StructType,
+        field_repr_types: Option<&HashMap<String, Term>>,
     ) -> io::Result<(String, ItemMeta)> {
         let id = format!("items[{}]", struct_type.name.data);
 
@@ -202,11 +215,17 @@ impl Context {
             for field in &struct_type.fields {
                 let field_id = format!("{}.fields[{}]", id, field.label.data);
                 let r#type = self.from_term_prec(&field.type_, Prec::Term);
+                let repr_type = field_repr_types
+                    .and_then(|field_repr_types| field_repr_types.get(&field.label.data))
+                    .map_or(Cow::from(""), |repr_type| {
+                        let repr_type = self.from_term_prec(repr_type, Prec::Term);
+                        format!(r##" <span class="repr">→ {}</span>"##, repr_type).into()
+                    });
 
                 write!(
                     writer,
                     r##"            <dt id="{id}" class="field">
-              <a href="#{id}">{name}</a> : {type_}
+              <a href="#{id}">{name}</a> : {type_}{repr_type}
             </dt>
             <dd class="field">
               <section class="doc">
@@ -214,6 +233,7 @@ impl Context {
                     id = field_id,
                     name = field.label.data,
                     type_ = r#type,
+                    repr_type = repr_type,
                 )?;
                 from_doc_lines(writer, "                ", &field.doc)?;
                 write!(
@@ -312,6 +332,10 @@ impl Context {
             .into(),
 
             TermData::NumberLiteral(literal) => format!("{}", literal).into(),
+            TermData::StringLiteral(literal) => (literal.replace('&', "&amp;"))
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .into(),
             TermData::If(head, if_true, if_false) => format!(
                 // TODO: multiline formatting!
                 "if {head} {{ {if_true} }} else {{ {if_false} }}",
@@ -320,10 +344,13 @@ impl Context {
                 if_false = self.from_term_prec(if_false, Prec::Term),
             )
             .into(),
-            TermData::Match(head, branches) => format!(
+            TermData::Match(heads, branches) => format!(
                 // TODO: multiline formatting!
-                "match {head} {{ {branches} }}",
-                head = self.from_term_prec(head, Prec::Term),
+                "match {heads} {{ {branches} }}",
+                heads = heads
+                    .iter()
+                    .map(|head| self.from_term_prec(head, Prec::Term))
+                    .format(", "),
                 branches = branches
                     .iter()
                     .map(|(pattern, term)| format!(
@@ -344,9 +371,19 @@ impl Context {
     }
 
     fn from_pattern<'term>(&self, pattern: &'term Pattern) -> Cow<'term, str> {
+        use itertools::Itertools;
+
         match &pattern.data {
             PatternData::Name(name) => format!(r##"<a href="#">{}</a>"##, name).into(), // TODO: add local binding
             PatternData::NumberLiteral(literal) => format!("{}", literal).into(),
+            PatternData::Tuple(patterns) => format!(
+                "({})",
+                patterns
+                    .iter()
+                    .map(|pattern| self.from_pattern(pattern))
+                    .format(", "),
+            )
+            .into(),
         }
     }
 }
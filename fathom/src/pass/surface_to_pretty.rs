@@ -1,7 +1,9 @@
 //! Pretty prints the surface language to a textual form.
 
+use itertools::Itertools;
 use pretty::{DocAllocator, DocBuilder};
 
+use crate::lang::core::binary::Endianness;
 use crate::lang::surface::{
     Constant, FieldDeclaration, FieldDefinition, Item, ItemData, Module, Pattern, PatternData,
     StructType, Term, TermData,
@@ -94,6 +96,18 @@ where
             .append(alloc.hardline())
     }));
 
+    let attributes = alloc.concat(struct_type.attributes.iter().map(|attribute| {
+        (alloc.nil())
+            .append("#[")
+            .append(&attribute.name.data)
+            .append(alloc.space())
+            .append("=")
+            .append(alloc.space())
+            .append(from_term_prec(alloc, &attribute.value, Prec::Term))
+            .append("]")
+            .append(alloc.hardline())
+    }));
+
     let struct_prefix =
         (alloc.nil())
             .append("struct")
@@ -127,18 +141,15 @@ where
             .append(struct_prefix)
             .append("{")
             .group()
-            .append(alloc.concat(struct_type.fields.iter().map(|field| {
-                (alloc.nil())
-                    .append(alloc.hardline())
-                    .append(from_field_declaration(alloc, field))
-                    .nest(4)
-                    .group()
-            })))
+            .append(from_field_declarations(alloc, &struct_type.fields))
             .append(alloc.hardline())
             .append("}")
     };
 
-    (alloc.nil()).append(docs).append(struct_type)
+    (alloc.nil())
+        .append(docs)
+        .append(attributes)
+        .append(struct_type)
 }
 
 pub fn from_struct_term<'a, D>(
@@ -172,6 +183,47 @@ where
     }
 }
 
+/// Print a list of field declarations, grouping consecutive fields with the
+/// same endianness into a single endianness scope.
+pub fn from_field_declarations<'a, D>(
+    alloc: &'a D,
+    field_declarations: &'a [FieldDeclaration],
+) -> DocBuilder<'a, D>
+where
+    D: DocAllocator<'a>,
+    D::Doc: Clone,
+{
+    let fields = |fields: &[&'a FieldDeclaration]| {
+        alloc.concat(fields.iter().map(|field| {
+            (alloc.nil())
+                .append(alloc.hardline())
+                .append(from_field_declaration(alloc, field))
+                .nest(4)
+                .group()
+        }))
+    };
+
+    let groups = field_declarations.iter().group_by(|field| field.endianness);
+    let groups = groups.into_iter().map(|(endianness, group)| {
+        let group = group.collect::<Vec<_>>();
+        match endianness {
+            None => fields(&group),
+            Some(endianness) => (alloc.nil())
+                .append(alloc.hardline())
+                .append(match endianness {
+                    Endianness::Big => "big_endian {",
+                    Endianness::Little => "little_endian {",
+                })
+                .append(fields(&group))
+                .append(alloc.hardline())
+                .append("},")
+                .nest(4),
+        }
+    });
+
+    alloc.concat(groups.collect::<Vec<_>>())
+}
+
 pub fn from_field_declaration<'a, D>(
     alloc: &'a D,
     field_declaration: &'a FieldDeclaration,
@@ -190,6 +242,10 @@ where
         .append(docs)
         .append(
             (alloc.nil())
+                .append(match field_declaration.flatten {
+                    true => alloc.text("flatten").append(alloc.space()),
+                    false => alloc.nil(),
+                })
                 .append(&field_declaration.label.data)
                 .append(alloc.space())
                 .append(":")
@@ -235,6 +291,13 @@ where
     match &pattern.data {
         PatternData::Name(name) => alloc.text(name),
         PatternData::NumberLiteral(literal) => alloc.as_string(literal),
+        PatternData::Tuple(patterns) => (alloc.nil())
+            .append("(")
+            .append(alloc.intersperse(
+                patterns.iter().map(|pattern| from_pattern(alloc, pattern)),
+                alloc.text(",").append(alloc.space()),
+            ))
+            .append(")"),
     }
 }
 
@@ -334,6 +397,7 @@ where
             .append("]"),
 
         TermData::NumberLiteral(literal) => alloc.as_string(literal),
+        TermData::StringLiteral(literal) => alloc.as_string(literal),
         TermData::If(head, if_true, if_false) => (alloc.nil())
             .append("if")
             .append(alloc.space())
@@ -367,10 +431,17 @@ where
             )
             .append(alloc.space())
             .append("}"),
-        TermData::Match(head, branches) => (alloc.nil())
+        TermData::Match(heads, branches) => (alloc.nil())
             .append("match")
             .append(alloc.space())
-            .append(from_term_prec(alloc, head, Prec::Term))
+            .append(
+                alloc.intersperse(
+                    heads
+                        .iter()
+                        .map(|head| from_term_prec(alloc, head, Prec::Term)),
+                    alloc.text(",").append(alloc.space()),
+                ),
+            )
             .append(alloc.space())
             .append("{")
             .append(alloc.concat(branches.iter().map(|(pattern, term)| {
@@ -8,12 +8,17 @@
 
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use itertools::Itertools;
+use num_bigint::BigInt;
 use pretty::DocAllocator;
 use std::path::PathBuf;
 
 use crate::lang::{core, surface, FileId, Located, Location};
 use crate::literal;
 
+mod explanations;
+
+pub use self::explanations::explain;
+
 macro_rules! label {
     ($style:ident($location:expr) $(= $message:expr)? $(,)?) => {
         match $location {
@@ -48,6 +53,10 @@ pub enum Message {
         path: PathBuf,
         error: String,
     },
+    RoundTripMismatch {
+        path: PathBuf,
+        offset: usize,
+    },
     Lexer(LexerMessage),
     LiteralParse(LiteralParseMessage),
     Parse(ParseMessage),
@@ -120,12 +129,27 @@ impl Message {
         }
     }
 
+    /// The stable error code of this message. Codes are grouped by the pass
+    /// that produced them, and should not be reused once they are assigned.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Message::NotYetImplemented { .. } => "E0001",
+            Message::ReadFile { .. } => "E0002",
+            Message::RoundTripMismatch { .. } => "E0003",
+            Message::Lexer(message) => message.code(),
+            Message::LiteralParse(message) => message.code(),
+            Message::Parse(message) => message.code(),
+            Message::CoreTyping(message) => message.code(),
+            Message::SurfaceToCore(message) => message.code(),
+        }
+    }
+
     pub fn to_diagnostic<'a, D>(&'a self, pretty_alloc: &'a D) -> Diagnostic<FileId>
     where
         D: DocAllocator<'a>,
         D::Doc: Clone,
     {
-        match self {
+        let diagnostic = match self {
             Message::NotYetImplemented {
                 location,
                 feature_name,
@@ -138,12 +162,23 @@ impl Message {
                 .with_message(format!("failed to read file `{}`", path.display()))
                 // TODO: add user-friendly suggestions
                 .with_notes(vec![format!("{}", error.to_lowercase())]),
+            Message::RoundTripMismatch { path, offset } => Diagnostic::error()
+                .with_message(format!(
+                    "writing the data in `{}` did not reproduce the original bytes",
+                    path.display(),
+                ))
+                .with_notes(vec![format!(
+                    "the bytes first differ at offset {:#x}",
+                    offset
+                )]),
             Message::Lexer(message) => message.to_diagnostic(),
             Message::Parse(message) => message.to_diagnostic(),
             Message::LiteralParse(message) => message.to_diagnostic(),
             Message::CoreTyping(message) => message.to_diagnostic(pretty_alloc),
             Message::SurfaceToCore(message) => message.to_diagnostic(pretty_alloc),
-        }
+        };
+
+        diagnostic.with_code(self.code())
     }
 }
 
@@ -154,6 +189,13 @@ pub enum LexerMessage {
 }
 
 impl LexerMessage {
+    /// The stable error code of this message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexerMessage::InvalidToken { .. } => "E0100",
+        }
+    }
+
     pub fn to_diagnostic(&self) -> Diagnostic<FileId> {
         match self {
             LexerMessage::InvalidToken { location } => Diagnostic::error()
@@ -182,6 +224,15 @@ pub enum ParseMessage {
 }
 
 impl ParseMessage {
+    /// The stable error code of this message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseMessage::UnrecognizedEof { .. } => "E0200",
+            ParseMessage::UnrecognizedToken { .. } => "E0201",
+            ParseMessage::ExtraToken { .. } => "E0202",
+        }
+    }
+
     pub fn to_diagnostic(&self) -> Diagnostic<FileId> {
         match self {
             ParseMessage::UnrecognizedEof { location, expected } => Diagnostic::error()
@@ -195,7 +246,11 @@ impl ParseMessage {
             } => Diagnostic::error()
                 .with_message(format!("unexpected token {}", token))
                 .with_labels(labels![primary(location) = "unexpected token"])
-                .with_notes(format_expected(expected).map_or(Vec::new(), |message| vec![message])),
+                .with_notes(
+                    (format_expected(expected).into_iter())
+                        .chain(field_separator_hint(token, expected))
+                        .collect(),
+                ),
             ParseMessage::ExtraToken { location, token } => Diagnostic::error()
                 .with_message(format!("extra token {}", token))
                 .with_labels(labels![primary(location) = "extra token"]),
@@ -203,6 +258,22 @@ impl ParseMessage {
     }
 }
 
+/// Suggest the correct separator if a field declaration was written like a
+/// field definition, or vice versa.
+fn field_separator_hint(token: &str, expected: &[String]) -> Option<String> {
+    match (token, expected) {
+        ("=", [expected]) if expected == r#"":""# => Some(
+            "help: field declarations use `:` to specify the type of a field, eg. `x : U8`"
+                .to_owned(),
+        ),
+        (":", [expected]) if expected == r#""=""# => Some(
+            "help: field definitions use `=` to specify the value of a field, eg. `x = 1`"
+                .to_owned(),
+        ),
+        (_, _) => None,
+    }
+}
+
 fn format_expected(expected: &[impl std::fmt::Display]) -> Option<String> {
     expected.split_last().map(|items| match items {
         // TODO: Improve token formatting
@@ -222,9 +293,28 @@ pub enum LiteralParseMessage {
     FloatLiteralExponentNotSupported(Location),
     UnsupportedFloatLiteralBase(Location, literal::Base),
     UnexpectedEndOfLiteral(Location),
+    ExpectedStartOfStringLiteral(Location),
+    UnknownEscapeSequence(Location),
 }
 
 impl LiteralParseMessage {
+    /// The stable error code of this message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LiteralParseMessage::ExpectedRadixOrDecimalDigit(..) => "E0300",
+            LiteralParseMessage::ExpectedStartOfNumericLiteral(..) => "E0301",
+            LiteralParseMessage::ExpectedDigit(..) => "E0302",
+            LiteralParseMessage::ExpectedDigitOrSeparator(..) => "E0303",
+            LiteralParseMessage::ExpectedDigitSeparatorOrExp(..) => "E0304",
+            LiteralParseMessage::ExpectedDigitSeparatorFracOrExp(..) => "E0305",
+            LiteralParseMessage::FloatLiteralExponentNotSupported(..) => "E0306",
+            LiteralParseMessage::UnsupportedFloatLiteralBase(..) => "E0307",
+            LiteralParseMessage::UnexpectedEndOfLiteral(..) => "E0308",
+            LiteralParseMessage::ExpectedStartOfStringLiteral(..) => "E0309",
+            LiteralParseMessage::UnknownEscapeSequence(..) => "E0310",
+        }
+    }
+
     pub fn to_diagnostic(&self) -> Diagnostic<FileId> {
         match self {
             LiteralParseMessage::ExpectedRadixOrDecimalDigit(location) => Diagnostic::error()
@@ -271,6 +361,16 @@ impl LiteralParseMessage {
             LiteralParseMessage::UnexpectedEndOfLiteral(location) => Diagnostic::error()
                 .with_message("unexpected end of literal")
                 .with_labels(labels![primary(location)]),
+            LiteralParseMessage::ExpectedStartOfStringLiteral(location) => Diagnostic::error()
+                .with_message("expected the start of a string literal")
+                .with_labels(labels![primary(location)]),
+            LiteralParseMessage::UnknownEscapeSequence(location) => Diagnostic::error()
+                .with_message("unknown character escape")
+                .with_labels(labels![primary(location)])
+                .with_notes(vec![
+                    "valid escapes are `\\\\`, `\\\"`, `\\'`, `\\n`, `\\r`, `\\t`, `\\0`, and `\\u{...}`"
+                        .to_owned(),
+                ]),
         }
     }
 }
@@ -348,6 +448,28 @@ pub enum CoreTypingMessage {
 }
 
 impl CoreTypingMessage {
+    /// The stable error code of this message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CoreTypingMessage::GlobalNameNotFound { .. } => "E0400",
+            CoreTypingMessage::ItemNameNotFound { .. } => "E0401",
+            CoreTypingMessage::LocalIndexNotFound { .. } => "E0402",
+            CoreTypingMessage::FieldRedeclaration { .. } => "E0403",
+            CoreTypingMessage::ItemRedefinition { .. } => "E0404",
+            CoreTypingMessage::TypeMismatch { .. } => "E0405",
+            CoreTypingMessage::UniverseMismatch { .. } => "E0406",
+            CoreTypingMessage::TermHasNoType { .. } => "E0407",
+            CoreTypingMessage::NotAFunction { .. } => "E0408",
+            CoreTypingMessage::FieldNotFound { .. } => "E0409",
+            CoreTypingMessage::AmbiguousTerm { .. } => "E0410",
+            CoreTypingMessage::UnexpectedArrayTerm { .. } => "E0411",
+            CoreTypingMessage::DuplicateStructFields { .. } => "E0412",
+            CoreTypingMessage::MissingStructFields { .. } => "E0413",
+            CoreTypingMessage::UnexpectedStructFields { .. } => "E0414",
+            CoreTypingMessage::UnexpectedStructTerm { .. } => "E0415",
+        }
+    }
+
     pub fn to_diagnostic<'a, D>(&'a self, pretty_alloc: &'a D) -> Diagnostic<FileId>
     where
         D: DocAllocator<'a>,
@@ -595,6 +717,11 @@ pub enum SurfaceToCoreMessage {
         found_location: Location,
         original_location: Location,
     },
+    FieldRedefinition {
+        name: String,
+        found_location: Location,
+        original_location: Location,
+    },
     ItemRedefinition {
         name: String,
         found_location: Location,
@@ -628,6 +755,7 @@ pub enum SurfaceToCoreMessage {
     VarNameNotFound {
         name: String,
         name_location: Location,
+        suggested_names: Vec<String>,
     },
     MismatchedArrayLength {
         term_location: Location,
@@ -658,11 +786,22 @@ pub enum SurfaceToCoreMessage {
     NoDefaultPattern {
         match_location: Location,
     },
+    EmptyMatch {
+        match_location: Location,
+    },
     UnreachablePattern {
         pattern_location: Location,
     },
-    DuplicateStructFields {
-        duplicate_labels: Vec<Located<String>>,
+    MismatchedPatternArity {
+        pattern_location: Location,
+        expected_count: usize,
+        found_count: usize,
+    },
+    BranchSizeMismatch {
+        if_true_location: Location,
+        if_true_size: BigInt,
+        if_false_location: Location,
+        if_false_size: BigInt,
     },
     MissingStructFields {
         term_location: Location,
@@ -676,9 +815,90 @@ pub enum SurfaceToCoreMessage {
         term_location: Location,
         expected_type: surface::Term,
     },
+    UnknownAttribute {
+        name: String,
+        name_location: Location,
+    },
+    ExpectedSizeMismatch {
+        name: String,
+        attribute_location: Location,
+        expected_size: BigInt,
+        found_size: Option<BigInt>,
+    },
+    MismatchedConcatElemTypes {
+        term_location: Location,
+        first_elem_type: surface::Term,
+        second_elem_type: surface::Term,
+    },
+    InvalidFlattenedField {
+        field_location: Location,
+    },
+    ExpectedFormatFoundType {
+        term_location: Location,
+        found_type: surface::Term,
+        suggested_formats: Vec<String>,
+    },
+    ZeroAlignment {
+        term_location: Location,
+    },
+    NonIntegerSentinelElem {
+        term_location: Location,
+        elem_type: surface::Term,
+    },
+    IndexDependentElemRepr {
+        term_location: Location,
+        elem_type: surface::Term,
+    },
+    InvalidWindowSize {
+        term_location: Location,
+        size: BigInt,
+        len: Option<BigInt>,
+    },
 }
 
 impl SurfaceToCoreMessage {
+    /// The stable error code of this message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SurfaceToCoreMessage::MissingStructAnnotation { .. } => "E0500",
+            SurfaceToCoreMessage::InvalidStructAnnotation { .. } => "E0501",
+            SurfaceToCoreMessage::FieldRedeclaration { .. } => "E0502",
+            SurfaceToCoreMessage::FieldRedefinition { .. } => "E0503",
+            SurfaceToCoreMessage::ItemRedefinition { .. } => "E0504",
+            SurfaceToCoreMessage::TypeMismatch { .. } => "E0505",
+            SurfaceToCoreMessage::UniverseMismatch { .. } => "E0506",
+            SurfaceToCoreMessage::TermHasNoType { .. } => "E0507",
+            SurfaceToCoreMessage::NotAFunction { .. } => "E0508",
+            SurfaceToCoreMessage::FieldNotFound { .. } => "E0509",
+            SurfaceToCoreMessage::AmbiguousMatchExpression { .. } => "E0510",
+            SurfaceToCoreMessage::VarNameNotFound { .. } => "E0511",
+            SurfaceToCoreMessage::MismatchedArrayLength { .. } => "E0512",
+            SurfaceToCoreMessage::UnexpectedSequenceTerm { .. } => "E0513",
+            SurfaceToCoreMessage::NumericLiteralNotSupported { .. } => "E0514",
+            SurfaceToCoreMessage::AmbiguousSequenceTerm { .. } => "E0515",
+            SurfaceToCoreMessage::AmbiguousNumericLiteral { .. } => "E0516",
+            SurfaceToCoreMessage::AmbiguousStructTerm { .. } => "E0517",
+            SurfaceToCoreMessage::UnsupportedPatternType { .. } => "E0518",
+            SurfaceToCoreMessage::NoDefaultPattern { .. } => "E0519",
+            SurfaceToCoreMessage::UnreachablePattern { .. } => "E0520",
+            SurfaceToCoreMessage::BranchSizeMismatch { .. } => "E0521",
+            SurfaceToCoreMessage::MissingStructFields { .. } => "E0522",
+            SurfaceToCoreMessage::UnexpectedStructFields { .. } => "E0523",
+            SurfaceToCoreMessage::UnexpectedStructTerm { .. } => "E0524",
+            SurfaceToCoreMessage::UnknownAttribute { .. } => "E0525",
+            SurfaceToCoreMessage::ExpectedSizeMismatch { .. } => "E0526",
+            SurfaceToCoreMessage::MismatchedPatternArity { .. } => "E0527",
+            SurfaceToCoreMessage::MismatchedConcatElemTypes { .. } => "E0528",
+            SurfaceToCoreMessage::InvalidFlattenedField { .. } => "E0529",
+            SurfaceToCoreMessage::ExpectedFormatFoundType { .. } => "E0530",
+            SurfaceToCoreMessage::EmptyMatch { .. } => "E0531",
+            SurfaceToCoreMessage::ZeroAlignment { .. } => "E0532",
+            SurfaceToCoreMessage::NonIntegerSentinelElem { .. } => "E0533",
+            SurfaceToCoreMessage::IndexDependentElemRepr { .. } => "E0534",
+            SurfaceToCoreMessage::InvalidWindowSize { .. } => "E0535",
+        }
+    }
+
     pub fn to_diagnostic<'a, D>(&'a self, pretty_alloc: &'a D) -> Diagnostic<FileId>
     where
         D: DocAllocator<'a>,
@@ -727,6 +947,20 @@ impl SurfaceToCoreMessage {
                     secondary(original_location) = "previous field declaration here",
                 ])
                 .with_notes(vec![format!("`{}` must be defined only per struct", name)]),
+            SurfaceToCoreMessage::FieldRedefinition {
+                name,
+                found_location,
+                original_location,
+            } => Diagnostic::error()
+                .with_message(format!("field `{}` is already defined", name))
+                .with_labels(labels![
+                    primary(found_location) = "field already defined",
+                    secondary(original_location) = "previous field definition here",
+                ])
+                .with_notes(vec![format!(
+                    "`{}` must be defined only once per struct term",
+                    name
+                )]),
             SurfaceToCoreMessage::ItemRedefinition {
                 name,
                 found_location,
@@ -836,9 +1070,21 @@ impl SurfaceToCoreMessage {
             SurfaceToCoreMessage::VarNameNotFound {
                 name,
                 name_location,
+                suggested_names,
             } => Diagnostic::error()
                 .with_message(format!("cannot find `{}` in this scope", name))
-                .with_labels(labels![primary(name_location) = "not found in this scope"]),
+                .with_labels(labels![primary(name_location) = "not found in this scope"])
+                .with_notes(match suggested_names.as_slice() {
+                    [] => Vec::new(),
+                    [suggested_name] => vec![format!("help: did you mean `{}`?", suggested_name)],
+                    suggested_names => vec![format!(
+                        "help: did you mean one of: {}?",
+                        suggested_names
+                            .iter()
+                            .map(|name| format!("`{}`", name))
+                            .format(", "),
+                    )],
+                }),
             SurfaceToCoreMessage::MismatchedArrayLength {
                 term_location,
                 found_len,
@@ -923,19 +1169,156 @@ impl SurfaceToCoreMessage {
             SurfaceToCoreMessage::NoDefaultPattern { match_location } => Diagnostic::error()
                 .with_message("non-exhaustive patterns")
                 .with_labels(labels![primary(match_location) = "missing default pattern"]),
+            SurfaceToCoreMessage::EmptyMatch { match_location } => Diagnostic::error()
+                .with_message("match expression has no branches")
+                .with_labels(labels![primary(match_location) = "no branches"])
+                .with_notes(vec![
+                    "empty matches are only valid over uninhabited types, \
+                     which are not yet supported by patterns"
+                        .to_owned(),
+                    "help: add a branch with a default pattern, for example `_ => ...`".to_owned(),
+                ]),
+            SurfaceToCoreMessage::ZeroAlignment { term_location } => Diagnostic::error()
+                .with_message("alignment of zero bytes")
+                .with_labels(labels![primary(term_location) = "cannot align to zero bytes"])
+                .with_notes(vec![
+                    "help: use an alignment of `1` if no padding is required".to_owned(),
+                ]),
+            SurfaceToCoreMessage::NonIntegerSentinelElem {
+                term_location,
+                elem_type,
+            } => Diagnostic::error()
+                .with_message("sentinel-terminated list of non-integer elements")
+                .with_labels(labels![
+                    primary(term_location) = format!(
+                        "expected elements represented by `Int`, found `{}`",
+                        to_doc(elem_type).pretty(usize::MAX),
+                    ),
+                ])
+                .with_notes(vec![
+                    "the predicate that finds the sentinel can only test integers".to_owned(),
+                ]),
+            SurfaceToCoreMessage::IndexDependentElemRepr {
+                term_location,
+                elem_type,
+            } => Diagnostic::error()
+                .with_message("element representation depends on the index of the element")
+                .with_labels(labels![
+                    primary(term_location) = format!(
+                        "elements represented by `{}`",
+                        to_doc(elem_type).pretty(usize::MAX),
+                    ),
+                ])
+                .with_notes(vec![
+                    "every element of an array must have the same representation".to_owned(),
+                ]),
+            SurfaceToCoreMessage::InvalidWindowSize {
+                term_location,
+                size,
+                len,
+            } => Diagnostic::error()
+                .with_message("invalid window size")
+                .with_labels(labels![
+                    primary(term_location) = match len {
+                        None => "windows must contain at least one element".to_owned(),
+                        Some(len) => format!(
+                            "windows of {} elements do not fit in an array of {} elements",
+                            size, len,
+                        ),
+                    },
+                ]),
             SurfaceToCoreMessage::UnreachablePattern { pattern_location } => Diagnostic::warning()
                 .with_message("unreachable pattern")
                 .with_labels(labels![primary(pattern_location) = "unreachable pattern"]),
-            SurfaceToCoreMessage::DuplicateStructFields { duplicate_labels } => Diagnostic::error()
-                .with_message("duplicate fields found in struct")
-                .with_labels(
-                    duplicate_labels
-                        .iter()
-                        .flat_map(|label| {
-                            label!(primary(&label.location) = "field already defined")
-                        })
-                        .collect(),
-                ),
+            SurfaceToCoreMessage::MismatchedPatternArity {
+                pattern_location,
+                expected_count,
+                found_count,
+            } => Diagnostic::error()
+                .with_message("mismatched number of patterns")
+                .with_labels(labels![
+                    primary(pattern_location) = format!(
+                        "expected {} patterns, found {}",
+                        expected_count, found_count,
+                    )
+                ])
+                .with_notes(vec![
+                    format!("the match has {} scrutinees", expected_count,),
+                ]),
+            SurfaceToCoreMessage::MismatchedConcatElemTypes {
+                term_location,
+                first_elem_type,
+                second_elem_type,
+            } => {
+                let first_elem_type = to_doc(first_elem_type);
+                let second_elem_type = to_doc(second_elem_type);
+
+                Diagnostic::error()
+                    .with_message("mismatched element types in concatenated arrays")
+                    .with_labels(labels![
+                        primary(term_location) = format!(
+                            "expected `{}`, found `{}`",
+                            first_elem_type.pretty(std::usize::MAX),
+                            second_elem_type.pretty(std::usize::MAX),
+                        ),
+                    ])
+                    .with_notes(vec![[
+                        format!("expected `{}`", first_elem_type.pretty(std::usize::MAX)),
+                        format!("   found `{}`", second_elem_type.pretty(std::usize::MAX)),
+                    ]
+                    .join("\n")])
+            }
+            SurfaceToCoreMessage::InvalidFlattenedField { field_location } => Diagnostic::error()
+                .with_message("cannot flatten field")
+                .with_labels(labels![primary(field_location) = "expected a struct format"])
+                .with_notes(vec![format!(
+                    "only fields of struct formats whose formats are struct formats can be flattened"
+                )]),
+            SurfaceToCoreMessage::ExpectedFormatFoundType {
+                term_location,
+                found_type,
+                suggested_formats,
+            } => {
+                let found_type = to_doc(found_type);
+
+                Diagnostic::error()
+                    .with_message("expected a format, found a host type")
+                    .with_labels(labels![
+                        primary(term_location) = format!(
+                            "expected `Format`, found the type `{}`",
+                            found_type.pretty(std::usize::MAX),
+                        ),
+                    ])
+                    .with_notes(vec![
+                        format!(
+                            "the fields of struct formats describe how their values are read, \
+                             so they must be formats rather than types"
+                        ),
+                        format!(
+                            "help: use a format that is represented as `{}`, such as {}",
+                            found_type.pretty(std::usize::MAX),
+                            suggested_formats
+                                .iter()
+                                .map(|name| format!("`{}`", name))
+                                .format(", "),
+                        ),
+                    ])
+            }
+            SurfaceToCoreMessage::BranchSizeMismatch {
+                if_true_location,
+                if_true_size,
+                if_false_location,
+                if_false_size,
+            } => Diagnostic::warning()
+                .with_message("branches of format have different sizes")
+                .with_labels(labels![
+                    primary(if_true_location) = format!("this branch reads {} bytes", if_true_size),
+                    primary(if_false_location) =
+                        format!("this branch reads {} bytes", if_false_size),
+                ])
+                .with_notes(vec![format!(
+                    "the offsets of subsequent data will depend on which branch is taken"
+                )]),
             SurfaceToCoreMessage::MissingStructFields {
                 term_location,
                 missing_labels,
@@ -983,6 +1366,37 @@ impl SurfaceToCoreMessage {
                         ),
                     ])
             }
+            SurfaceToCoreMessage::UnknownAttribute {
+                name,
+                name_location,
+            } => Diagnostic::error()
+                .with_message(format!("unknown attribute `{}`", name))
+                .with_labels(labels![primary(name_location) = "unknown attribute"])
+                .with_notes(vec![format!("expected `expected_size`")]),
+            SurfaceToCoreMessage::ExpectedSizeMismatch {
+                name,
+                attribute_location,
+                expected_size,
+                found_size,
+            } => match found_size {
+                Some(found_size) => Diagnostic::error()
+                    .with_message(format!("unexpected size for format `{}`", name))
+                    .with_labels(labels![
+                        primary(attribute_location) = format!(
+                            "expected {} bytes, found {} bytes",
+                            expected_size, found_size,
+                        ),
+                    ]),
+                None => Diagnostic::error()
+                    .with_message(format!("unknown size for format `{}`", name))
+                    .with_labels(labels![
+                        primary(attribute_location) = format!("expected {} bytes", expected_size),
+                    ])
+                    .with_notes(vec![format!(
+                        "the size of `{}` could not be computed during elaboration",
+                        name,
+                    )]),
+            },
         }
     }
 }
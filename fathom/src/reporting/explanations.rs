@@ -0,0 +1,633 @@
+//! Extended explanations for diagnostic codes.
+
+/// Look up the extended explanation for a diagnostic code, eg. `E0505`.
+pub fn explain(code: &str) -> Option<&'static str> {
+    match code {
+        "E0001" => Some(E0001),
+        "E0002" => Some(E0002),
+        "E0003" => Some(E0003),
+        "E0100" => Some(E0100),
+        "E0200" | "E0201" | "E0202" => Some(E0200),
+        "E0300" | "E0301" | "E0302" | "E0303" | "E0304" | "E0305" => Some(E0300),
+        "E0306" | "E0307" => Some(E0306),
+        "E0308" => Some(E0308),
+        "E0309" | "E0310" => Some(E0309),
+        "E0400" | "E0401" | "E0402" | "E0403" | "E0404" | "E0405" | "E0406" | "E0407" | "E0408"
+        | "E0409" | "E0410" | "E0411" | "E0412" | "E0413" | "E0414" | "E0415" => Some(E0400),
+        "E0500" => Some(E0500),
+        "E0501" => Some(E0501),
+        "E0502" => Some(E0502),
+        "E0503" => Some(E0503),
+        "E0504" => Some(E0504),
+        "E0505" => Some(E0505),
+        "E0506" => Some(E0506),
+        "E0507" => Some(E0507),
+        "E0508" => Some(E0508),
+        "E0509" => Some(E0509),
+        "E0510" => Some(E0510),
+        "E0511" => Some(E0511),
+        "E0512" => Some(E0512),
+        "E0513" => Some(E0513),
+        "E0514" => Some(E0514),
+        "E0515" => Some(E0515),
+        "E0516" => Some(E0516),
+        "E0517" => Some(E0517),
+        "E0518" => Some(E0518),
+        "E0519" => Some(E0519),
+        "E0520" => Some(E0520),
+        "E0521" => Some(E0521),
+        "E0522" => Some(E0522),
+        "E0523" => Some(E0523),
+        "E0524" => Some(E0524),
+        "E0525" => Some(E0525),
+        "E0526" => Some(E0526),
+        "E0527" => Some(E0527),
+        "E0528" => Some(E0528),
+        "E0529" => Some(E0529),
+        "E0530" => Some(E0530),
+        "E0531" => Some(E0531),
+        "E0532" => Some(E0532),
+        "E0533" => Some(E0533),
+        "E0534" => Some(E0534),
+        "E0535" => Some(E0535),
+        _ => None,
+    }
+}
+
+const E0001: &str = r#"A language feature that has not been implemented yet was used.
+
+This is reported when the compiler recognises a construct, but does not yet
+know how to elaborate or interpret it. Rewriting the description without the
+feature is the only workaround for now.
+"#;
+
+const E0002: &str = r#"A file could not be read.
+
+The path passed on the command line must point to a readable file, for example:
+
+```text
+fathom check examples/stl.fathom
+```
+"#;
+
+const E0003: &str = r#"Writing the data back out did not reproduce the original bytes.
+
+This is reported by `fathom data --round-trip` when a format discards
+information while reading. For example, `FormatAsciiLine` strips the line
+terminator, so `"abc\r\n"` is written back out as `"abc\n"`:
+
+```fathom
+struct Main : Format {
+    line : FormatAsciiLine,
+}
+```
+"#;
+
+const E0100: &str = r#"The source contains a character that does not start a valid token.
+
+Erroneous code example:
+
+```fathom
+const x : Int = 1 $ 2;
+```
+
+Remove the stray character, or replace it with the intended operator.
+"#;
+
+const E0200: &str = r#"The source could not be parsed.
+
+This is reported when the parser encounters the end of the file, or a token
+that it did not expect. The most common cause is a missing or extra delimiter:
+
+```fathom
+struct Point : Type {
+    x : Int,
+    y : Int,
+// missing closing brace
+```
+"#;
+
+const E0300: &str = r#"A numeric literal is malformed.
+
+Numeric literals must start with a digit, and may only contain digits that are
+valid in their base, along with `_` digit separators:
+
+```fathom
+const x : Int = 0b102; // `2` is not a base 2 digit
+```
+"#;
+
+const E0306: &str = r#"A float literal uses syntax that is not yet supported.
+
+Float literals must be written in base 10 without an exponent:
+
+```fathom
+const x : F32 = 1e3; // error
+const y : F32 = 1000.0; // ok
+```
+"#;
+
+const E0308: &str = r#"A numeric literal ended before it was complete.
+
+A base prefix must be followed by at least one digit:
+
+```fathom
+const x : Int = 0x; // error
+const y : Int = 0x0; // ok
+```
+"#;
+
+const E0309: &str = r#"A string literal is malformed.
+
+String literals are enclosed in double quotes, and may contain the escape
+sequences `\\`, `\"`, `\'`, `\n`, `\r`, `\t`, `\0`, and `\u{...}`, where the
+braces contain the hexadecimal code of a Unicode scalar value:
+
+```fathom
+const x : String = "tab:\q"; // error
+const y : String = "tab:\t"; // ok
+```
+"#;
+
+const E0400: &str = r#"The core language failed to type check.
+
+These errors are reported when validating the elaborated core language, which
+is only done when the `--validate-core` flag is passed. Well-typed surface
+descriptions should never produce them, so they indicate a bug in the
+compiler. Please report them, along with the description that caused them.
+"#;
+
+const E0500: &str = r#"A struct definition is missing a type annotation.
+
+Structs must be annotated with the universe they belong to:
+
+```fathom
+struct Point { // error
+    x : Int,
+}
+
+struct Point : Type { // ok
+    x : Int,
+}
+```
+"#;
+
+const E0501: &str = r#"A struct definition has an invalid type annotation.
+
+Struct definitions may only be annotated with `Type` or `Format`:
+
+```fathom
+struct Point : Int {} // error
+struct Point : Type {} // ok
+```
+"#;
+
+const E0502: &str = r#"A field was declared more than once in a struct definition.
+
+Each field name may only appear once per struct definition:
+
+```fathom
+struct Point : Type {
+    x : Int,
+    x : Int, // error
+}
+```
+"#;
+
+const E0503: &str = r#"A field was defined more than once in a struct term.
+
+Each field name may only appear once per struct term:
+
+```fathom
+struct Point : Type {
+    x : Int,
+}
+
+const origin : Point = struct {
+    x = 0,
+    x = 0, // error
+};
+```
+"#;
+
+const E0504: &str = r#"An item was defined more than once in a module.
+
+Item names must be unique within a module:
+
+```fathom
+const x : Int = 1;
+const x : Int = 2; // error
+```
+"#;
+
+const E0505: &str = r#"A term did not have the type it was expected to have.
+
+Erroneous code example:
+
+```fathom
+const x : Int = true;
+```
+
+Either change the term, or change the type annotation to match it.
+"#;
+
+const E0506: &str = r#"A type was expected to live in a different universe.
+
+For example, struct formats must be used where a `Format` is expected, and
+host types where a `Type` is expected:
+
+```fathom
+struct Point : Type {
+    x : Int,
+}
+
+struct Main : Format {
+    point : Point, // error: `Point` is a `Type`, not a `Format`
+}
+```
+"#;
+
+const E0507: &str = r#"A term was used that has no type.
+
+The universe `Kind` sits at the top of the universe hierarchy, and so can not be
+given a type:
+
+```fathom
+const TestKind = Kind;
+```
+"#;
+
+const E0508: &str = r#"A term was applied to arguments, but it is not a function.
+
+Erroneous code example:
+
+```fathom
+const x : Int = 1 2;
+```
+"#;
+
+const E0509: &str = r#"A field was accessed that does not exist.
+
+Erroneous code example:
+
+```fathom
+struct Point : Type {
+    x : Int,
+}
+
+const origin : Point = struct { x = 0 };
+const y : Int = origin.y;
+```
+"#;
+
+const E0510: &str = r#"The type of a match expression could not be inferred.
+
+Add a type annotation to the item containing the match expression:
+
+```fathom
+const x = match 1 : Int { _ => 0 }; // error
+const x : Int = match 1 : Int { _ => 0 }; // ok
+```
+"#;
+
+const E0511: &str = r#"A name was used that is not in scope.
+
+Erroneous code example:
+
+```fathom
+const x : Int = y;
+```
+
+Check the spelling of the name, or define it before using it.
+"#;
+
+const E0512: &str = r#"An array term has the wrong number of elements.
+
+The number of elements must match the length given in the array type:
+
+```fathom
+const xs : Array 3 Int = [1, 2]; // error
+```
+"#;
+
+const E0513: &str = r#"A sequence term was used where a non-sequence type was expected.
+
+Sequence terms can only be used for `Array`s and `List`s:
+
+```fathom
+const x : Int = [1, 2, 3];
+```
+"#;
+
+const E0514: &str = r#"A numeric literal was used where a non-numeric type was expected.
+
+Erroneous code example:
+
+```fathom
+const x : Bool = 33;
+```
+"#;
+
+const E0515: &str = r#"The type of a sequence term could not be inferred.
+
+Add a type annotation to the sequence term:
+
+```fathom
+const xs = [1, 2, 3]; // error
+const xs : Array 3 Int = [1, 2, 3]; // ok
+```
+"#;
+
+const E0516: &str = r#"The type of a numeric literal could not be inferred.
+
+Add a type annotation to the numeric literal:
+
+```fathom
+const x : Bool = match 33 : Int {
+    42 => true,
+    _ => false,
+};
+```
+"#;
+
+const E0517: &str = r#"The type of a struct term could not be inferred.
+
+Add a type annotation to the struct term:
+
+```fathom
+struct Point : Type {
+    x : Int,
+}
+
+const origin = struct { x = 0 }; // error
+const origin : Point = struct { x = 0 }; // ok
+```
+"#;
+
+const E0518: &str = r#"A pattern was used to match on a type that does not support it.
+
+Numeric literal patterns can only be used when matching on integers:
+
+```fathom
+const x : Int = match true {
+    1 => 0, // error
+    _ => 1,
+};
+```
+"#;
+
+const E0519: &str = r#"A match expression does not cover every possible value.
+
+Add a default pattern to handle the remaining values:
+
+```fathom
+const x : Int = match 23 : Int {
+    23 => 42,
+    _ => 0,
+};
+```
+"#;
+
+const E0520: &str = r#"A match branch can never be reached.
+
+Branches that follow a default pattern will never be selected:
+
+```fathom
+const x : Int = match 23 : Int {
+    _ => 0,
+    23 => 42, // warning
+};
+```
+"#;
+
+const E0521: &str = r#"The branches of a conditional format read different numbers of bytes.
+
+This warning is reported when the sizes of both branches are known, and differ.
+This is often a mistake, as the size of the format then depends on the
+condition:
+
+```fathom
+struct Data (is_large : Bool) : Format {
+    value : if is_large { U32Be } else { U16Be }, // warning
+}
+```
+"#;
+
+const E0522: &str = r#"A struct term is missing some of the fields of its type.
+
+Every field declared in the struct definition must be given a value:
+
+```fathom
+struct Point : Type {
+    x : Int,
+    y : Int,
+}
+
+const origin : Point = struct { x = 0 }; // error: missing `y`
+```
+"#;
+
+const E0523: &str = r#"A struct term has fields that are not in its type.
+
+Only the fields declared in the struct definition may be given values:
+
+```fathom
+struct Point : Type {
+    x : Int,
+}
+
+const origin : Point = struct { x = 0, y = 0 }; // error: unexpected `y`
+```
+"#;
+
+const E0524: &str = r#"A struct term was used where a non-struct type was expected.
+
+Erroneous code example:
+
+```fathom
+const x : Int = struct { x = 0 };
+```
+"#;
+
+const E0525: &str = r#"An attribute was used that is not supported.
+
+The only attribute that is currently supported is `expected_size`:
+
+```fathom
+#[expected_size = 4]
+struct Point : Format { // ok
+    x : U16Be,
+    y : U16Be,
+}
+
+#[size = 4] // error
+struct Size : Format {
+    width : U16Be,
+    height : U16Be,
+}
+```
+"#;
+
+const E0526: &str = r#"The size of a format did not match its `expected_size` attribute.
+
+The `expected_size` attribute records the number of bytes that a struct format
+is expected to read. This error is reported if the size of the format differs,
+or if it could not be computed during elaboration, for example if the format
+contains arrays whose lengths depend on the data:
+
+```fathom
+#[expected_size = 8]
+struct Point : Format { // error: found 4 bytes
+    x : U16Be,
+    y : U16Be,
+}
+
+#[expected_size = 4]
+struct Data : Format { // error: the size of `Data` depends on `len`
+    len : U8,
+    data : FormatArray len U8,
+}
+```
+"#;
+
+const E0527: &str = r#"A pattern did not have one element for each scrutinee of a match.
+
+When matching on multiple scrutinees, each pattern must either be a tuple with
+one element per scrutinee, or a single name that matches any combination of
+values:
+
+```fathom
+const x : Int = match 1 : Int, 2 : Int {
+    (1, 2) => 3,
+    (1, 2, 3) => 4, // error: expected 2 patterns, found 3
+    _ => 5,
+};
+```
+"#;
+
+const E0528: &str = r#"Arrays with different element types were concatenated.
+
+The formats passed to `FormatArrayConcat` must both be represented as arrays
+of the same type of element:
+
+```fathom
+struct Table : Format {
+    ids : FormatArrayConcat (FormatArray 2 U16Be) (FormatArray 2 U16Le),
+    data : FormatArrayConcat (FormatArray 2 U16Be) (FormatArray 2 F32Be), // error
+}
+```
+"#;
+
+const E0529: &str = r#"A field was flattened, but its format was not a struct format.
+
+Flattening splices the fields of a struct format into the enclosing struct
+format, so it can only be used on the fields of struct formats, and only when
+the format of the field is itself a struct format:
+
+```fathom
+struct Header : Format {
+    width : U16Be,
+    height : U16Be,
+}
+
+struct Image : Format {
+    flatten header : Header,
+    flatten len : U8, // error: expected a struct format
+}
+```
+"#;
+
+const E0530: &str = r#"A host type was used as the format of a struct format field.
+
+The fields of struct formats must be formats, which describe how the value of
+the field is read from the binary data. Host types like `Int` and `F32` only
+describe how values are represented once they have been read, so a format that
+is represented by the type should be used instead:
+
+```fathom
+struct Header : Format {
+    width : Int, // error: expected a format, found a host type
+    height : U16Be,
+}
+```
+"#;
+
+const E0531: &str = r#"A match expression has no branches.
+
+A match without any branches could only be valid if its scrutinee had no
+possible values, and patterns do not support uninhabited types yet. Add a
+branch with a default pattern instead:
+
+```fathom
+const x : Int = match 23 : Int {}; // error
+
+const y : Int = match 23 : Int {
+    _ => 0,
+};
+```
+"#;
+
+const E0532: &str = r#"A format was aligned to a multiple of zero bytes.
+
+Positions can only be aligned to a positive number of bytes. An alignment of
+one byte never requires any padding:
+
+```fathom
+struct Chunk : Format {
+    tag : U8,
+    padding : FormatAlign 0, // error: cannot align to zero bytes
+    len : U32Be,
+}
+```
+"#;
+
+const E0533: &str = r#"A sentinel-terminated list was given elements that are not integers.
+
+The predicate passed to `FormatRepeatUntil` and `FormatRepeatUntilInclusive`
+has the type `Int -> Bool`, so the elements of the list must be represented by
+`Int`:
+
+```fathom
+struct Point : Format {
+    x : U16Be,
+    y : U16Be,
+}
+
+struct Points : Format {
+    points : FormatRepeatUntil (IntEq 0) Point, // error: `Point` is a struct
+    offsets : FormatRepeatUntil (IntEq 0) U16Be,
+}
+```
+"#;
+
+const E0534: &str = r#"An array was given elements whose representations depend on their index.
+
+Every element of an array has the same representation, so the element format
+passed to `FormatArrayMapIndex` can only use the index in ways that do not
+change the representation, like the position of a link:
+
+```fathom
+struct Entry (index : Int) : Format {
+    data : FormatArray index U8,
+}
+
+struct Table : Format {
+    count : U8,
+    entries : FormatArrayMapIndex count Entry, // error: `data` depends on the index
+}
+```
+"#;
+
+const E0535: &str = r#"An array was split into windows that are empty or longer than the array.
+
+Each window passed to `FormatArrayWindows` must contain at least one element,
+and can contain at most every element of the array:
+
+```fathom
+struct Samples : Format {
+    empty : FormatArrayWindows 0 (FormatArray 4 U8), // error: empty windows
+    wide : FormatArrayWindows 5 (FormatArray 4 U8), // error: 5 elements don't fit in 4
+    pairs : FormatArrayWindows 2 (FormatArray 4 U8),
+}
+```
+"#;
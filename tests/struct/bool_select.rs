@@ -0,0 +1,44 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/bool_select.core.fathom");
+
+#[test]
+fn valid_data() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Data::small[0]
+    writer.write::<U8>(2); // Data::large[0]
+    writer.write::<U8>(3); // Data::large[1]
+    writer.write::<U8>(4); // (unread)
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Data").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                (
+                    "small".to_owned(),
+                    Arc::new(Value::ArrayTerm(vec![Arc::new(Value::int(1))])),
+                ),
+                (
+                    "large".to_owned(),
+                    Arc::new(Value::ArrayTerm(vec![
+                        Arc::new(Value::int(2)),
+                        Arc::new(Value::int(3)),
+                    ])),
+                ),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
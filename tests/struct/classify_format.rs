@@ -0,0 +1,32 @@
+#![cfg(test)]
+
+use fathom_test_util::fathom::lang::core::semantics::FormatClass;
+use fathom_test_util::fathom::lang::core::{self, binary};
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/classify_format.core.fathom");
+
+#[test]
+fn classify_items() {
+    let globals = core::Globals::default();
+    let read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let classify = |name: &str| read_context.classify_item(name);
+
+    assert_eq!(classify("Header"), Some(FormatClass::Struct("Header".to_owned())));
+    assert_eq!(classify("Alias"), Some(FormatClass::Struct("Header".to_owned())));
+    assert_eq!(classify("Magic"), Some(FormatClass::Scalar("U32Be".to_owned())));
+    assert_eq!(classify("Weight"), Some(FormatClass::Scalar("F32Le".to_owned())));
+    assert_eq!(classify("Headers"), Some(FormatClass::Array("FormatArray".to_owned())));
+    assert_eq!(classify("Name"), Some(FormatClass::Array("FormatRepeatUntil".to_owned())));
+    assert_eq!(classify("Extra"), Some(FormatClass::Other("FormatPresentIf".to_owned())));
+}
+
+#[test]
+fn classify_non_formats() {
+    let globals = core::Globals::default();
+    let read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    assert_eq!(read_context.classify_item("Point"), None);
+    assert_eq!(read_context.classify_item("count"), None);
+    assert_eq!(read_context.classify_item("Missing"), None);
+}
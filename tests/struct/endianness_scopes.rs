@@ -0,0 +1,41 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, I16Be, I32Le, U16Be, U16Le, U32Be};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/endianness_scopes.core.fathom");
+
+#[test]
+fn valid_header() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(640); // Header::width
+    writer.write::<U32Be>(480); // Header::height
+    writer.write::<U16Le>(0x0102); // Header::flags
+    writer.write::<I32Le>(-16); // Header::offset
+    writer.write::<I16Be>(-24); // Header::depth
+    writer.write::<U16Le>(0xBEEF); // Header::checksum
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Header").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("width".to_owned(), Arc::new(Value::int(640))),
+                ("height".to_owned(), Arc::new(Value::int(480))),
+                ("flags".to_owned(), Arc::new(Value::int(0x0102))),
+                ("offset".to_owned(), Arc::new(Value::int(-16))),
+                ("depth".to_owned(), Arc::new(Value::int(-24))),
+                ("checksum".to_owned(), Arc::new(Value::int(0xBEEF))),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
@@ -0,0 +1,95 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U16Le};
+use fathom_test_util::fathom::lang::core::semantics::{self, Value};
+use fathom_test_util::fathom::lang::core::{self, binary, ItemData};
+use std::collections::{BTreeMap, HashMap};
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/f16.core.fathom");
+
+#[test]
+fn valid_weights() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(0x3C00); // Weights::big
+    writer.write::<U16Le>(0xC000); // Weights::little
+
+    assert_eq!(writer.buffer(), &[0x3C, 0x00, 0x00, 0xC0]);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let weights = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("big".to_owned(), Arc::new(Value::f16(0x3C00))),
+        ("little".to_owned(), Arc::new(Value::f16(0xC000))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Weights").unwrap(),
+        (weights.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Weights", &weights)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn nan_weights() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(0x7E00); // Weights::big
+    writer.write::<U16Le>(0xFE01); // Weights::little
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    // NaNs are equal to each other, regardless of their payloads
+    let weights = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("big".to_owned(), Arc::new(Value::f16(0x7E00))),
+        ("little".to_owned(), Arc::new(Value::f16(0x7E00))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Weights").unwrap(),
+        (weights, Vec::new()),
+    );
+}
+
+#[test]
+fn conversions() {
+    let globals = core::Globals::default();
+    let mut items = HashMap::new();
+
+    for item in &FIXTURE.items {
+        if let ItemData::Constant(constant) = &item.data {
+            let value = semantics::eval(&globals, &items, &mut core::Locals::new(), &constant.term);
+            let item_data = semantics::ItemData::Constant(value);
+            items.insert(constant.name.clone(), semantics::Item::new(item.location, item_data));
+        }
+    }
+
+    let constant_value = |name: &str| match &items[name].data {
+        semantics::ItemData::Constant(value) => value.clone(),
+        _ => panic!("expected constant"),
+    };
+
+    assert!(semantics::is_equal(
+        &globals,
+        &items,
+        &constant_value("half"),
+        &Value::f16(0x3800),
+    ));
+    assert!(semantics::is_equal(
+        &globals,
+        &items,
+        &constant_value("half_widened"),
+        &Value::f32(0.5),
+    ));
+}
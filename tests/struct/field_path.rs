@@ -0,0 +1,111 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U32Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::{self, Value};
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::HashMap;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/field_path.core.fathom");
+
+#[test]
+fn valid_nested_field() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U32Be>(0xCAFE_F00D); // Main::header::magic
+    writer.write::<U16Be>(2); //           Main::header::version
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let version = read_context
+        .read_item_field(&mut reader, "Main", &["header", "version"])
+        .unwrap();
+
+    assert!(semantics::is_equal(
+        &globals,
+        &HashMap::new(),
+        &version,
+        &Value::int(2),
+    ));
+    assert_eq!(reader.current_pos(), Some(6));
+}
+
+#[test]
+fn valid_dependent_field() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U32Be>(0xCAFE_F00D); // Main::header::magic
+    writer.write::<U16Be>(2); //           Main::header::version
+    writer.write::<U16Be>(3); //           Main::len
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item_field(&mut reader, "Main", &["data"]) {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("eof error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn invalid_missing_field() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U32Be>(0xCAFE_F00D); // Main::header::magic
+    writer.write::<U16Be>(2); //           Main::header::version
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item_field(&mut reader, "Main", &["header", "nope"]) {
+        Err(ReadError::UnknownField { label }) => assert_eq!(label, "nope"),
+        Err(err) => panic!("unknown field error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn invalid_field_of_non_struct() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U32Be>(0xCAFE_F00D); // Main::header::magic
+    writer.write::<U16Be>(2); //           Main::header::version
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item_field(&mut reader, "Main", &["header", "version", "nope"]) {
+        Err(ReadError::UnknownField { label }) => assert_eq!(label, "nope"),
+        Err(err) => panic!("unknown field error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn valid_field_after_fixed_size_field() {
+    let mut writer = FormatWriter::new(vec![]);
+    for _ in 0..1000 {
+        writer.write::<U8>(0); //          Reserved::reserved
+    }
+    writer.write::<U32Be>(0xCAFE_F00D); // Reserved::header::magic
+    writer.write::<U16Be>(2); //           Reserved::header::version
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+    // Reading the reserved bytes one at a time would exceed this limit.
+    read_context.set_step_limit(Some(10));
+
+    let version = read_context
+        .read_item_field(&mut reader, "Reserved", &["header", "version"])
+        .unwrap();
+
+    assert!(semantics::is_equal(
+        &globals,
+        &HashMap::new(),
+        &version,
+        &Value::int(2),
+    ));
+    assert_eq!(reader.current_pos(), Some(1006));
+}
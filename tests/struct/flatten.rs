@@ -0,0 +1,47 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/flatten.core.fathom");
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(2); // Table::len
+    writer.write::<U8>(0x80); // Table::flags
+    writer.write::<U8>(7); // Table::entries
+    writer.write::<U8>(9);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let table = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(2))),
+        ("flags".to_owned(), Arc::new(Value::int(0x80))),
+        (
+            "entries".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![
+                Arc::new(Value::int(7)),
+                Arc::new(Value::int(9)),
+            ])),
+        ),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (table.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Table", &table)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
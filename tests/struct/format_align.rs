@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U32Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_align.core.fathom");
+
+fn chunk(tag: u8, len: u32) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("tag".to_owned(), Arc::new(Value::int(tag))),
+        ("padding".to_owned(), Arc::new(Value::global("unit", Vec::new()))),
+        ("len".to_owned(), Arc::new(Value::int(len))),
+    ]))
+}
+
+#[test]
+fn valid_chunk() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(7); // Chunk::tag
+    writer.write::<U8>(0); // Chunk::padding
+    writer.write::<U8>(0);
+    writer.write::<U8>(0);
+    writer.write::<U32Be>(24); // Chunk::len
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Chunk").unwrap(),
+        (chunk(7, 24), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Chunk", &chunk(7, 24))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_nested_chunk() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Chunks::first::tag
+    writer.write::<U8>(0); // Chunks::first::padding
+    writer.write::<U8>(0);
+    writer.write::<U8>(0);
+    writer.write::<U32Be>(2); // Chunks::first::len
+    writer.write::<U8>(3); // Chunks::second_tag
+    for _ in 0..7 {
+        writer.write::<U8>(0); // Chunks::second_padding
+    }
+    writer.write::<U16Be>(4); // Chunks::second_len
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let chunks = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("first".to_owned(), Arc::new(chunk(1, 2))),
+        ("second_tag".to_owned(), Arc::new(Value::int(3))),
+        ("second_padding".to_owned(), Arc::new(Value::global("unit", Vec::new()))),
+        ("second_len".to_owned(), Arc::new(Value::int(4))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Chunks").unwrap(),
+        (chunks.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Chunks", &chunks)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn alignment_overflow() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(7); // Chunk::tag
+    writer.write::<U8>(0); // Chunk::padding
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Chunk") {
+        Err(ReadError::AlignmentOverflow {
+            offset: 1,
+            align: 4,
+        }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
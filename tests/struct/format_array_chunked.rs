@@ -0,0 +1,72 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_chunked.core.fathom");
+
+fn row(values: &[u16]) -> Arc<Value> {
+    let values = values.iter().map(|value| Arc::new(Value::int(*value)));
+    Arc::new(Value::ArrayTerm(values.collect()))
+}
+
+#[test]
+fn valid_matrix() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(2); // Matrix::rows
+    writer.write::<U8>(3); // Matrix::cols
+    for value in 1..=6 {
+        writer.write::<U16Be>(value); // Matrix::data
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Matrix").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("rows".to_owned(), Arc::new(Value::int(2))),
+                ("cols".to_owned(), Arc::new(Value::int(3))),
+                (
+                    "data".to_owned(),
+                    Arc::new(Value::ArrayTerm(vec![row(&[1, 2, 3]), row(&[4, 5, 6])])),
+                ),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
+
+#[test]
+fn valid_empty_rows() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(2); // Matrix::rows
+    writer.write::<U8>(0); // Matrix::cols
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Matrix").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("rows".to_owned(), Arc::new(Value::int(2))),
+                ("cols".to_owned(), Arc::new(Value::int(0))),
+                (
+                    "data".to_owned(),
+                    Arc::new(Value::ArrayTerm(vec![row(&[]), row(&[])])),
+                ),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
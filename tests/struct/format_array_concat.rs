@@ -0,0 +1,51 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U16Le, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_concat.core.fathom");
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); // Table::len
+    writer.write::<U16Be>(1); // Table::ids
+    writer.write::<U16Be>(2);
+    writer.write::<U16Le>(3);
+    writer.write::<U16Le>(4);
+    writer.write::<U16Le>(5);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let table = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(3))),
+        (
+            "ids".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![
+                Arc::new(Value::int(1)),
+                Arc::new(Value::int(2)),
+                Arc::new(Value::int(3)),
+                Arc::new(Value::int(4)),
+                Arc::new(Value::int(5)),
+            ])),
+        ),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (table.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Table", &table)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
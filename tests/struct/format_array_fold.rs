@@ -0,0 +1,72 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_fold.core.fathom");
+
+fn folded(elems: &[u8], result: u16) -> Arc<Value> {
+    let elems = elems.iter().map(|elem| Arc::new(Value::int(*elem)));
+    Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+        ("elems".to_owned(), Arc::new(Value::ArrayTerm(elems.collect()))),
+        ("result".to_owned(), Arc::new(Value::int(result))),
+    ])))
+}
+
+fn write_totals(total: u16) -> FormatWriter {
+    let mut writer = FormatWriter::new(vec![]);
+    for value in &[3, 5, 7] {
+        writer.write::<U8>(*value); // Totals::values
+    }
+    for value in &[4, 9, 2] {
+        writer.write::<U8>(*value); // Totals::largest
+    }
+    writer.write::<U16Be>(total); // Totals::total
+    writer
+}
+
+#[test]
+fn valid_totals() {
+    let writer = write_totals(15);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let totals = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("values".to_owned(), folded(&[3, 5, 7], 15)),
+        ("largest".to_owned(), folded(&[4, 9, 2], 9)),
+        ("total".to_owned(), Arc::new(Value::int(15))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Totals").unwrap(),
+        (totals.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Totals", &totals)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn invalid_total() {
+    let writer = write_totals(16);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Totals") {
+        Err(ReadError::UnexpectedConst { .. }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
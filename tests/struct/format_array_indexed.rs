@@ -0,0 +1,50 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_indexed.core.fathom");
+
+fn indexed(index: usize, value: u16) -> Arc<Value> {
+    Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+        ("index".to_owned(), Arc::new(Value::int(index))),
+        ("value".to_owned(), Arc::new(Value::int(value))),
+    ])))
+}
+
+#[test]
+fn valid_offsets() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(16); // Offsets::offsets
+    writer.write::<U16Be>(48);
+    writer.write::<U16Be>(96);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let offsets = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "offsets".to_owned(),
+        Arc::new(Value::ArrayTerm(vec![
+            indexed(0, 16),
+            indexed(1, 48),
+            indexed(2, 96),
+        ])),
+    )]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Offsets").unwrap(),
+        (offsets.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Offsets", &offsets)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
@@ -0,0 +1,52 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_map_index.core.fathom");
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(2); //        0 ..  1:   Table::count
+    writer.write::<U8>(b'a'); //     1 ..  2:   Table::entries[0]::tag
+    writer.write::<U8>(b'b'); //     2 ..  3:   Table::entries[1]::tag
+    writer.write::<U8>(0); //        3 ..  4:   ...
+    writer.write::<U8>(0); //        4 ..  5:   ...
+    writer.write::<U8>(0); //        5 ..  6:   ...
+    writer.write::<U8>(0); //        6 ..  7:   ...
+    writer.write::<U8>(0); //        7 ..  8:   ...
+    writer.write::<U16Be>(300); //   8 .. 10:   Table::entries[0]::value
+    writer.write::<U16Be>(400); //  10 .. 12:   Table::entries[1]::value
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let entry = |tag: u8, offset: usize| {
+        Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+            ("tag".to_owned(), Arc::new(Value::int(tag))),
+            ("value".to_owned(), Arc::new(Value::pos(offset))),
+        ])))
+    };
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("start".to_owned(), Arc::new(Value::pos(0))),
+                ("count".to_owned(), Arc::new(Value::int(2))),
+                (
+                    "entries".to_owned(),
+                    Arc::new(Value::ArrayTerm(vec![entry(b'a', 8), entry(b'b', 10)])),
+                ),
+            ])),
+            vec![(8, Value::int(300)), (10, Value::int(400))],
+        ),
+    );
+}
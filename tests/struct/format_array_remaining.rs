@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_remaining.core.fathom");
+
+#[test]
+fn valid_points() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(8); //        0 ..  1:   Points::len
+    writer.write::<U16Be>(1); //     1 ..  3:   Points::points[0]
+    writer.write::<U16Be>(2); //     3 ..  5:   Points::points[1]
+    writer.write::<U16Be>(3); //     5 ..  7:   Points::points[2]
+    writer.write::<U16Be>(4); //     7 ..  9:   Points::points[3]
+    writer.write::<U8>(42); //       9 .. 10:   Points::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Points").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("len".to_owned(), Arc::new(Value::int(8))),
+                (
+                    "points".to_owned(),
+                    Arc::new(Value::ArrayTerm(vec![
+                        Arc::new(Value::int(1)),
+                        Arc::new(Value::int(2)),
+                        Arc::new(Value::int(3)),
+                        Arc::new(Value::int(4)),
+                    ])),
+                ),
+                ("trailer".to_owned(), Arc::new(Value::int(42))),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
+
+#[test]
+fn partial_element() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); //        0 ..  1:   Points::len
+    writer.write::<U16Be>(1); //     1 ..  3:   Points::points[0]
+    writer.write::<U8>(2); //        3 ..  4:   Points::points[1] (partial)
+    writer.write::<U8>(42); //       4 ..  5:   Points::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Points") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("eof error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_reverse.core.fathom");
+
+#[test]
+fn valid_stack() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); //        0 ..  1:   Stack::len
+    writer.write::<U16Be>(3); //     1 ..  3:   Stack::items[2]
+    writer.write::<U16Be>(2); //     3 ..  5:   Stack::items[1]
+    writer.write::<U16Be>(1); //     5 ..  7:   Stack::items[0]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let stack = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(3))),
+        (
+            "items".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![
+                Arc::new(Value::int(1)),
+                Arc::new(Value::int(2)),
+                Arc::new(Value::int(3)),
+            ])),
+        ),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Stack").unwrap(),
+        (stack.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Stack", &stack)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
@@ -0,0 +1,56 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8, WriteError};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_sort.core.fathom");
+
+fn ints(values: &[u16]) -> Arc<Value> {
+    let values = values.iter().map(|value| Arc::new(Value::int(*value)));
+    Arc::new(Value::ArrayTerm(values.collect()))
+}
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(4); //        0 ..  1:   Table::len
+    writer.write::<U16Be>(7); //     1 ..  3:   Table::keys[0]
+    writer.write::<U16Be>(1); //     3 ..  5:   Table::keys[1]
+    writer.write::<U16Be>(5); //     5 ..  7:   Table::keys[2]
+    writer.write::<U16Be>(1); //     7 ..  9:   Table::keys[3]
+    writer.write::<U8>(3); //        9 .. 10:   Table::tags[0]
+    writer.write::<U8>(1); //       10 .. 11:   Table::tags[1]
+    writer.write::<U8>(3); //       11 .. 12:   Table::tags[2]
+    writer.write::<U8>(1); //       12 .. 13:   Table::tags[3]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let table = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(4))),
+        ("keys".to_owned(), ints(&[1, 1, 5, 7])),
+        ("tags".to_owned(), ints(&[3, 1])),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (table.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    match binary::write::Context::new(&globals, &FIXTURE).write_item(
+        &mut round_trip,
+        "Table",
+        &table,
+    ) {
+        Err(WriteError::NonInvertibleFormat { format }) => assert_eq!(format, "FormatArraySort"),
+        Err(err) => panic!("non-invertible format error expected, found: {:?}", err),
+        Ok(()) => panic!("error expected, found: Ok(())"),
+    }
+}
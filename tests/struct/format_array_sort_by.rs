@@ -0,0 +1,51 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_sort_by.core.fathom");
+
+fn records(values: &[(u8, u16)]) -> Arc<Value> {
+    let values = values.iter().map(|(tag, offset)| {
+        Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+            ("tag".to_owned(), Arc::new(Value::int(*tag))),
+            ("offset".to_owned(), Arc::new(Value::int(*offset))),
+        ])))
+    });
+    Arc::new(Value::ArrayTerm(values.collect()))
+}
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(4); // Table::len
+    for (tag, offset) in &[(7, 0), (1, 1), (5, 2), (1, 3)] {
+        writer.write::<U8>(*tag);
+        writer.write::<U16Be>(*offset);
+    }
+    for (tag, offset) in &[(0, 3), (1, 1), (2, 3), (3, 1)] {
+        writer.write::<U8>(*tag);
+        writer.write::<U16Be>(*offset);
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("len".to_owned(), Arc::new(Value::int(4))),
+                ("records".to_owned(), records(&[(1, 1), (1, 3), (5, 2), (7, 0)])),
+                ("offsets".to_owned(), records(&[(0, 3), (1, 1)])),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_sorted.core.fathom");
+
+fn ints(values: &[u16]) -> Arc<Value> {
+    let values = values.iter().map(|value| Arc::new(Value::int(*value)));
+    Arc::new(Value::ArrayTerm(values.collect()))
+}
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); //        0 ..  1:   Table::len
+    writer.write::<U16Be>(1); //     1 ..  3:   Table::keys[0]
+    writer.write::<U16Be>(1); //     3 ..  5:   Table::keys[1]
+    writer.write::<U16Be>(7); //     5 ..  7:   Table::keys[2]
+    writer.write::<U8>(3); //        7 ..  8:   Table::tags[0]
+    writer.write::<U8>(1); //        8 ..  9:   Table::tags[1]
+    writer.write::<U8>(2); //        9 .. 10:   Table::tags[2]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("len".to_owned(), Arc::new(Value::int(3))),
+                ("keys".to_owned(), ints(&[1, 1, 7])),
+                ("tags".to_owned(), ints(&[3, 1, 2])),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
+
+#[test]
+fn unsorted_keys() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); //        0 ..  1:   Table::len
+    writer.write::<U16Be>(1); //     1 ..  3:   Table::keys[0]
+    writer.write::<U16Be>(7); //     3 ..  5:   Table::keys[1]
+    writer.write::<U16Be>(5); //     5 ..  7:   Table::keys[2]
+    writer.write::<U8>(3); //        7 ..  8:   Table::tags[0]
+    writer.write::<U8>(1); //        8 ..  9:   Table::tags[1]
+    writer.write::<U8>(2); //        9 .. 10:   Table::tags[2]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Table") {
+        Err(ReadError::UnsortedElement { index }) => assert_eq!(index, 2),
+        Err(err) => panic!("unsorted element error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn duplicate_tags() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); //        0 ..  1:   Table::len
+    writer.write::<U16Be>(1); //     1 ..  3:   Table::keys[0]
+    writer.write::<U16Be>(2); //     3 ..  5:   Table::keys[1]
+    writer.write::<U16Be>(3); //     5 ..  7:   Table::keys[2]
+    writer.write::<U8>(3); //        7 ..  8:   Table::tags[0]
+    writer.write::<U8>(1); //        8 ..  9:   Table::tags[1]
+    writer.write::<U8>(3); //        9 .. 10:   Table::tags[2]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Table") {
+        Err(ReadError::DuplicateElement { index }) => assert_eq!(index, 2),
+        Err(err) => panic!("duplicate element error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_sorted_by.core.fathom");
+
+fn records(values: &[(u8, u16)]) -> Arc<Value> {
+    let values = values.iter().map(|(tag, offset)| {
+        Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+            ("tag".to_owned(), Arc::new(Value::int(*tag))),
+            ("offset".to_owned(), Arc::new(Value::int(*offset))),
+        ])))
+    });
+    Arc::new(Value::ArrayTerm(values.collect()))
+}
+
+fn write_table(writer: &mut FormatWriter, records: &[(u8, u16)], offsets: &[(u8, u16)]) {
+    writer.write::<U8>(records.len() as u8);
+    for (tag, offset) in records.iter().chain(offsets) {
+        writer.write::<U8>(*tag);
+        writer.write::<U16Be>(*offset);
+    }
+}
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    write_table(
+        &mut writer,
+        &[(1, 9), (1, 3), (4, 3)],
+        &[(7, 3), (7, 1), (2, 2)],
+    );
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("len".to_owned(), Arc::new(Value::int(3))),
+                ("records".to_owned(), records(&[(1, 9), (1, 3), (4, 3)])),
+                ("offsets".to_owned(), records(&[(7, 3), (7, 1), (2, 2)])),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
+
+#[test]
+fn unsorted_records() {
+    let mut writer = FormatWriter::new(vec![]);
+    write_table(
+        &mut writer,
+        &[(1, 0), (3, 0), (2, 0)],
+        &[(0, 1), (0, 2), (0, 3)],
+    );
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Table") {
+        Err(ReadError::UnsortedElement { index }) => assert_eq!(index, 2),
+        Err(err) => panic!("unsorted element error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn duplicate_offsets() {
+    let mut writer = FormatWriter::new(vec![]);
+    write_table(
+        &mut writer,
+        &[(1, 0), (2, 0), (3, 0), (4, 0)],
+        &[(0, 9), (0, 5), (0, 9), (0, 5)],
+    );
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Table") {
+        Err(ReadError::DuplicateElement { index }) => assert_eq!(index, 2),
+        Err(err) => panic!("duplicate element error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
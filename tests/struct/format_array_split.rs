@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_split.core.fathom");
+
+fn array(values: &[u8]) -> Arc<Value> {
+    Arc::new(Value::ArrayTerm(
+        values.iter().map(|value| Arc::new(Value::int(*value))).collect(),
+    ))
+}
+
+#[test]
+fn valid_split() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Values::values
+    writer.write::<U8>(2);
+    writer.write::<U8>(3);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let split = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "values".to_owned(),
+        Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+            ("head".to_owned(), array(&[1])),
+            ("tail".to_owned(), array(&[2, 3])),
+        ]))),
+    )]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Values").unwrap(),
+        (split.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Values", &split)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn split_past_end() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // ValuesPastEnd::values
+    writer.write::<U8>(2);
+    writer.write::<U8>(3);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"ValuesPastEnd") {
+        Err(ReadError::ArrayIndexOutOfBounds { index: 4, len: 3 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
@@ -0,0 +1,150 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary, Primitive};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_take_drop.core.fathom");
+
+fn array(values: &[u8]) -> Arc<Value> {
+    Arc::new(Value::ArrayTerm(
+        values.iter().map(|value| Arc::new(Value::int(*value))).collect(),
+    ))
+}
+
+#[test]
+fn valid_take() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Taken::values
+    writer.write::<U8>(2);
+    writer.write::<U8>(3);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let taken = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "values".to_owned(),
+        array(&[1, 2]),
+    )]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Taken").unwrap(),
+        (taken.clone(), Vec::new()),
+    );
+
+    // The dropped element is written back as its default value.
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Taken", &taken)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), &[1, 2, 0]);
+}
+
+#[test]
+fn take_past_end() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // TakenPastEnd::values
+    writer.write::<U8>(2);
+    writer.write::<U8>(3);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"TakenPastEnd") {
+        Err(ReadError::ArrayIndexOutOfBounds { index: 4, len: 3 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn write_taken_strings() {
+    let globals = core::Globals::default();
+    let taken = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "values".to_owned(),
+        Arc::new(Value::ArrayTerm(vec![Arc::new(Value::Primitive(
+            Primitive::String(String::new()),
+        ))])),
+    )]));
+
+    // The dropped string is written back as the empty string.
+    let mut writer = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "TakenStrings", &taken)
+        .unwrap();
+    assert_eq!(writer.buffer(), &[] as &[u8]);
+}
+
+#[test]
+fn write_taken_pairs() {
+    let globals = core::Globals::default();
+    let pair = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("fst".to_owned(), Arc::new(Value::int(1))),
+        ("snd".to_owned(), Arc::new(Value::int(2))),
+    ]));
+    let taken = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "values".to_owned(),
+        Arc::new(Value::ArrayTerm(vec![Arc::new(pair)])),
+    )]));
+
+    // The dropped pair is written back with the defaults of its fields.
+    let mut writer = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "TakenPairs", &taken)
+        .unwrap();
+    assert_eq!(writer.buffer(), &[1, 0, 2, 0]);
+}
+
+#[test]
+fn valid_drop() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Dropped::values
+    writer.write::<U8>(2);
+    writer.write::<U8>(3);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let dropped = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "values".to_owned(),
+        array(&[3]),
+    )]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Dropped").unwrap(),
+        (dropped.clone(), Vec::new()),
+    );
+
+    // The dropped elements are written back as their default values.
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Dropped", &dropped)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), &[0, 0, 3]);
+}
+
+#[test]
+fn drop_past_end() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // DroppedPastEnd::values
+    writer.write::<U8>(2);
+    writer.write::<U8>(3);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"DroppedPastEnd") {
+        Err(ReadError::ArrayIndexOutOfBounds { index: 4, len: 3 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
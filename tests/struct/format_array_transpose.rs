@@ -0,0 +1,48 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_transpose.core.fathom");
+
+fn column(values: &[u16]) -> Arc<Value> {
+    let values = values.iter().map(|value| Arc::new(Value::int(*value)));
+    Arc::new(Value::ArrayTerm(values.collect()))
+}
+
+#[test]
+fn valid_matrix() {
+    let mut writer = FormatWriter::new(vec![]);
+    for value in 1..=6 {
+        writer.write::<U16Be>(value); // Matrix::columns
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let matrix = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "columns".to_owned(),
+        Arc::new(Value::ArrayTerm(vec![
+            column(&[1, 4]),
+            column(&[2, 5]),
+            column(&[3, 6]),
+        ])),
+    )]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Matrix").unwrap(),
+        (matrix.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Matrix", &matrix)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
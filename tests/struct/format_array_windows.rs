@@ -0,0 +1,91 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_windows.core.fathom");
+
+fn array(values: &[Value]) -> Arc<Value> {
+    Arc::new(Value::ArrayTerm(values.iter().cloned().map(Arc::new).collect()))
+}
+
+fn window(values: &[u8]) -> Value {
+    Value::ArrayTerm(values.iter().map(|value| Arc::new(Value::int(*value))).collect())
+}
+
+#[test]
+fn valid_pairs() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Samples::pairs[0]
+    writer.write::<U8>(2); // Samples::pairs[1]
+    writer.write::<U8>(3); // Samples::pairs[2]
+    writer.write::<U8>(4); // Samples::pairs[3]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let samples = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "pairs".to_owned(),
+        array(&[window(&[1, 2]), window(&[2, 3]), window(&[3, 4])]),
+    )]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Samples").unwrap(),
+        (samples.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Samples", &samples)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_single_window() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(5); // Triples::triples[0]
+    writer.write::<U8>(6); // Triples::triples[1]
+    writer.write::<U8>(7); // Triples::triples[2]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let triples = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "triples".to_owned(),
+        array(&[window(&[5, 6, 7])]),
+    )]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Triples").unwrap(),
+        (triples, Vec::new()),
+    );
+}
+
+#[test]
+fn invalid_window_size() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(4); // TooWide::len
+    writer.write::<U8>(1); // TooWide::windows[0]
+    writer.write::<U8>(2); // TooWide::windows[1]
+    writer.write::<U8>(3); // TooWide::windows[2]
+    writer.write::<U8>(4); // TooWide::windows[3]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"TooWide") {
+        Err(ReadError::ArrayIndexOutOfBounds { index: 5, len: 4 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
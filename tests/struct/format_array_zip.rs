@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, I8, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_array_zip.core.fathom");
+
+fn pair(fst: i32, snd: i32) -> Arc<Value> {
+    Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+        ("fst".to_owned(), Arc::new(Value::int(fst))),
+        ("snd".to_owned(), Arc::new(Value::int(snd))),
+    ])))
+}
+
+#[test]
+fn valid_samples() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); // Samples::len
+    writer.write::<U16Be>(100); // Samples::samples (first)
+    writer.write::<U16Be>(200);
+    writer.write::<U16Be>(300);
+    writer.write::<I8>(-1); // Samples::samples (second)
+    writer.write::<I8>(0);
+    writer.write::<I8>(1);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let samples = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(3))),
+        (
+            "samples".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![
+                pair(100, -1),
+                pair(200, 0),
+                pair(300, 1),
+            ])),
+        ),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Samples").unwrap(),
+        (samples.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Samples", &samples)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn mismatched_lengths() {
+    let mut writer = FormatWriter::new(vec![]);
+    for value in 1..=5 {
+        writer.write::<U8>(value); // Mismatched::samples
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Mismatched") {
+        Err(ReadError::MismatchedArrayLengths {
+            first: 2,
+            second: 3,
+        }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
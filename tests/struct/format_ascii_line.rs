@@ -0,0 +1,95 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_ascii_line.core.fathom");
+
+fn header(line: &[u8], last: u8) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        (
+            "line".to_owned(),
+            Arc::new(Value::ArrayTerm(
+                line.iter().map(|byte| Arc::new(Value::int(*byte))).collect(),
+            )),
+        ),
+        ("last".to_owned(), Arc::new(Value::int(last))),
+    ]))
+}
+
+#[test]
+fn valid_crlf() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b"abc\r\n" {
+        writer.write::<U8>(*byte); // Header::line
+    }
+    writer.write::<U8>(b'!'); // Header::last
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Header").unwrap(),
+        (header(b"abc", b'!'), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_lf() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b"a\rb\n" {
+        writer.write::<U8>(*byte); // Header::line
+    }
+    writer.write::<U8>(b'!'); // Header::last
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Header").unwrap(),
+        (header(b"a\rb", b'!'), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_empty() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(b'\n'); // Header::line
+    writer.write::<U8>(b'!'); // Header::last
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Header").unwrap(),
+        (header(b"", b'!'), Vec::new()),
+    );
+}
+
+#[test]
+fn eof_missing_newline() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b"abc" {
+        writer.write::<U8>(*byte); // Header::line
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Header") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("unexpected error: {:?}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_assert_size.core.fathom");
+
+fn chunk(data: &[u8], tag: u8) -> Value {
+    let header = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(data.len() as u8))),
+        (
+            "data".to_owned(),
+            Arc::new(Value::ArrayTerm(
+                data.iter().map(|byte| Arc::new(Value::int(*byte))).collect(),
+            )),
+        ),
+    ]));
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("header".to_owned(), Arc::new(header)),
+        ("tag".to_owned(), Arc::new(Value::int(tag))),
+    ]))
+}
+
+fn read_chunk(bytes: &[u8]) -> Result<Value, ReadError> {
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(bytes).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+    read_context
+        .read_item(&mut reader, &"Chunk")
+        .map(|(value, _)| value)
+}
+
+#[test]
+fn exact_size() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(2); // Chunk::header::len
+    writer.write::<U8>(10); // Chunk::header::data
+    writer.write::<U8>(20);
+    writer.write::<U8>(42); // Chunk::tag
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Chunk").unwrap(),
+        (chunk(&[10, 20], 42), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Chunk", &chunk(&[10, 20], 42))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn under_consumed() {
+    match read_chunk(&[1, 10, 42]) {
+        Err(ReadError::SizeMismatch {
+            expected: 3,
+            found: 2,
+        }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn over_consumed() {
+    match read_chunk(&[3, 10, 20, 30, 42]) {
+        Err(ReadError::SizeMismatch {
+            expected: 3,
+            found: 4,
+        }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn write_wrong_size() {
+    let globals = core::Globals::default();
+    let mut writer = FormatWriter::new(vec![]);
+    assert!(binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "Chunk", &chunk(&[10], 42))
+        .is_err());
+}
@@ -0,0 +1,63 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8, WriteError};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_at_offsets.core.fathom");
+
+fn record(id: u8, value: u16) -> Arc<Value> {
+    Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+        ("id".to_owned(), Arc::new(Value::int(id))),
+        ("value".to_owned(), Arc::new(Value::int(value))),
+    ])))
+}
+
+#[test]
+fn valid_pool() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(10); // Pool::records
+    writer.write::<U8>(4);
+    writer.write::<U8>(7);
+    writer.write::<U8>(0xFF); // Pool::end
+    writer.write::<U8>(1); // Record (offset 4)
+    writer.write::<U16Be>(100);
+    writer.write::<U8>(2); // Record (offset 7)
+    writer.write::<U16Be>(200);
+    writer.write::<U8>(3); // Record (offset 10)
+    writer.write::<U16Be>(300);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let pool = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("start".to_owned(), Arc::new(Value::pos(0))),
+        (
+            "records".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![
+                record(3, 300),
+                record(1, 100),
+                record(2, 200),
+            ])),
+        ),
+        ("end".to_owned(), Arc::new(Value::int(0xFF))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Pool").unwrap(),
+        (pool.clone(), Vec::new()),
+    );
+
+    // The offsets are not kept, so they can't be written back out.
+    let mut round_trip = FormatWriter::new(vec![]);
+    match binary::write::Context::new(&globals, &FIXTURE).write_item(&mut round_trip, "Pool", &pool) {
+        Err(WriteError::NonInvertibleFormat { format }) => assert_eq!(format, "FormatAtOffsets"),
+        Err(err) => panic!("non-invertible format error expected, found: {:?}", err),
+        Ok(()) => panic!("error expected, found: Ok(())"),
+    }
+}
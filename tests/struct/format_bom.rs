@@ -0,0 +1,104 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_bom.core.fathom");
+
+fn text(bom: u8, first: u8) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("bom".to_owned(), Arc::new(Value::int(bom))),
+        ("first".to_owned(), Arc::new(Value::int(first))),
+    ]))
+}
+
+#[test]
+fn valid_no_bom() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(b'a'); // Text::first
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Text").unwrap(),
+        (text(0, b'a'), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_utf8_bom() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0xEF); // Text::bom
+    writer.write::<U8>(0xBB); // Text::bom
+    writer.write::<U8>(0xBF); // Text::bom
+    writer.write::<U8>(b'a'); // Text::first
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Text").unwrap(),
+        (text(1, b'a'), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_utf16be_bom() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0xFE); // Text::bom
+    writer.write::<U8>(0xFF); // Text::bom
+    writer.write::<U8>(b'a'); // Text::first
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Text").unwrap(),
+        (text(2, b'a'), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_utf16le_bom() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0xFF); // Text::bom
+    writer.write::<U8>(0xFE); // Text::bom
+    writer.write::<U8>(b'a'); // Text::first
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Text").unwrap(),
+        (text(3, b'a'), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_partial_utf8_bom() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0xEF); // Text::first
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Text").unwrap(),
+        (text(0, 0xEF), Vec::new()),
+    );
+}
@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_c_str.core.fathom");
+
+fn symbol(name: &[u8], value: u8) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        (
+            "name".to_owned(),
+            Arc::new(Value::ArrayTerm(
+                name.iter().map(|byte| Arc::new(Value::int(*byte))).collect(),
+            )),
+        ),
+        ("value".to_owned(), Arc::new(Value::int(value))),
+    ]))
+}
+
+#[test]
+fn valid_symbol() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b".text\0" {
+        writer.write::<U8>(*byte); // Symbol::name
+    }
+    writer.write::<U8>(42); // Symbol::value
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Symbol").unwrap(),
+        (symbol(b".text", 42), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Symbol", &symbol(b".text", 42))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn missing_terminator() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b".text" {
+        writer.write::<U8>(*byte); // Symbol::name
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Symbol") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn write_interior_nul() {
+    let globals = core::Globals::default();
+    let mut writer = FormatWriter::new(vec![]);
+    assert!(binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "Symbol", &symbol(b"a\0b", 42))
+        .is_err());
+}
@@ -0,0 +1,53 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_const.core.fathom");
+
+#[test]
+fn valid_const() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(2); // Header::version
+    writer.write::<U8>(7); //    Header::flags
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Header").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("version".to_owned(), Arc::new(Value::int(2))),
+                ("flags".to_owned(), Arc::new(Value::int(7))),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
+
+#[test]
+fn invalid_const() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(3); // Header::version
+    writer.write::<U8>(7); //    Header::flags
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Header") {
+        Err(ReadError::UnexpectedConst { expected, found }) => {
+            assert_eq!(expected, "2");
+            assert_eq!(found, "3");
+        }
+        Err(err) => panic!("unexpected const error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, I16Be, I32Be, ReadScope, U16Le, WriteError};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_fixed.core.fathom");
+
+fn transform(scale: f64, angle: f64, weight: f64) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("scale".to_owned(), Arc::new(Value::f64(scale))),
+        ("angle".to_owned(), Arc::new(Value::f64(angle))),
+        ("weight".to_owned(), Arc::new(Value::f64(weight))),
+    ]))
+}
+
+fn assert_round_trip(writer: FormatWriter, value: Value) {
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Transform").unwrap(),
+        (value.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Transform", &value)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_positive() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<I32Be>(0x0001_8000); // Transform::scale
+    writer.write::<I16Be>(0x2000); // Transform::angle
+    writer.write::<U16Le>(0x0340); // Transform::weight
+
+    assert_round_trip(writer, transform(1.5, 0.5, 3.25));
+}
+
+#[test]
+fn valid_negative() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<I32Be>(-0x0002_4000); // Transform::scale
+    writer.write::<I16Be>(-0x8000); // Transform::angle
+    writer.write::<U16Le>(0xffff); // Transform::weight
+
+    assert_round_trip(writer, transform(-2.25, -2.0, 255.99609375));
+}
+
+#[test]
+fn write_inexact() {
+    let globals = core::Globals::default();
+    let mut writer = FormatWriter::new(vec![]);
+
+    match binary::write::Context::new(&globals, &FIXTURE).write_item(
+        &mut writer,
+        "Transform",
+        &transform(0.1, 0.0, 0.0),
+    ) {
+        Err(WriteError::UnrepresentableValue { value }) => assert_eq!(value, "0.1"),
+        Err(err) => panic!("unrepresentable value error expected, found: {:?}", err),
+        Ok(()) => panic!("error expected, found: Ok(())"),
+    }
+}
+
+#[test]
+fn write_out_of_range() {
+    let globals = core::Globals::default();
+    let mut writer = FormatWriter::new(vec![]);
+
+    match binary::write::Context::new(&globals, &FIXTURE).write_item(
+        &mut writer,
+        "Transform",
+        &transform(0.0, 2.0, 0.0),
+    ) {
+        Err(WriteError::UnrepresentableValue { value }) => assert_eq!(value, "32768"),
+        Err(err) => panic!("unrepresentable value error expected, found: {:?}", err),
+        Ok(()) => panic!("error expected, found: Ok(())"),
+    }
+}
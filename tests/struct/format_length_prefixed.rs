@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_length_prefixed.core.fathom");
+
+#[test]
+fn valid_message() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Message::tag
+    writer.write::<U16Be>(3); // Message::data
+    writer.write::<U8>(0xA);
+    writer.write::<U8>(0xB);
+    writer.write::<U8>(0xC);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let message = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("tag".to_owned(), Arc::new(Value::int(1))),
+        (
+            "data".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![
+                Arc::new(Value::int(0xA)),
+                Arc::new(Value::int(0xB)),
+                Arc::new(Value::int(0xC)),
+            ])),
+        ),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Message").unwrap(),
+        (message.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Message", &message)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn truncated_message() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Message::tag
+    writer.write::<U16Be>(3); // Message::data
+    writer.write::<U8>(0xA);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Message") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
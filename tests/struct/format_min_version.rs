@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_min_version.core.fathom");
+
+fn header(major: u16, minor: u16, patch: u16) -> Value {
+    let version = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("major".to_owned(), Arc::new(Value::int(major))),
+        ("minor".to_owned(), Arc::new(Value::int(minor))),
+        ("patch".to_owned(), Arc::new(Value::int(patch))),
+    ]));
+    let reserved = (0..major).map(|_| Arc::new(Value::int(0)));
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("version".to_owned(), Arc::new(version)),
+        ("reserved".to_owned(), Arc::new(Value::ArrayTerm(reserved.collect()))),
+    ]))
+}
+
+fn write_version(major: u16, minor: u16, patch: u16) -> FormatWriter {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(major); // Header::version
+    writer.write::<U16Be>(minor); // Header::version
+    writer.write::<U16Be>(patch); // Header::version
+    for _ in 0..major {
+        writer.write::<U8>(0); // Header::reserved
+    }
+    writer
+}
+
+#[test]
+fn valid_at_minimum() {
+    let writer = write_version(1, 2, 3);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Header").unwrap(),
+        (header(1, 2, 3), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Header", &header(1, 2, 3))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_above_minimum() {
+    let writer = write_version(1, 10, 0);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Header").unwrap(),
+        (header(1, 10, 0), Vec::new()),
+    );
+}
+
+#[test]
+fn invalid_below_minimum() {
+    let writer = write_version(1, 2, 2);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Header") {
+        Err(ReadError::UnsupportedVersion { found, required }) => {
+            assert_eq!(found, "1.2.2");
+            assert_eq!(required, "1.2.3");
+        }
+        Err(err) => panic!("unexpected error: {:?}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
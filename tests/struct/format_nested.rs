@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_nested.core.fathom");
+
+fn point_struct(len: u8, x: u8, y: u8, trailer: u8) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(len))),
+        (
+            "point".to_owned(),
+            Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+                ("x".to_owned(), Arc::new(Value::int(x))),
+                ("y".to_owned(), Arc::new(Value::int(y))),
+            ]))),
+        ),
+        ("trailer".to_owned(), Arc::new(Value::int(trailer))),
+    ]))
+}
+
+#[test]
+fn valid_exact() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(2); //  Exact::len
+    writer.write::<U8>(3); //  Exact::point::x
+    writer.write::<U8>(4); //  Exact::point::y
+    writer.write::<U8>(5); //  Exact::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Exact").unwrap(),
+        (point_struct(2, 3, 4, 5), Vec::new()),
+    );
+}
+
+#[test]
+fn invalid_exact_trailing() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(3); //  Exact::len
+    writer.write::<U8>(3); //  Exact::point::x
+    writer.write::<U8>(4); //  Exact::point::y
+    writer.write::<U8>(0); //  ...
+    writer.write::<U8>(5); //  Exact::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Exact") {
+        Err(ReadError::TrailingBytes { count }) => assert_eq!(count, 1),
+        Err(err) => panic!("trailing bytes error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn invalid_exact_eof_inner() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); //  Exact::len
+    writer.write::<U8>(3); //  Exact::point::x
+    writer.write::<U8>(4); //  Exact::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Exact") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("eof error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn valid_padded_trailing() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(4); //  Padded::len
+    writer.write::<U8>(3); //  Padded::point::x
+    writer.write::<U8>(4); //  Padded::point::y
+    writer.write::<U8>(0); //  ...
+    writer.write::<U8>(0); //  ...
+    writer.write::<U8>(5); //  Padded::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Padded").unwrap(),
+        (point_struct(4, 3, 4, 5), Vec::new()),
+    );
+}
@@ -0,0 +1,50 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_never.core.fathom");
+
+#[test]
+fn never_is_unreachable() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Message::kind
+    writer.write::<U16Be>(0xBEEF); // Message::body
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Message").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("kind".to_owned(), Arc::new(Value::int(1))),
+                ("body".to_owned(), Arc::new(Value::int(0xBEEF))),
+            ])),
+            Vec::new(),
+        ),
+    );
+}
+
+#[test]
+fn reaching_never_is_a_bug() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(2); // Broken::kind
+    writer.write::<U16Be>(0xBEEF);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Broken") {
+        Err(ReadError::ReachedNever) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
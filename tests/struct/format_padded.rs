@@ -0,0 +1,68 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_padded.core.fathom");
+
+#[test]
+fn valid_fill() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b"abc   " {
+        writer.write::<U8>(*byte); // Entry::tag
+    }
+    writer.write::<U8>(42); // Entry::id
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let entry = Value::StructTerm(BTreeMap::from_iter(vec![
+        (
+            "tag".to_owned(),
+            Arc::new(Value::ArrayTerm(
+                b"abc".iter().map(|byte| Arc::new(Value::int(*byte))).collect(),
+            )),
+        ),
+        ("id".to_owned(), Arc::new(Value::int(42))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Entry").unwrap(),
+        (entry.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Entry", &entry)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn invalid_fill() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b"abc \0 " {
+        writer.write::<U8>(*byte); // Entry::tag
+    }
+    writer.write::<U8>(42); // Entry::id
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Entry") {
+        Err(ReadError::InvalidPadding {
+            index: 4,
+            expected: 0x20,
+            found: 0x00,
+        }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
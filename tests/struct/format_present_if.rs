@@ -0,0 +1,97 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::{Elim, Value};
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_present_if.core.fathom");
+
+fn some(value: Value) -> Arc<Value> {
+    Arc::new(Value::global("some", vec![Elim::Function(Arc::new(value))]))
+}
+
+fn none() -> Arc<Value> {
+    Arc::new(Value::global("none", Vec::new()))
+}
+
+fn record(flags: u8, width: Arc<Value>, height: Arc<Value>) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("flags".to_owned(), Arc::new(Value::int(flags))),
+        ("width".to_owned(), width),
+        ("height".to_owned(), height),
+    ]))
+}
+
+#[test]
+fn valid_no_fields() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0b00); // Record::flags
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(0b00, none(), none()), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_first_field() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0b01); // Record::flags
+    writer.write::<U16Be>(300); // Record::width
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(0b01, some(Value::int(300)), none()), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_second_field() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0b10); // Record::flags
+    writer.write::<U8>(20); // Record::height
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(0b10, none(), some(Value::int(20))), Vec::new()),
+    );
+}
+
+#[test]
+fn valid_both_fields() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0b11); // Record::flags
+    writer.write::<U16Be>(300); // Record::width
+    writer.write::<U8>(20); // Record::height
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (
+            record(0b11, some(Value::int(300)), some(Value::int(20))),
+            Vec::new(),
+        ),
+    );
+}
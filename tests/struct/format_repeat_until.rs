@@ -0,0 +1,112 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, WriteError, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_repeat_until.core.fathom");
+
+fn record(values: &[u16], tag: u8) -> Value {
+    let values = values.iter().map(|value| Arc::new(Value::int(*value)));
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("values".to_owned(), Arc::new(Value::ArrayTerm(values.collect()))),
+        ("tag".to_owned(), Arc::new(Value::int(tag))),
+    ]))
+}
+
+#[test]
+fn valid_zero_terminated() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(3); // Record::values[0]
+    writer.write::<U16Be>(1); // Record::values[1]
+    writer.write::<U16Be>(4); // Record::values[2]
+    writer.write::<U16Be>(0); // Record::values sentinel
+    writer.write::<U8>(42); // Record::tag
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(&[3, 1, 4], 42), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Record", &record(&[3, 1, 4], 42))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_empty() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(0); // Record::values sentinel
+    writer.write::<U8>(7); // Record::tag
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(&[], 7), Vec::new()),
+    );
+}
+
+#[test]
+fn missing_sentinel() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(3); // Record::values[0]
+    writer.write::<U16Be>(1); // Record::values[1]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Record") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("eof error expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
+
+#[test]
+fn write_sentinel_element() {
+    let globals = core::Globals::default();
+    let mut writer = FormatWriter::new(vec![]);
+
+    match binary::write::Context::new(&globals, &FIXTURE).write_item(
+        &mut writer,
+        "Record",
+        &record(&[3, 0, 4], 42),
+    ) {
+        Err(WriteError::UnrepresentableValue { value }) => assert_eq!(value, "0"),
+        Err(err) => panic!("unrepresentable value error expected, found: {:?}", err),
+        Ok(()) => panic!("error expected, found: Ok(())"),
+    }
+}
+
+#[test]
+fn elements_consuming_no_data() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(b'a');
+    writer.write::<U8>(b'b');
+    writer.write::<U8>(b'c');
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Empty") {
+        Err(ReadError::InvalidDataDescription) => {}
+        Err(err) => panic!("invalid data description expected, found: {:?}", err),
+        Ok(_) => panic!("error expected, found: Ok(_)"),
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_repeat_until_nested.core.fathom");
+
+fn group(values: &[u8]) -> Arc<Value> {
+    let values = values.iter().map(|value| Arc::new(Value::int(*value)));
+    Arc::new(Value::ArrayTerm(values.collect()))
+}
+
+#[test]
+fn valid_groups() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Groups::groups[0][0]
+    writer.write::<U8>(2); // Groups::groups[0][1]
+    writer.write::<U8>(0xFF); // Groups::groups[0] sentinel
+    writer.write::<U8>(3); // Groups::groups[1][0]
+    writer.write::<U8>(0xFF); // Groups::groups[1] sentinel
+    writer.write::<U8>(0xFF); // Groups::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let groups = Value::StructTerm(BTreeMap::from_iter(vec![
+        (
+            "groups".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![group(&[1, 2]), group(&[3])])),
+        ),
+        ("trailer".to_owned(), Arc::new(Value::int(0xFF))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Groups").unwrap(),
+        (groups, Vec::new()),
+    );
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+fn valid_empty_groups() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0xFF); // Groups::groups[0] sentinel
+    writer.write::<U8>(0xFF); // Groups::groups[1] sentinel
+    writer.write::<U8>(4); // Groups::trailer
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let groups = Value::StructTerm(BTreeMap::from_iter(vec![
+        (
+            "groups".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![group(&[]), group(&[])])),
+        ),
+        ("trailer".to_owned(), Arc::new(Value::int(4))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Groups").unwrap(),
+        (groups, Vec::new()),
+    );
+}
+
+#[test]
+fn missing_inner_sentinel() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Groups::groups[0][0]
+    writer.write::<U8>(0xFF); // Groups::groups[0] sentinel
+    writer.write::<U8>(2); // Groups::groups[1][0]
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Groups") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_repr_coerce.core.fathom");
+
+#[test]
+fn raw_bytes_match_input() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(0xCAFE); // File::header::magic
+    writer.write::<U8>(3); // File::header::version
+    writer.write::<U8>(0x7F); // File::checksum
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let raw = writer.buffer()[..3].iter();
+    let header = Value::StructTerm(BTreeMap::from_iter(vec![
+        (
+            "raw".to_owned(),
+            Arc::new(Value::ArrayTerm(
+                raw.map(|byte| Arc::new(Value::int(*byte))).collect(),
+            )),
+        ),
+        (
+            "value".to_owned(),
+            Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+                ("magic".to_owned(), Arc::new(Value::int(0xCAFE))),
+                ("version".to_owned(), Arc::new(Value::int(3))),
+            ]))),
+        ),
+    ]));
+    let file = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("header".to_owned(), Arc::new(header)),
+        ("checksum".to_owned(), Arc::new(Value::int(0x7F))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"File").unwrap(),
+        (file.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "File", &file)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
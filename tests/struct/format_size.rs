@@ -0,0 +1,51 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U32Be};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_size.core.fathom");
+
+fn entry(tag: u16, offset: u32) -> Arc<Value> {
+    Arc::new(Value::StructTerm(BTreeMap::from_iter(vec![
+        ("tag".to_owned(), Arc::new(Value::int(tag))),
+        ("offset".to_owned(), Arc::new(Value::int(offset))),
+    ])))
+}
+
+#[test]
+fn valid_table() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(12); // Table::total_size
+    writer.write::<U16Be>(1); // Table::entries[0]::tag
+    writer.write::<U32Be>(16); // Table::entries[0]::offset
+    writer.write::<U16Be>(2); // Table::entries[1]::tag
+    writer.write::<U32Be>(32); // Table::entries[1]::offset
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let table = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("total_size".to_owned(), Arc::new(Value::int(12))),
+        (
+            "entries".to_owned(),
+            Arc::new(Value::ArrayTerm(vec![entry(1, 16), entry(2, 32)])),
+        ),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Table").unwrap(),
+        (table.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Table", &table)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
@@ -0,0 +1,100 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_sleb128.core.fathom");
+
+fn entry(value: i64, tag: u8) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("value".to_owned(), Arc::new(Value::int(value))),
+        ("tag".to_owned(), Arc::new(Value::int(tag))),
+    ]))
+}
+
+fn assert_round_trip(bytes: &[u8], value: i64) {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in bytes {
+        writer.write::<U8>(*byte); // Entry::value
+    }
+    writer.write::<U8>(42); // Entry::tag
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Entry").unwrap(),
+        (entry(value, 42), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Entry", &entry(value, 42))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_single_byte() {
+    assert_round_trip(&[0x00], 0);
+    assert_round_trip(&[0x3f], 63);
+    assert_round_trip(&[0x7f], -1);
+    assert_round_trip(&[0x40], -64);
+}
+
+#[test]
+fn valid_multi_byte() {
+    assert_round_trip(&[0xc0, 0x00], 64);
+    assert_round_trip(&[0x80, 0x7f], -128);
+    assert_round_trip(&[0xc0, 0xbb, 0x78], -123_456);
+}
+
+#[test]
+fn valid_extremes() {
+    let bytes = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+    assert_round_trip(&bytes, i64::MAX);
+    let bytes = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f];
+    assert_round_trip(&bytes, i64::MIN);
+}
+
+#[test]
+fn overflowing_value() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01] {
+        writer.write::<U8>(*byte); // Entry::value
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Entry") {
+        Err(ReadError::OverflowingInteger { count: 10 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn too_many_bytes() {
+    let mut writer = FormatWriter::new(vec![]);
+    for _ in 0..11 {
+        writer.write::<U8>(0x80); // Entry::value
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Entry") {
+        Err(ReadError::OverflowingInteger { count: 10 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
@@ -0,0 +1,101 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_str_fixed.core.fathom");
+
+fn name(name: &[u8]) -> Arc<Value> {
+    Arc::new(Value::ArrayTerm(
+        name.iter().map(|byte| Arc::new(Value::int(*byte))).collect(),
+    ))
+}
+
+fn member(member_name: &[u8], mode: u8) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("name".to_owned(), name(member_name)),
+        ("mode".to_owned(), Arc::new(Value::int(mode))),
+    ]))
+}
+
+#[test]
+fn valid_member() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b"a.o/    " {
+        writer.write::<U8>(*byte); // Member::name
+    }
+    writer.write::<U8>(6); // Member::mode
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Member").unwrap(),
+        (member(b"a.o/", 6), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Member", &member(b"a.o/", 6))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_empty() {
+    let writer = FormatWriter::new(vec![]);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let empty = Value::StructTerm(BTreeMap::from_iter(vec![("name".to_owned(), name(b""))]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Empty").unwrap(),
+        (empty, Vec::new()),
+    );
+}
+
+#[test]
+fn name_past_end() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in b"a.o/" {
+        writer.write::<U8>(*byte); // Member::name
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Member") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn write_too_long() {
+    let globals = core::Globals::default();
+    let mut writer = FormatWriter::new(vec![]);
+    assert!(binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "Member", &member(b"libfoo.a/", 6))
+        .is_err());
+}
+
+#[test]
+fn write_trailing_fill() {
+    let globals = core::Globals::default();
+    let mut writer = FormatWriter::new(vec![]);
+    assert!(binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "Member", &member(b"a.o ", 6))
+        .is_err());
+}
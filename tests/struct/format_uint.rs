@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, I32Be, I32Le, U16Be, U16Le, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_uint.core.fathom");
+
+fn tiff(order: u16, width: u16, offset: i32, data: &[u8]) -> Value {
+    let data = data.iter().map(|byte| Arc::new(Value::int(*byte))).collect();
+    let header = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("width".to_owned(), Arc::new(Value::int(width))),
+        ("offset".to_owned(), Arc::new(Value::int(offset))),
+        ("flags".to_owned(), Arc::new(Value::int(1))),
+        ("data".to_owned(), Arc::new(Value::ArrayTerm(data))),
+    ]));
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("order".to_owned(), Arc::new(Value::int(order))),
+        ("header".to_owned(), Arc::new(header)),
+    ]))
+}
+
+fn assert_round_trip(writer: &FormatWriter, expected: Value) {
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Tiff").unwrap(),
+        (expected.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Tiff", &expected)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn little_endian() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(0x4949); // Tiff::order
+    writer.write::<U16Le>(3); // Header::width
+    writer.write::<I32Le>(-2); // Header::offset
+    writer.write::<U8>(1); // Header::flags
+    writer.write::<U8>(7); // Header::data
+    writer.write::<U8>(8);
+    writer.write::<U8>(9);
+
+    assert_round_trip(&writer, tiff(0x4949, 3, -2, &[7, 8, 9]));
+}
+
+#[test]
+fn big_endian() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U16Be>(0x4D4D); // Tiff::order
+    writer.write::<U16Be>(2); // Header::width
+    writer.write::<I32Be>(-2); // Header::offset
+    writer.write::<U8>(1); // Header::flags
+    writer.write::<U8>(7); // Header::data
+    writer.write::<U8>(8);
+
+    assert_round_trip(&writer, tiff(0x4D4D, 2, -2, &[7, 8]));
+}
@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary, Primitive};
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_utf8.core.fathom");
+
+#[test]
+fn valid_utf8() {
+    let text = "kākāpō";
+
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(text.len() as u8); // Label::len
+    for byte in text.bytes() {
+        writer.write::<U8>(byte); // Label::text
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let label = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("len".to_owned(), Arc::new(Value::int(text.len()))),
+        (
+            "text".to_owned(),
+            Arc::new(Value::Primitive(Primitive::String(text.to_owned()))),
+        ),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Label").unwrap(),
+        (label.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Label", &label)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(4); // Label::len
+    writer.write::<U8>(b'o'); // Label::text
+    writer.write::<U8>(b'k');
+    writer.write::<U8>(0xC3); // truncated two-byte sequence
+    writer.write::<U8>(b'!');
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Label") {
+        Err(ReadError::InvalidUtf8 { offset: 2 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
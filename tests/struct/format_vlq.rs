@@ -0,0 +1,96 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/format_vlq.core.fathom");
+
+fn event(delta_time: u32, status: u8) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("delta_time".to_owned(), Arc::new(Value::int(delta_time))),
+        ("status".to_owned(), Arc::new(Value::int(status))),
+    ]))
+}
+
+fn assert_round_trip(bytes: &[u8], delta_time: u32) {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in bytes {
+        writer.write::<U8>(*byte); // Event::delta_time
+    }
+    writer.write::<U8>(0x90); // Event::status
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Event").unwrap(),
+        (event(delta_time, 0x90), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Event", &event(delta_time, 0x90))
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn valid_single_byte() {
+    assert_round_trip(&[0x00], 0);
+    assert_round_trip(&[0x40], 0x40);
+    assert_round_trip(&[0x7f], 0x7f);
+}
+
+#[test]
+fn valid_multi_byte() {
+    assert_round_trip(&[0x81, 0x00], 0x80);
+    assert_round_trip(&[0xc0, 0x00], 0x2000);
+    assert_round_trip(&[0xff, 0x7f], 0x3fff);
+    assert_round_trip(&[0x81, 0x80, 0x80, 0x00], 0x0020_0000);
+}
+
+#[test]
+fn valid_max() {
+    assert_round_trip(&[0x8f, 0xff, 0xff, 0xff, 0x7f], u32::MAX);
+}
+
+#[test]
+fn overflowing_value() {
+    let mut writer = FormatWriter::new(vec![]);
+    for byte in &[0x90, 0x80, 0x80, 0x80, 0x00] {
+        writer.write::<U8>(*byte); // Event::delta_time
+    }
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Event") {
+        Err(ReadError::OverflowingInteger { count: 5 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn truncated_value() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0x81); // Event::delta_time
+    writer.write::<U8>(0x80);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Event") {
+        Err(ReadError::Eof(_)) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
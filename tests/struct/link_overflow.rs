@@ -0,0 +1,36 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, I8, U8};
+use fathom_test_util::fathom::lang::core::{self, binary};
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/link_overflow.core.fathom");
+
+#[test]
+fn valid_link() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0xAA); // Root::magic
+    writer.write::<I8>(-1); // Table::offset
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    read_context.read_item(&mut reader, &"Root").unwrap();
+}
+
+#[test]
+fn overflowing_link() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(0xAA); // Root::magic
+    writer.write::<I8>(-2); // Table::offset
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    match read_context.read_item(&mut reader, &"Root") {
+        Err(ReadError::ArithmeticOverflow { field: Some(field) }) if field == "data" => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
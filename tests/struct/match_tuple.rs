@@ -0,0 +1,72 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U16Be, U32Be, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/match_tuple.core.fathom");
+
+fn record(kind: u8, version: u8, body: u32) -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("kind".to_owned(), Arc::new(Value::int(kind))),
+        ("version".to_owned(), Arc::new(Value::int(version))),
+        ("body".to_owned(), Arc::new(Value::int(body))),
+    ]))
+}
+
+#[test]
+fn combined_pattern() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Record::kind
+    writer.write::<U8>(2); // Record::version
+    writer.write::<U32Be>(0xDEADBEEF); // Record::body
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(1, 2, 0xDEADBEEF), Vec::new()),
+    );
+}
+
+#[test]
+fn partial_pattern() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(1); // Record::kind
+    writer.write::<U8>(3); // Record::version
+    writer.write::<U16Be>(0xBEEF); // Record::body
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(1, 3, 0xBEEF), Vec::new()),
+    );
+}
+
+#[test]
+fn fallthrough() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U8>(2); // Record::kind
+    writer.write::<U8>(2); // Record::version
+    writer.write::<U8>(0xEF); // Record::body
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Record").unwrap(),
+        (record(2, 2, 0xEF), Vec::new()),
+    );
+}
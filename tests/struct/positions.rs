@@ -91,6 +91,69 @@ fn valid_root() {
     // TODO: Check remaining
 }
 
+#[test]
+fn valid_root_at_offset() {
+    let mut writer = FormatWriter::new(vec![]);
+    for _ in 0..16 {
+        writer.write::<U8>(0xFF); // 0 .. 16:   ...
+    }
+    writer.write::<U8>(b'p'); //    16 .. 17:   Root::magic[0]
+    writer.write::<U8>(b'o'); //    17 .. 18:   Root::magic[1]
+    writer.write::<U8>(b's'); //    18 .. 19:   Root::magic[2]
+    writer.write::<U8>(b' '); //    19 .. 20:   Root::magic[3]
+    writer.write::<U16Be>(8); //    20 .. 22:   Root::offset1
+    writer.write::<U16Be>(12); //   22 .. 24:   Root::offset2
+    writer.write::<U16Be>(1); //    24 .. 26:   Chunk::width
+    writer.write::<U16Be>(2); //    26 .. 28:   Chunk::height
+    writer.write::<U16Be>(3); //    28 .. 30:   Chunk::width
+    writer.write::<U16Be>(4); //    30 .. 32:   Chunk::height
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader_at(16);
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Root").unwrap(),
+        (
+            Value::StructTerm(BTreeMap::from_iter(vec![
+                ("start".to_owned(), Arc::new(Value::pos(16))),
+                (
+                    "magic".to_owned(),
+                    Arc::new(Value::ArrayTerm(vec![
+                        Arc::new(Value::int(b'p')),
+                        Arc::new(Value::int(b'o')),
+                        Arc::new(Value::int(b's')),
+                        Arc::new(Value::int(b' ')),
+                    ])),
+                ),
+                ("offset1".to_owned(), Arc::new(Value::int(8))),
+                ("offset2".to_owned(), Arc::new(Value::int(12))),
+                ("position1".to_owned(), Arc::new(Value::pos(24))),
+                ("position2".to_owned(), Arc::new(Value::pos(28))),
+            ])),
+            vec![
+                (
+                    24,
+                    Value::StructTerm(BTreeMap::from_iter(vec![
+                        ("start".to_owned(), Arc::new(Value::pos(24))),
+                        ("width".to_owned(), Arc::new(Value::int(1))),
+                        ("height".to_owned(), Arc::new(Value::int(2))),
+                    ])),
+                ),
+                (
+                    28,
+                    Value::StructTerm(BTreeMap::from_iter(vec![
+                        ("start".to_owned(), Arc::new(Value::pos(28))),
+                        ("width".to_owned(), Arc::new(Value::int(3))),
+                        ("height".to_owned(), Arc::new(Value::int(4))),
+                    ])),
+                ),
+            ],
+        ),
+    );
+}
+
 #[test]
 fn valid_root_trailing() {
     let mut writer = FormatWriter::new(vec![]);
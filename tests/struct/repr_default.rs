@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use fathom_test_util::fathom::lang::core::semantics::{self, Elim, Value};
+use fathom_test_util::fathom::lang::core::{self, ItemData};
+use std::collections::{BTreeMap, HashMap};
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/repr_default.core.fathom");
+
+fn items() -> HashMap<String, semantics::Item> {
+    FIXTURE
+        .items
+        .iter()
+        .filter_map(|item| match &item.data {
+            ItemData::StructFormat(struct_format) => Some((
+                struct_format.name.clone(),
+                semantics::Item::new(
+                    item.location,
+                    semantics::ItemData::StructFormat(
+                        struct_format.params.len(),
+                        struct_format.fields.clone(),
+                    ),
+                ),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn point() -> Value {
+    Value::StructTerm(BTreeMap::from_iter(vec![
+        ("x".to_owned(), Arc::new(Value::int(0))),
+        ("y".to_owned(), Arc::new(Value::int(0))),
+    ]))
+}
+
+#[test]
+fn default_point() {
+    let globals = core::Globals::default();
+    let items = items();
+    let r#type = Value::item("Point", vec![Elim::Repr]);
+
+    let value = semantics::repr_default(&globals, &items, &r#type).unwrap();
+
+    assert!(semantics::is_equal(&globals, &items, &value, &point()));
+}
+
+#[test]
+fn default_header() {
+    let globals = core::Globals::default();
+    let items = items();
+    let r#type = Value::item("Header", vec![Elim::Repr]);
+
+    let value = semantics::repr_default(&globals, &items, &r#type).unwrap();
+    let expected = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("is_big".to_owned(), Arc::new(Value::int(0))),
+            ("origin".to_owned(), Arc::new(point())),
+            ("len".to_owned(), Arc::new(Value::int(0))),
+            ("data".to_owned(), Arc::new(Value::ArrayTerm(Vec::new()))),
+            (
+                "points".to_owned(),
+                Arc::new(Value::ArrayTerm(vec![Arc::new(point()), Arc::new(point())])),
+            ),
+            ("line".to_owned(), Arc::new(Value::ArrayTerm(Vec::new()))),
+    ]));
+
+    assert!(semantics::is_equal(&globals, &items, &value, &expected));
+}
+
+#[test]
+fn no_default_for_format() {
+    let globals = core::Globals::default();
+    let items = items();
+    let format = Value::item("Point", Vec::new());
+
+    assert!(semantics::repr_default(&globals, &items, &format).is_none());
+}
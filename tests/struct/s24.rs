@@ -0,0 +1,76 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U24Be, U24Le};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/s24.core.fathom");
+
+#[test]
+fn valid_samples() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U24Be>(0x7F_FF_FF); // Samples::big
+    writer.write::<U24Le>(0xFF_FF_FE); // Samples::little
+
+    assert_eq!(writer.buffer(), &[0x7F, 0xFF, 0xFF, 0xFE, 0xFF, 0xFF]);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let samples = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("big".to_owned(), Arc::new(Value::int(8_388_607))),
+        ("little".to_owned(), Arc::new(Value::int(-2))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Samples").unwrap(),
+        (samples.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Samples", &samples)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn min_sample() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U24Be>(0x80_00_00); // Samples::big
+    writer.write::<U24Le>(0x80_00_00); // Samples::little
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let samples = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("big".to_owned(), Arc::new(Value::int(-8_388_608))),
+        ("little".to_owned(), Arc::new(Value::int(-8_388_608))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Samples").unwrap(),
+        (samples, Vec::new()),
+    );
+}
+
+#[test]
+fn write_out_of_range() {
+    let globals = core::Globals::default();
+    let samples = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("big".to_owned(), Arc::new(Value::int(8_388_608))),
+        ("little".to_owned(), Arc::new(Value::int(0))),
+    ]));
+
+    let mut writer = FormatWriter::new(vec![]);
+    assert!(binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "Samples", &samples)
+        .is_err());
+}
@@ -0,0 +1,62 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadError, ReadScope, U8};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/step_limit.core.fathom");
+
+#[test]
+fn pathological_format() {
+    let writer = FormatWriter::new(vec![]);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+    read_context.set_step_limit(Some(1000));
+
+    match read_context.read_item(&mut reader, &"Spin") {
+        Err(ReadError::ReadTimeout { limit: 1000 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn within_limit() {
+    let mut writer = FormatWriter::new(vec![]);
+    for value in 0..4 {
+        writer.write::<U8>(value); // Small::values
+    }
+
+    let globals = core::Globals::default();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+    read_context.set_step_limit(Some(5));
+
+    let values = (0..4).map(|value| Arc::new(Value::int(value))).collect();
+    let small = Value::StructTerm(BTreeMap::from_iter(vec![(
+        "values".to_owned(),
+        Arc::new(Value::ArrayTerm(values)),
+    )]));
+
+    // The limit applies to each item separately
+    for _ in 0..2 {
+        let mut reader = ReadScope::new(writer.buffer()).reader();
+        fathom_test_util::assert_is_equal!(
+            globals,
+            read_context.read_item(&mut reader, &"Small").unwrap(),
+            (small.clone(), Vec::new()),
+        );
+    }
+
+    read_context.set_step_limit(Some(4));
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    match read_context.read_item(&mut reader, &"Small") {
+        Err(ReadError::ReadTimeout { limit: 4 }) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(test)]
+
+use fathom_runtime::{FormatWriter, ReadScope, U24Be, U24Le};
+use fathom_test_util::fathom::lang::core::semantics::Value;
+use fathom_test_util::fathom::lang::core::{self, binary};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+fathom_test_util::core_module!(FIXTURE, "./snapshots/u24.core.fathom");
+
+#[test]
+fn valid_offsets() {
+    let mut writer = FormatWriter::new(vec![]);
+    writer.write::<U24Be>(0x01_02_03); // Offsets::big
+    writer.write::<U24Le>(0xFF_FE_FD); // Offsets::little
+
+    assert_eq!(writer.buffer(), &[0x01, 0x02, 0x03, 0xFD, 0xFE, 0xFF]);
+
+    let globals = core::Globals::default();
+    let mut reader = ReadScope::new(writer.buffer()).reader();
+    let mut read_context = binary::read::Context::new(&globals, &FIXTURE);
+
+    let offsets = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("big".to_owned(), Arc::new(Value::int(0x01_02_03))),
+        ("little".to_owned(), Arc::new(Value::int(0xFF_FE_FD))),
+    ]));
+
+    fathom_test_util::assert_is_equal!(
+        globals,
+        read_context.read_item(&mut reader, &"Offsets").unwrap(),
+        (offsets.clone(), Vec::new()),
+    );
+
+    let mut round_trip = FormatWriter::new(vec![]);
+    binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut round_trip, "Offsets", &offsets)
+        .unwrap();
+    assert_eq!(round_trip.buffer(), writer.buffer());
+}
+
+#[test]
+fn write_out_of_range() {
+    let globals = core::Globals::default();
+    let offsets = Value::StructTerm(BTreeMap::from_iter(vec![
+        ("big".to_owned(), Arc::new(Value::int(0x01_00_00_00))),
+        ("little".to_owned(), Arc::new(Value::int(0))),
+    ]));
+
+    let mut writer = FormatWriter::new(vec![]);
+    assert!(binary::write::Context::new(&globals, &FIXTURE)
+        .write_item(&mut writer, "Offsets", &offsets)
+        .is_err());
+}